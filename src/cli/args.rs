@@ -21,6 +21,14 @@ pub struct Args {
     #[arg(short, long)]
     pub list: bool,
 
+    /// Only list instances in this group
+    #[arg(long, requires = "list")]
+    pub group: Option<String>,
+
+    /// Only list instances with this tag
+    #[arg(long, requires = "list")]
+    pub tag: Option<String>,
+
     /// Enable verbose logging
     #[arg(short, long)]
     pub verbose: bool,
@@ -42,6 +50,61 @@ pub enum Commands {
         /// Mod loader (vanilla, fabric, forge)
         #[arg(short, long, default_value = "vanilla")]
         loader: String,
+        /// Template to start from
+        #[arg(short, long)]
+        template: Option<String>,
+    },
+
+    /// Duplicate an instance
+    Clone {
+        /// Instance to copy
+        source: String,
+        /// Name of the copy
+        name: String,
+        /// Leave worlds out of the copy
+        #[arg(long)]
+        no_saves: bool,
+        /// Always copy files instead of hardlinking mod jars and packs
+        #[arg(long)]
+        no_links: bool,
+    },
+
+    /// Back up an instance's worlds
+    Backup {
+        /// Instance name
+        instance: String,
+        /// Only back up this world
+        #[arg(short, long)]
+        world: Option<String>,
+        /// List existing backups instead of creating one
+        #[arg(short, long)]
+        list: bool,
+        /// Back up even if the game is running
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Restore a world from a backup
+    Restore {
+        /// Instance name
+        instance: String,
+        /// Backup file name, or <world>/<file name> (see `backup --list`)
+        backup: String,
+        /// Restore even if the game is running
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Snapshot and roll back an instance's mods and config
+    Snapshot {
+        #[command(subcommand)]
+        action: SnapshotAction,
+    },
+
+    /// Manage templates for new instances
+    Template {
+        #[command(subcommand)]
+        action: TemplateAction,
     },
 
     /// Manage authentication
@@ -50,10 +113,174 @@ pub enum Commands {
         action: AuthAction,
     },
 
+    /// Manage Java installations
+    Java {
+        #[command(subcommand)]
+        action: JavaAction,
+    },
+
+    /// Import a modpack or another launcher's instance
+    Import {
+        /// Modpack (.mrpack or CurseForge .zip), MultiMC/Prism instance
+        /// folder, or official launcher folder
+        path: String,
+        /// Instance name (defaults to the pack or instance name)
+        #[arg(short, long)]
+        name: Option<String>,
+        /// Official launcher profile to import (name or ID)
+        #[arg(short, long)]
+        profile: Option<String>,
+        /// Link to the original game folder instead of copying it
+        #[arg(long)]
+        symlink: bool,
+    },
+
+    /// Export an instance as a modpack
+    Export {
+        /// Instance name
+        instance: String,
+        /// Output file (defaults to <instance>.<format>)
+        output: Option<String>,
+        /// Archive format: mrpack or zip (defaults to the output extension, then mrpack)
+        #[arg(short, long)]
+        format: Option<String>,
+        /// Game folder entries to put in a zip (default: mods, config,
+        /// resourcepacks, shaderpacks, saves, options.txt)
+        #[arg(short, long, value_delimiter = ',')]
+        include: Vec<String>,
+    },
+
+    /// Manage instance mods
+    Mods {
+        #[command(subcommand)]
+        action: ModsAction,
+    },
+
     /// Check for updates
     Update,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ModsAction {
+    /// Search for mods compatible with an instance
+    Search {
+        /// Instance name
+        instance: String,
+        /// Search text
+        query: String,
+        /// Maximum number of results
+        #[arg(short, long, default_value = "10")]
+        limit: u32,
+        /// Mod source (modrinth, curseforge)
+        #[arg(short, long, default_value = "modrinth")]
+        source: String,
+    },
+    /// Install a project and its required dependencies
+    Add {
+        /// Instance name
+        instance: String,
+        /// Project ID or slug
+        project: String,
+        /// Mod source (modrinth, curseforge)
+        #[arg(short, long, default_value = "modrinth")]
+        source: String,
+    },
+    /// Show which installed jars a mod source recognises
+    Identify {
+        /// Instance name
+        instance: String,
+        /// Mod source (modrinth, curseforge)
+        #[arg(short, long, default_value = "modrinth")]
+        source: String,
+    },
+    /// List mods with newer versions on Modrinth
+    Update {
+        /// Instance name
+        instance: String,
+        /// Install the updates (old jars are kept as .jar.disabled)
+        #[arg(long)]
+        apply: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum JavaAction {
+    /// List detected Java installations
+    List,
+    /// Download a managed Java runtime
+    Install {
+        /// Java major version (e.g. 17)
+        major: u32,
+    },
+    /// Remove managed Java runtimes of a major version
+    Remove {
+        /// Java major version (e.g. 17)
+        major: u32,
+    },
+    /// Set the Java used for all instances (empty to auto-detect)
+    Default {
+        /// Path to a java executable
+        path: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotAction {
+    /// Snapshot mods, config and instance settings
+    Create {
+        /// Instance name
+        instance: String,
+        /// Snapshot name
+        name: String,
+    },
+    /// List snapshots
+    List {
+        /// Instance name
+        instance: String,
+    },
+    /// Show what changed since a snapshot
+    Diff {
+        /// Instance name
+        instance: String,
+        /// Snapshot to compare from
+        name: String,
+        /// Snapshot to compare to (defaults to the current files)
+        other: Option<String>,
+    },
+    /// Roll mods, config and instance settings back to a snapshot
+    Restore {
+        /// Instance name
+        instance: String,
+        /// Snapshot name
+        name: String,
+    },
+    /// Delete a snapshot
+    Delete {
+        /// Instance name
+        instance: String,
+        /// Snapshot name
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum TemplateAction {
+    /// Save an instance's settings, mods and options.txt as a template
+    Save {
+        /// Instance name
+        instance: String,
+        /// Template name
+        name: String,
+    },
+    /// List templates
+    List,
+    /// Delete a template
+    Delete {
+        /// Template name
+        name: String,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum AuthAction {
     /// Login with Microsoft account
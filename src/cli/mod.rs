@@ -4,11 +4,28 @@
 
 mod args;
 
-pub use args::{Args, AuthAction, Commands};
+pub use args::{
+    Args, AuthAction, Commands, JavaAction, ModsAction, SnapshotAction, TemplateAction,
+};
 
+use crate::config;
 use crate::core::auth::{AccountManager, AccountType};
-use crate::core::instance::{InstanceManager, ModLoader};
+use crate::core::backup::BackupManager;
+use crate::core::instance::{
+    CloneOptions, GameDirMode, InstanceManager, ModLoader, format_playtime, groups,
+    import_external, read_external, running_game_pid, sort_instances,
+};
+use crate::core::java::{JavaManager, JavaProbe};
 use crate::core::launch::{LaunchResult, launch_instance_async};
+use crate::core::modpack::{self, ExportFormat};
+use crate::core::mods::{
+    CurseForgeClient, ModManager, ModProvider, ModSource, ModrinthClient, SearchQuery,
+    apply_update, check_updates, format_size, install_project, mod_jars,
+};
+use crate::core::snapshot::{SnapshotDiff, SnapshotManager};
+use crate::core::template::{
+    InstanceTemplate, TemplateManager, install_template_mods, template_mods,
+};
 use anyhow::{Context, Result};
 
 /// Launch a specific instance directly
@@ -48,10 +65,11 @@ pub async fn run_instance(name: &str, _offline: bool) -> Result<()> {
     })
     .await?
     {
-        LaunchResult::Success(mut child) => {
-            println!("✅ Minecraft started (PID: {:?})", child.id());
+        LaunchResult::Success(session) => {
+            println!("✅ Minecraft started (PID: {})", session.pid());
             // Wait for process
-            let _ = child.wait()?;
+            let played = session.wait()?;
+            println!("⏱️  Played for {}", format_playtime(played.as_secs()));
         }
         LaunchResult::EarlyExit(code) => {
             println!("❌ Minecraft exited early with code: {:?}", code);
@@ -63,9 +81,9 @@ pub async fn run_instance(name: &str, _offline: bool) -> Result<()> {
 }
 
 /// List all available instances
-pub fn list_instances() -> Result<()> {
+pub fn list_instances(group: Option<&str>, tag: Option<&str>) -> Result<()> {
     let instance_manager = InstanceManager::new();
-    let instances = instance_manager.list()?;
+    let mut instances = instance_manager.list()?;
 
     if instances.is_empty() {
         println!("📦 No instances found.");
@@ -73,23 +91,64 @@ pub fn list_instances() -> Result<()> {
         return Ok(());
     }
 
+    instances.retain(|i| {
+        group.is_none_or(|g| i.info.in_group(g)) && tag.is_none_or(|t| i.info.has_tag(t))
+    });
+    if instances.is_empty() {
+        println!("📦 No instances match.");
+        return Ok(());
+    }
+    sort_instances(
+        &mut instances,
+        config::load().unwrap_or_default().general.instance_sort,
+    );
+
     println!("📦 Instances ({}):", instances.len());
-    println!();
 
-    for instance in &instances {
-        let loader = format!("{}", instance.info.loader);
-        println!(
-            "   {} - {} ({})",
-            instance.info.name, instance.info.version, loader
-        );
+    let mut sections = vec![String::new()];
+    sections.extend(groups(&instances));
+    for section in sections {
+        let members: Vec<_> = instances
+            .iter()
+            .filter(|i| i.info.in_group(&section))
+            .collect();
+        if members.is_empty() {
+            continue;
+        }
+        println!();
+        if !section.is_empty() {
+            println!("   📁 {}", section);
+        }
+        for instance in members {
+            let star = if instance.info.favorite { "⭐ " } else { "" };
+            let tags: String = instance
+                .info
+                .tags
+                .iter()
+                .map(|t| format!(" #{}", t))
+                .collect();
+            println!(
+                "   {}{} - {} ({}){}",
+                star, instance.info.name, instance.info.version, instance.info.loader, tags
+            );
+            println!("      {}", instance.info.play_stats());
+        }
     }
 
     Ok(())
 }
 
 /// Create a new instance from CLI
-pub async fn create_instance(name: &str, version: &str, loader: &str) -> Result<()> {
+pub async fn create_instance(
+    name: &str,
+    version: &str,
+    loader: &str,
+    template: Option<&str>,
+) -> Result<()> {
     let instance_manager = InstanceManager::new();
+    let template = template
+        .map(|t| TemplateManager::new().load(t))
+        .transpose()?;
 
     let mod_loader = match loader.to_lowercase().as_str() {
         "vanilla" => ModLoader::Vanilla,
@@ -102,7 +161,39 @@ pub async fn create_instance(name: &str, version: &str, loader: &str) -> Result<
     println!("   Version: {}", version);
     println!("   Loader: {}", loader);
 
-    instance_manager.create(name, version, mod_loader, None)?;
+    let mut instance = instance_manager.create(name, version, mod_loader, None)?;
+
+    if let Some(template) = &template {
+        println!("   Template: {}", template.name);
+        instance_manager.apply_template(&mut instance, template)?;
+
+        if !template.mods.is_empty() {
+            let mods_dir = instance_manager
+                .get_game_dir(&instance.info.id)
+                .join("mods");
+            let report = install_template_mods(
+                &ModrinthClient::new(),
+                template,
+                &mods_dir,
+                &instance,
+                &|msg| println!("   {}", msg),
+            )
+            .await;
+            for file in &report.installed {
+                println!("   + {} ({})", file.filename, file.version_number);
+            }
+            for file in &report.manual {
+                println!(
+                    "   ⚠️  Download {} by hand: {}",
+                    file.filename,
+                    file.page_url.as_deref().unwrap_or("")
+                );
+            }
+            for (project, error) in &report.failed {
+                println!("   ⚠️  Skipped {}: {}", project, error);
+            }
+        }
+    }
 
     println!("✅ Instance '{}' created successfully!", name);
     println!("   Use 'glauncher -i {}' to launch.", name);
@@ -110,6 +201,250 @@ pub async fn create_instance(name: &str, version: &str, loader: &str) -> Result<
     Ok(())
 }
 
+/// Handle template commands
+pub async fn handle_template(action: TemplateAction) -> Result<()> {
+    let template_manager = TemplateManager::new();
+    match action {
+        TemplateAction::Save { instance, name } => {
+            let instance_manager = InstanceManager::new();
+            let instance = instance_manager
+                .load(&instance)
+                .context(format!("Instance '{}' not found", instance))?;
+            let game_dir = instance_manager.get_game_dir(&instance.info.id);
+
+            let mut template = InstanceTemplate::from_instance(&name, &instance, &game_dir)?;
+            let (projects, unknown) =
+                template_mods(&ModrinthClient::new(), &game_dir.join("mods")).await?;
+            template.mods = projects;
+            template_manager.save(&template)?;
+
+            println!(
+                "📋 Template '{}' saved from '{}' ({} mods)",
+                template.name,
+                instance.info.name,
+                template.mods.len()
+            );
+            if !unknown.is_empty() {
+                println!("⚠️  Not found on Modrinth, left out of the template:");
+                for file in &unknown {
+                    println!("   - {}", file);
+                }
+            }
+            Ok(())
+        }
+        TemplateAction::List => {
+            let templates = template_manager.list();
+            if templates.is_empty() {
+                println!("📋 No templates.");
+                println!("   Use 'glauncher template save <instance> <name>' to create one.");
+                return Ok(());
+            }
+            println!("📋 Templates ({}):", templates.len());
+            for template in &templates {
+                let memory = if template.max_memory.is_empty() {
+                    "default memory".to_string()
+                } else {
+                    template.max_memory.clone()
+                };
+                println!(
+                    "   {}  {}x{}  {}  {} mods",
+                    template.name,
+                    template.game.resolution_width,
+                    template.game.resolution_height,
+                    memory,
+                    template.mods.len()
+                );
+            }
+            Ok(())
+        }
+        TemplateAction::Delete { name } => {
+            template_manager.delete(&name)?;
+            println!("🗑️  Deleted template '{}'.", name);
+            Ok(())
+        }
+    }
+}
+
+/// Duplicate an instance
+pub fn clone_instance(
+    source: &str,
+    name: &str,
+    include_saves: bool,
+    link_files: bool,
+) -> Result<()> {
+    println!("📋 Cloning '{}' to '{}'...", source, name);
+
+    let options = CloneOptions {
+        include_saves,
+        link_files,
+    };
+    InstanceManager::new().clone_instance(source, name, &options)?;
+
+    println!("✅ Instance '{}' created.", name);
+    Ok(())
+}
+
+/// Backup manager for an existing instance, using the configured retention
+fn backup_manager(name: &str, force: bool) -> Result<BackupManager> {
+    let instance_manager = InstanceManager::new();
+    let instance = instance_manager.load(name)?;
+    let keep = config::load().unwrap_or_default().backups.keep;
+    Ok(
+        BackupManager::new(&instance_manager.get_instance_dir(&instance.info.id))
+            .keep(keep)
+            .allow_running(force),
+    )
+}
+
+/// Back up one or all worlds of an instance
+pub fn backup_worlds(name: &str, world: Option<&str>, force: bool) -> Result<()> {
+    let manager = backup_manager(name, force)?;
+    if force && running_game_pid(manager.instance_dir()).is_some() {
+        println!("⚠️  Minecraft is running; the backup may catch worlds mid-save.");
+    }
+
+    let backups = match world {
+        Some(world) => vec![manager.backup_world(world)?],
+        None => manager.backup_all()?,
+    };
+    if backups.is_empty() {
+        println!("🌍 No worlds to back up in '{}'.", name);
+        return Ok(());
+    }
+
+    for backup in &backups {
+        println!(
+            "✅ {} → {} ({})",
+            backup.world,
+            backup.path.display(),
+            format_size(backup.size)
+        );
+    }
+    Ok(())
+}
+
+/// List world backups of an instance
+pub fn list_backups(name: &str, world: Option<&str>) -> Result<()> {
+    let backups = backup_manager(name, false)?.list(world)?;
+    if backups.is_empty() {
+        println!("💾 No backups for '{}'.", name);
+        return Ok(());
+    }
+
+    println!("💾 Backups ({}):", backups.len());
+    for backup in &backups {
+        println!(
+            "   {}/{}  {}  {}",
+            backup.world,
+            backup.file_name(),
+            backup.created_at.format("%Y-%m-%d %H:%M:%S"),
+            format_size(backup.size)
+        );
+    }
+    Ok(())
+}
+
+/// Restore a world from a backup
+pub fn restore_backup(name: &str, id: &str, force: bool) -> Result<()> {
+    let manager = backup_manager(name, force)?;
+    let backup = manager.find(id)?;
+
+    println!(
+        "♻️  Restoring {} from {}...",
+        backup.world,
+        backup.file_name()
+    );
+    manager.restore(&backup)?;
+    println!("✅ World '{}' restored.", backup.world);
+    Ok(())
+}
+
+/// Handle snapshot subcommands
+pub fn handle_snapshot(action: SnapshotAction) -> Result<()> {
+    match action {
+        SnapshotAction::Create { instance, name } => {
+            let snapshot = snapshot_manager(&instance)?.create(&name)?;
+            println!(
+                "📸 Snapshot '{}' created ({} files, {})",
+                snapshot.name,
+                snapshot.files.len(),
+                format_size(snapshot.size())
+            );
+            Ok(())
+        }
+        SnapshotAction::List { instance } => {
+            let snapshots = snapshot_manager(&instance)?.list()?;
+            if snapshots.is_empty() {
+                println!("📸 No snapshots for '{}'.", instance);
+                return Ok(());
+            }
+            println!("📸 Snapshots ({}):", snapshots.len());
+            for snapshot in &snapshots {
+                println!(
+                    "   {}  {}  {} files",
+                    snapshot.name,
+                    snapshot
+                        .created_at
+                        .with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S"),
+                    snapshot.files.len()
+                );
+            }
+            Ok(())
+        }
+        SnapshotAction::Diff {
+            instance,
+            name,
+            other,
+        } => {
+            let diff = snapshot_manager(&instance)?.diff(&name, other.as_deref())?;
+            let target = other.as_deref().unwrap_or("current files");
+            if diff.is_empty() {
+                println!("✅ No changes between '{}' and {}.", name, target);
+            } else {
+                println!("🔍 Changes from '{}' to {}:", name, target);
+                print_snapshot_diff(&diff);
+            }
+            Ok(())
+        }
+        SnapshotAction::Restore { instance, name } => {
+            let diff = snapshot_manager(&instance)?.restore(&name)?;
+            if diff.is_empty() {
+                println!("✅ '{}' already matches snapshot '{}'.", instance, name);
+            } else {
+                print_snapshot_diff(&diff);
+                println!("✅ Restored snapshot '{}'.", name);
+            }
+            Ok(())
+        }
+        SnapshotAction::Delete { instance, name } => {
+            snapshot_manager(&instance)?.delete(&name)?;
+            println!("🗑️  Deleted snapshot '{}'.", name);
+            Ok(())
+        }
+    }
+}
+
+fn snapshot_manager(name: &str) -> Result<SnapshotManager> {
+    let instance_manager = InstanceManager::new();
+    let instance = instance_manager.load(name)?;
+    Ok(SnapshotManager::new(
+        &instance_manager.get_instance_dir(&instance.info.id),
+    ))
+}
+
+fn print_snapshot_diff(diff: &SnapshotDiff) {
+    for path in &diff.added {
+        println!("   + {}", path);
+    }
+    for path in &diff.removed {
+        println!("   - {}", path);
+    }
+    for path in &diff.changed {
+        println!("   ~ {}", path);
+    }
+}
+
 /// Add an offline account
 pub fn add_offline_account(username: &str) -> Result<()> {
     let mut manager = AccountManager::new()?;
@@ -208,3 +543,461 @@ fn auth_status() -> Result<()> {
 
     Ok(())
 }
+
+/// Handle java subcommands
+pub async fn handle_java(action: JavaAction) -> Result<()> {
+    match action {
+        JavaAction::List => java_list(),
+        JavaAction::Install { major } => java_install(major).await,
+        JavaAction::Remove { major } => java_remove(major),
+        JavaAction::Default { path } => java_default(&path),
+    }
+}
+
+/// Create a Java manager using the configured download provider
+fn java_manager() -> JavaManager {
+    let config = config::load().unwrap_or_default();
+    JavaManager::new(&config::config_dir()).with_provider(config.java.provider)
+}
+
+/// List detected Java installations
+fn java_list() -> Result<()> {
+    let registry = java_manager().registry();
+    let installations = registry.installations();
+    let default_path = config::load().unwrap_or_default().java.path;
+
+    if installations.is_empty() {
+        println!("☕ No Java installations found.");
+        println!("   Use 'glauncher java install <major>' to download one.");
+        return Ok(());
+    }
+
+    println!("☕ Java installations ({}):", installations.len());
+    for java in installations {
+        let managed = if java.managed { " [managed]" } else { "" };
+        let default = if !default_path.is_empty()
+            && std::path::Path::new(&default_path).canonicalize().ok() == Some(java.path.clone())
+        {
+            " ✓ default"
+        } else {
+            ""
+        };
+        println!(
+            "   Java {} ({}, {}, {}){}{}",
+            java.major_version, java.version, java.vendor, java.arch, managed, default
+        );
+        println!("      {}", java.path.display());
+    }
+
+    Ok(())
+}
+
+/// Download a managed Java runtime
+async fn java_install(major: u32) -> Result<()> {
+    println!("☕ Installing Java {}...", major);
+
+    let java_path = java_manager()
+        .install_managed(major, |msg| {
+            println!("   {}", msg);
+        })
+        .await?;
+
+    println!("✅ Java {} installed at {}", major, java_path.display());
+    Ok(())
+}
+
+/// Remove managed Java runtimes of a major version
+fn java_remove(major: u32) -> Result<()> {
+    let removed = java_manager().remove_managed(major)?;
+
+    if removed.is_empty() {
+        println!("No managed Java {} installation found.", major);
+        return Ok(());
+    }
+
+    for dir in &removed {
+        println!("🗑️  Removed {}", dir.display());
+    }
+    println!("✅ Removed {} Java {} runtime(s).", removed.len(), major);
+    Ok(())
+}
+
+/// Set the global Java executable
+fn java_default(path: &str) -> Result<()> {
+    let mut config = config::load()?;
+
+    if path.is_empty() {
+        config.java.path.clear();
+        config::save(&config)?;
+        println!("✅ Java will be selected automatically.");
+        return Ok(());
+    }
+
+    let probe = JavaProbe::run(std::path::Path::new(path))
+        .context(format!("'{}' is not a working java executable", path))?;
+
+    config.java.path = path.to_string();
+    config::save(&config)?;
+
+    println!(
+        "✅ Default Java set to {} (Java {}, {})",
+        path, probe.version, probe.vendor
+    );
+    Ok(())
+}
+
+/// Handle mods subcommands
+pub async fn handle_mods(action: ModsAction) -> Result<()> {
+    match action {
+        ModsAction::Search {
+            instance,
+            query,
+            limit,
+            source,
+        } => match source.parse()? {
+            ModSource::Modrinth => {
+                mods_search(&ModrinthClient::new(), &instance, &query, limit).await
+            }
+            ModSource::CurseForge => {
+                mods_search(&CurseForgeClient::from_config()?, &instance, &query, limit).await
+            }
+        },
+        ModsAction::Add {
+            instance,
+            project,
+            source,
+        } => match source.parse()? {
+            ModSource::Modrinth => mods_add(&ModrinthClient::new(), &instance, &project).await,
+            ModSource::CurseForge => {
+                mods_add(&CurseForgeClient::from_config()?, &instance, &project).await
+            }
+        },
+        ModsAction::Identify { instance, source } => match source.parse()? {
+            ModSource::Modrinth => mods_identify(&ModrinthClient::new(), &instance).await,
+            ModSource::CurseForge => {
+                mods_identify(&CurseForgeClient::from_config()?, &instance).await
+            }
+        },
+        ModsAction::Update { instance, apply } => mods_update(&instance, apply).await,
+    }
+}
+
+/// Search a mod source for mods matching an instance's version and loader
+async fn mods_search<P: ModProvider + Sync>(
+    provider: &P,
+    name: &str,
+    query: &str,
+    limit: u32,
+) -> Result<()> {
+    let instance = InstanceManager::new()
+        .load(name)
+        .context(format!("Instance '{}' not found", name))?;
+
+    let hits = provider
+        .search(&SearchQuery {
+            query: query.to_string(),
+            mc_version: instance.info.version.clone(),
+            loader: instance.info.loader.clone(),
+            limit,
+        })
+        .await?;
+
+    if hits.is_empty() {
+        println!(
+            "🔍 No mods found on {} for Minecraft {} ({}).",
+            provider.name(),
+            instance.info.version,
+            instance.info.loader
+        );
+        return Ok(());
+    }
+
+    println!(
+        "🔍 {} results for '{}' ({} {}):",
+        provider.name(),
+        query,
+        instance.info.version,
+        instance.info.loader
+    );
+    for hit in hits {
+        println!(
+            "   {} [{}] by {} ({} downloads)",
+            hit.title, hit.slug, hit.author, hit.downloads
+        );
+        println!("      {}", hit.description);
+    }
+    println!();
+    println!("   Use 'glauncher mods add {} <slug>' to install.", name);
+
+    Ok(())
+}
+
+/// Install a project into an instance
+async fn mods_add<P: ModProvider + Sync>(provider: &P, name: &str, project: &str) -> Result<()> {
+    let instance_manager = InstanceManager::new();
+    let instance = instance_manager
+        .load(name)
+        .context(format!("Instance '{}' not found", name))?;
+
+    if instance.info.loader == ModLoader::Vanilla {
+        anyhow::bail!("Instance '{}' has no mod loader", name);
+    }
+
+    println!(
+        "📦 Installing {} from {} into {}...",
+        project,
+        provider.name(),
+        name
+    );
+    let mods_dir = instance_manager
+        .get_game_dir(&instance.info.id)
+        .join("mods");
+    let report = install_project(
+        provider,
+        project,
+        &mods_dir,
+        &instance.info.version,
+        &instance.info.loader,
+        &|msg| println!("   {}", msg),
+    )
+    .await?;
+
+    if report.installed.is_empty() && report.manual.is_empty() {
+        println!("✅ {} is already installed.", project);
+        return Ok(());
+    }
+
+    for file in &report.installed {
+        println!("   + {} ({})", file.filename, file.version_number);
+    }
+    println!("✅ Installed {} file(s).", report.installed.len());
+
+    if !report.manual.is_empty() {
+        println!();
+        println!("⚠️  These authors do not allow third-party downloads.");
+        println!("   Download the files by hand and put them in:");
+        println!("   {}", mods_dir.display());
+        for file in &report.manual {
+            println!("   - {}", file.filename);
+            if let Some(url) = &file.page_url {
+                println!("     {}", url);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Show which installed jars a mod source recognises
+async fn mods_identify<P: ModProvider + Sync>(provider: &P, name: &str) -> Result<()> {
+    let instance_manager = InstanceManager::new();
+    let instance = instance_manager
+        .load(name)
+        .context(format!("Instance '{}' not found", name))?;
+
+    let jars = mod_jars(
+        &instance_manager
+            .get_game_dir(&instance.info.id)
+            .join("mods"),
+    );
+    if jars.is_empty() {
+        println!("📦 No mods installed.");
+        return Ok(());
+    }
+
+    let identified = provider.identify(&jars).await?;
+    println!(
+        "📦 {} of {} jar(s) recognised by {}:",
+        identified.len(),
+        jars.len(),
+        provider.name()
+    );
+    for jar in &jars {
+        let filename = jar.file_name().unwrap_or_default().to_string_lossy();
+        match identified.get(jar) {
+            Some(file) => println!(
+                "   ✓ {} → project {} ({})",
+                filename, file.project_id, file.version_number
+            ),
+            None => println!("   ? {}", filename),
+        }
+    }
+
+    Ok(())
+}
+
+/// List and optionally apply mod updates for an instance
+async fn mods_update(name: &str, apply: bool) -> Result<()> {
+    let instance_manager = InstanceManager::new();
+    let instance = instance_manager
+        .load(name)
+        .context(format!("Instance '{}' not found", name))?;
+
+    let mods_dir = instance_manager
+        .get_game_dir(&instance.info.id)
+        .join("mods");
+    let mods = ModManager::new(&mods_dir).list_mods()?;
+
+    println!("🔍 Checking {} mod(s) for updates...", mods.len());
+    let updates = check_updates(
+        &ModrinthClient::new(),
+        &mods,
+        &instance.info.version,
+        &instance.info.loader,
+    )
+    .await?;
+
+    if updates.is_empty() {
+        println!("✅ All mods are up to date.");
+        return Ok(());
+    }
+
+    for update in &updates {
+        println!(
+            "   {} {} → {}",
+            update.name, update.current_version, update.file.version_number
+        );
+    }
+
+    if !apply {
+        println!();
+        println!(
+            "   Use 'glauncher mods update {} --apply' to install.",
+            name
+        );
+        return Ok(());
+    }
+
+    let client = reqwest::Client::new();
+    for update in &updates {
+        println!("   Downloading {}...", update.file.filename);
+        apply_update(&client, update, &mods_dir).await?;
+    }
+    println!(
+        "✅ Updated {} mod(s). Old versions were kept as .jar.disabled.",
+        updates.len()
+    );
+
+    Ok(())
+}
+
+/// Import a modpack as a new instance
+pub async fn import_pack(path: &str, name: Option<&str>) -> Result<()> {
+    println!("📥 Importing {}...", path);
+
+    let report = modpack::import_pack(
+        &InstanceManager::new(),
+        std::path::Path::new(path),
+        name,
+        &|msg| println!("   {}", msg),
+    )
+    .await?;
+
+    println!(
+        "✅ Created instance '{}' ({} downloaded, {} from overrides)",
+        report.instance_name, report.downloaded, report.overrides
+    );
+
+    if !report.manual.is_empty() {
+        println!();
+        println!("⚠️  These authors do not allow third-party downloads.");
+        println!("   Download the files by hand and put them in the listed folder:");
+        for file in &report.manual {
+            println!("   - {} ({})", file.name, file.filename);
+            println!("     {}", file.page_url);
+            println!("     → {}", file.dest_dir.display());
+        }
+    }
+    Ok(())
+}
+
+/// Import an instance from MultiMC, Prism Launcher or the official launcher
+pub fn import_instance(
+    path: &str,
+    name: Option<&str>,
+    profile: Option<&str>,
+    symlink: bool,
+) -> Result<()> {
+    let external = read_external(std::path::Path::new(path), profile)?;
+    println!(
+        "📥 Importing {} (Minecraft {}, {}) from {}...",
+        external.name,
+        external.version,
+        external.loader,
+        external.game_dir.display()
+    );
+
+    let mode = if symlink {
+        GameDirMode::Symlink
+    } else {
+        GameDirMode::Copy
+    };
+    let instance = import_external(&InstanceManager::new(), &external, name, mode)?;
+
+    println!("✅ Created instance '{}'", instance.info.name);
+    if symlink {
+        println!("   The game folder is shared with the original launcher.");
+    }
+    Ok(())
+}
+
+/// Export an instance as a modpack or portable archive
+pub async fn export_instance(
+    name: &str,
+    output: Option<&str>,
+    format: Option<&str>,
+    include: &[String],
+) -> Result<()> {
+    let instance_manager = InstanceManager::new();
+    if !instance_manager.exists(name) {
+        anyhow::bail!("Instance '{}' not found", name);
+    }
+
+    let format = match format {
+        Some(format) => format.parse()?,
+        None => output
+            .and_then(|o| ExportFormat::from_path(std::path::Path::new(o)))
+            .unwrap_or(ExportFormat::Mrpack),
+    };
+    let output = output
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| format!("{}.{}", name, format.extension()).into());
+    println!("📤 Exporting {} to {}...", name, output.display());
+
+    match format {
+        ExportFormat::Mrpack => {
+            let report = modpack::export_mrpack(
+                &instance_manager,
+                name,
+                &output,
+                &ModrinthClient::new(),
+                &|msg| println!("   {}", msg),
+            )
+            .await?;
+
+            println!(
+                "✅ Exported {} ({} Modrinth file(s), {} in overrides)",
+                output.display(),
+                report.indexed,
+                report.overrides
+            );
+        }
+        ExportFormat::Zip => {
+            let include: Vec<&str> = if include.is_empty() {
+                modpack::DEFAULT_ARCHIVE_CONTENT.to_vec()
+            } else {
+                include.iter().map(String::as_str).collect()
+            };
+            let report =
+                modpack::export_archive(&instance_manager, name, &output, &include, &|_| {})?;
+
+            println!(
+                "✅ Exported {} ({} file(s): {})",
+                output.display(),
+                report.overrides,
+                include.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
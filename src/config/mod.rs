@@ -4,7 +4,9 @@
 
 mod schema;
 
-pub use schema::{Config, GeneralConfig, JavaConfig, NetworkConfig};
+pub use schema::{
+    BackupConfig, Config, GeneralConfig, InstanceSort, JavaConfig, JavaProviderKind, NetworkConfig,
+};
 
 use anyhow::Result;
 use std::path::PathBuf;
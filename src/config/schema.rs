@@ -15,6 +15,12 @@ pub struct Config {
 
     #[serde(default)]
     pub network: NetworkConfig,
+
+    #[serde(default)]
+    pub mods: ModsConfig,
+
+    #[serde(default)]
+    pub backups: BackupConfig,
 }
 
 impl Default for Config {
@@ -23,6 +29,8 @@ impl Default for Config {
             general: GeneralConfig::default(),
             java: JavaConfig::default(),
             network: NetworkConfig::default(),
+            mods: ModsConfig::default(),
+            backups: BackupConfig::default(),
         }
     }
 }
@@ -45,6 +53,10 @@ pub struct GeneralConfig {
     /// Close launcher after game starts
     #[serde(default)]
     pub close_on_launch: bool,
+
+    /// Instance list order
+    #[serde(default)]
+    pub instance_sort: InstanceSort,
 }
 
 impl Default for GeneralConfig {
@@ -54,6 +66,44 @@ impl Default for GeneralConfig {
             language: default_language(),
             check_updates: true,
             close_on_launch: false,
+            instance_sort: InstanceSort::default(),
+        }
+    }
+}
+
+/// Instance list order; favourites always come first
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum InstanceSort {
+    /// Newest first
+    #[default]
+    Created,
+    Name,
+    /// Most recently played first
+    LastPlayed,
+    /// Newest Minecraft version first
+    Version,
+    Loader,
+}
+
+impl InstanceSort {
+    pub const ALL: [InstanceSort; 5] = [
+        InstanceSort::Created,
+        InstanceSort::Name,
+        InstanceSort::LastPlayed,
+        InstanceSort::Version,
+        InstanceSort::Loader,
+    ];
+}
+
+impl std::fmt::Display for InstanceSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InstanceSort::Created => write!(f, "Date created"),
+            InstanceSort::Name => write!(f, "Name"),
+            InstanceSort::LastPlayed => write!(f, "Last played"),
+            InstanceSort::Version => write!(f, "Minecraft version"),
+            InstanceSort::Loader => write!(f, "Loader"),
         }
     }
 }
@@ -76,6 +126,10 @@ pub struct JavaConfig {
     /// Extra JVM arguments
     #[serde(default)]
     pub extra_args: Vec<String>,
+
+    /// Where to download Java runtimes from
+    #[serde(default)]
+    pub provider: JavaProviderKind,
 }
 
 impl Default for JavaConfig {
@@ -85,6 +139,27 @@ impl Default for JavaConfig {
             min_memory: default_min_memory(),
             max_memory: default_max_memory(),
             extra_args: Vec::new(),
+            provider: JavaProviderKind::default(),
+        }
+    }
+}
+
+/// Java runtime download source
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum JavaProviderKind {
+    /// Mojang's runtimes (same as the official launcher)
+    #[default]
+    Mojang,
+    /// Eclipse Temurin builds from Adoptium
+    Adoptium,
+}
+
+impl std::fmt::Display for JavaProviderKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JavaProviderKind::Mojang => write!(f, "Mojang"),
+            JavaProviderKind::Adoptium => write!(f, "Adoptium"),
         }
     }
 }
@@ -115,6 +190,35 @@ impl Default for NetworkConfig {
     }
 }
 
+/// Mod source settings
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModsConfig {
+    /// CurseForge API key (required for CurseForge downloads)
+    #[serde(default)]
+    pub curseforge_api_key: String,
+}
+
+/// World backup settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupConfig {
+    /// Backups kept per world (0 = keep all)
+    #[serde(default = "default_backup_keep")]
+    pub keep: usize,
+
+    /// Back up every world before launching an instance
+    #[serde(default)]
+    pub before_launch: bool,
+}
+
+impl Default for BackupConfig {
+    fn default() -> Self {
+        Self {
+            keep: default_backup_keep(),
+            before_launch: false,
+        }
+    }
+}
+
 // Default value functions for serde
 fn default_theme() -> String {
     "dark".to_string()
@@ -134,6 +238,9 @@ fn default_max_memory() -> String {
 fn default_concurrent_downloads() -> u32 {
     4
 }
+fn default_backup_keep() -> usize {
+    10
+}
 fn default_timeout() -> u64 {
     30
 }
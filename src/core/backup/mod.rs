@@ -0,0 +1,431 @@
+//! World backups
+//!
+//! Snapshots worlds in an instance's `saves/` folder into timestamped zip
+//! archives under `<instance>/backups/<world>/`, with retention and restore.
+
+use crate::core::instance::running_game_pid;
+use crate::core::modpack::extract_prefix;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+use zip::write::SimpleFileOptions;
+
+/// Backup file name format (local time)
+const TIMESTAMP_FORMAT: &str = "%Y-%m-%d_%H-%M-%S";
+
+/// Lock file Minecraft holds while a world is open
+const SESSION_LOCK: &str = "session.lock";
+
+/// A backup archive of one world
+#[derive(Debug, Clone)]
+pub struct WorldBackup {
+    pub world: String,
+    pub path: PathBuf,
+    pub created_at: DateTime<Local>,
+    /// Archive size in bytes
+    pub size: u64,
+}
+
+impl WorldBackup {
+    /// Archive file name
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// World backup manager for one instance
+pub struct BackupManager {
+    instance_dir: PathBuf,
+    keep: usize,
+    allow_running: bool,
+}
+
+impl BackupManager {
+    /// Create a manager for an instance directory
+    pub fn new(instance_dir: &Path) -> Self {
+        Self {
+            instance_dir: instance_dir.to_path_buf(),
+            keep: 0,
+            allow_running: false,
+        }
+    }
+
+    /// Keep at most `keep` backups per world (0 = keep all)
+    pub fn keep(mut self, keep: usize) -> Self {
+        self.keep = keep;
+        self
+    }
+
+    /// Allow backups and restores while the game is running
+    pub fn allow_running(mut self, allow: bool) -> Self {
+        self.allow_running = allow;
+        self
+    }
+
+    /// Instance directory the backups belong to
+    pub fn instance_dir(&self) -> &Path {
+        &self.instance_dir
+    }
+
+    fn saves_dir(&self) -> PathBuf {
+        self.instance_dir.join(".minecraft").join("saves")
+    }
+
+    fn backups_dir(&self) -> PathBuf {
+        self.instance_dir.join("backups")
+    }
+
+    /// Refuse to touch worlds while the instance's game is running
+    fn check_not_running(&self) -> Result<()> {
+        if let Some(pid) = running_game_pid(&self.instance_dir) {
+            if self.allow_running {
+                tracing::warn!(
+                    "Minecraft is running (PID {}); world files may be inconsistent",
+                    pid
+                );
+            } else {
+                anyhow::bail!(
+                    "Minecraft is running for this instance (PID {}). Close the game first.",
+                    pid
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Worlds in the saves folder
+    pub fn worlds(&self) -> Result<Vec<String>> {
+        let saves = self.saves_dir();
+        if !saves.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut worlds = Vec::new();
+        for entry in std::fs::read_dir(&saves)? {
+            let path = entry?.path();
+            if path.join("level.dat").exists()
+                && let Some(name) = path.file_name().and_then(|n| n.to_str())
+            {
+                worlds.push(name.to_string());
+            }
+        }
+        worlds.sort_by_key(|w| w.to_lowercase());
+        Ok(worlds)
+    }
+
+    /// Back up one world, then apply the retention limit
+    pub fn backup_world(&self, world: &str) -> Result<WorldBackup> {
+        self.check_not_running()?;
+        let world_dir = self.saves_dir().join(world_name(world)?);
+        if !world_dir.join("level.dat").exists() {
+            anyhow::bail!("World '{}' not found", world);
+        }
+
+        let dir = self.backups_dir().join(world);
+        std::fs::create_dir_all(&dir)?;
+
+        let now = Local::now();
+        let stamp = now.format(TIMESTAMP_FORMAT).to_string();
+        let mut path = dir.join(format!("{}.zip", stamp));
+        let mut n = 2;
+        while path.exists() {
+            path = dir.join(format!("{}-{}.zip", stamp, n));
+            n += 1;
+        }
+
+        let tmp = path.with_extension("zip.part");
+        let result = write_world_zip(&world_dir, world, &tmp)
+            .and_then(|_| std::fs::rename(&tmp, &path).context("Failed to save backup"));
+        if let Err(e) = result {
+            let _ = std::fs::remove_file(&tmp);
+            return Err(e);
+        }
+        tracing::info!("Backed up world {} to {}", world, path.display());
+
+        self.prune(world)?;
+        Ok(WorldBackup {
+            world: world.to_string(),
+            size: std::fs::metadata(&path)?.len(),
+            path,
+            created_at: now,
+        })
+    }
+
+    /// Back up every world
+    pub fn backup_all(&self) -> Result<Vec<WorldBackup>> {
+        self.check_not_running()?;
+        self.worlds()?
+            .iter()
+            .map(|world| self.backup_world(world))
+            .collect()
+    }
+
+    /// Backups of one world, or all worlds, newest first
+    pub fn list(&self, world: Option<&str>) -> Result<Vec<WorldBackup>> {
+        let root = self.backups_dir();
+        if !root.exists() {
+            return Ok(Vec::new());
+        }
+
+        let worlds = match world {
+            Some(world) => vec![world_name(world)?.to_string()],
+            None => std::fs::read_dir(&root)?
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_dir())
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .collect(),
+        };
+
+        let mut backups = Vec::new();
+        for world in worlds {
+            let dir = root.join(&world);
+            if !dir.is_dir() {
+                continue;
+            }
+            for entry in std::fs::read_dir(&dir)? {
+                let path = entry?.path();
+                if path.extension().and_then(|e| e.to_str()) != Some("zip") {
+                    continue;
+                }
+                let metadata = std::fs::metadata(&path)?;
+                let created_at = backup_time(&path)
+                    .or_else(|| metadata.modified().ok().map(DateTime::<Local>::from))
+                    .unwrap_or_else(Local::now);
+                backups.push(WorldBackup {
+                    world: world.clone(),
+                    path,
+                    created_at,
+                    size: metadata.len(),
+                });
+            }
+        }
+
+        backups.sort_by_key(|b| std::cmp::Reverse((b.created_at, backup_sequence(&b.path))));
+        Ok(backups)
+    }
+
+    /// Find a backup by file name (`2024-01-01_12-00-00.zip`) or
+    /// `world/file name`
+    pub fn find(&self, id: &str) -> Result<WorldBackup> {
+        let (world, file) = match id.rsplit_once('/') {
+            Some((world, file)) => (Some(world), file),
+            None => (None, id),
+        };
+        let matches: Vec<_> = self
+            .list(world)?
+            .into_iter()
+            .filter(|b| b.file_name() == file || b.file_name() == format!("{}.zip", file))
+            .collect();
+
+        match matches.len() {
+            0 => anyhow::bail!("Backup '{}' not found", id),
+            1 => Ok(matches.into_iter().next().unwrap()),
+            _ => anyhow::bail!("Backup '{}' is ambiguous; use <world>/{}", id, file),
+        }
+    }
+
+    /// Remove the oldest backups of a world beyond the retention limit
+    pub fn prune(&self, world: &str) -> Result<usize> {
+        if self.keep == 0 {
+            return Ok(0);
+        }
+        let old = self.list(Some(world))?.into_iter().skip(self.keep);
+        let mut removed = 0;
+        for backup in old {
+            std::fs::remove_file(&backup.path)?;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+
+    /// Replace a world with the contents of a backup
+    ///
+    /// The archive is fully extracted beside the world before the current
+    /// copy is swapped out, so a bad archive leaves the world untouched.
+    pub fn restore(&self, backup: &WorldBackup) -> Result<()> {
+        self.check_not_running()?;
+        let saves = self.saves_dir();
+        let world = world_name(&backup.world)?;
+        let world_dir = saves.join(world);
+        let staging = saves.join(format!(".{}.restoring", world));
+        let previous = saves.join(format!(".{}.previous", world));
+        let _ = std::fs::remove_dir_all(&staging);
+        let _ = std::fs::remove_dir_all(&previous);
+
+        let file = std::fs::File::open(&backup.path).context("Failed to open backup")?;
+        let mut archive = ZipArchive::new(file).context("Backup is not a valid zip")?;
+        let extracted = extract_prefix(&mut archive, world, &staging);
+        match extracted {
+            Ok(_) if staging.join("level.dat").exists() => {}
+            Ok(_) => {
+                let _ = std::fs::remove_dir_all(&staging);
+                anyhow::bail!("Backup does not contain world '{}'", world);
+            }
+            Err(e) => {
+                let _ = std::fs::remove_dir_all(&staging);
+                return Err(e);
+            }
+        }
+
+        if world_dir.exists() {
+            std::fs::rename(&world_dir, &previous).context("Failed to move current world aside")?;
+        }
+        if let Err(e) = std::fs::rename(&staging, &world_dir) {
+            let _ = std::fs::rename(&previous, &world_dir);
+            return Err(e).context("Failed to restore world");
+        }
+        let _ = std::fs::remove_dir_all(&previous);
+
+        tracing::info!("Restored world {} from {}", world, backup.path.display());
+        Ok(())
+    }
+
+    /// Delete a backup archive
+    pub fn delete(&self, backup: &WorldBackup) -> Result<()> {
+        std::fs::remove_file(&backup.path).context("Failed to delete backup")
+    }
+}
+
+/// Reject world names that are not a single folder name
+fn world_name(world: &str) -> Result<&str> {
+    let path = Path::new(world);
+    let is_plain = !world.is_empty()
+        && !world.contains(['/', '\\'])
+        && matches!(
+            path.components().next(),
+            Some(std::path::Component::Normal(_))
+        );
+    if !is_plain {
+        anyhow::bail!("Invalid world name: {}", world);
+    }
+    Ok(world)
+}
+
+/// Creation time encoded in a backup file name
+fn backup_time(path: &Path) -> Option<DateTime<Local>> {
+    let stem = path.file_stem()?.to_str()?;
+    let stamp = stem.get(..19)?;
+    let naive = NaiveDateTime::parse_from_str(stamp, TIMESTAMP_FORMAT).ok()?;
+    Local.from_local_datetime(&naive).earliest()
+}
+
+/// Numbered suffix of backups taken within the same second (`...-2.zip`)
+fn backup_sequence(path: &Path) -> u32 {
+    path.file_stem()
+        .and_then(|s| s.to_str())
+        .and_then(|s| s.get(19..))
+        .and_then(|s| s.strip_prefix('-'))
+        .and_then(|n| n.parse().ok())
+        .unwrap_or(1)
+}
+
+fn write_world_zip(world_dir: &Path, world: &str, output: &Path) -> Result<()> {
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(output)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+    add_dir(&mut zip, world_dir, world, options)?;
+    zip.finish()?;
+    Ok(())
+}
+
+fn add_dir<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    dir: &Path,
+    prefix: &str,
+    options: SimpleFileOptions,
+) -> Result<()> {
+    zip.add_directory(format!("{}/", prefix), options)?;
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        let path = entry.path();
+        let entry_name = format!("{}/{}", prefix, name);
+
+        if entry.file_type()?.is_dir() {
+            add_dir(zip, &path, &entry_name, options)?;
+        } else if name != SESSION_LOCK {
+            zip.start_file(entry_name, options)?;
+            let mut file = std::fs::File::open(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            std::io::copy(&mut file, zip)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::record_game_pid;
+    use crate::util::temp::TempDir;
+
+    fn instance_dir(tag: &str) -> TempDir {
+        let dir = TempDir::new(tag);
+        let world = dir.join(".minecraft/saves/My World");
+        std::fs::create_dir_all(world.join("region")).unwrap();
+        std::fs::write(world.join("level.dat"), b"v1").unwrap();
+        std::fs::write(world.join("region/r.0.0.mca"), b"chunks").unwrap();
+        std::fs::write(world.join(SESSION_LOCK), b"").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_backup_and_restore() {
+        let dir = instance_dir("backup-restore");
+        let manager = BackupManager::new(&dir);
+        assert_eq!(manager.worlds().unwrap(), vec!["My World"]);
+
+        let backup = manager.backup_world("My World").unwrap();
+        assert!(backup.path.starts_with(dir.join("backups/My World")));
+
+        let world = dir.join(".minecraft/saves/My World");
+        std::fs::write(world.join("level.dat"), b"v2").unwrap();
+        std::fs::write(world.join("griefed.txt"), b"x").unwrap();
+
+        let found = manager.find(&backup.file_name()).unwrap();
+        manager.restore(&found).unwrap();
+        assert_eq!(std::fs::read(world.join("level.dat")).unwrap(), b"v1");
+        assert!(world.join("region/r.0.0.mca").exists());
+        assert!(!world.join("griefed.txt").exists());
+        assert!(!world.join(SESSION_LOCK).exists());
+
+        manager.delete(&found).unwrap();
+        assert!(manager.list(None).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_retention_limit() {
+        let dir = instance_dir("backup-retention");
+        let manager = BackupManager::new(&dir).keep(2);
+        let first = manager.backup_world("My World").unwrap();
+        manager.backup_world("My World").unwrap();
+        let last = manager.backup_world("My World").unwrap();
+
+        let backups = manager.list(Some("My World")).unwrap();
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[0].path, last.path);
+        assert!(!first.path.exists());
+    }
+
+    #[test]
+    fn test_refuses_while_running() {
+        let dir = instance_dir("backup-running");
+        record_game_pid(&dir, std::process::id()).unwrap();
+
+        let err = BackupManager::new(&dir)
+            .backup_world("My World")
+            .unwrap_err();
+        assert!(err.to_string().contains("running"), "{}", err);
+        assert!(
+            BackupManager::new(&dir)
+                .allow_running(true)
+                .backup_world("My World")
+                .is_ok()
+        );
+        assert!(BackupManager::new(&dir).backup_world("../escape").is_err());
+    }
+}
@@ -0,0 +1,177 @@
+//! Instance duplication
+//!
+//! Copies an instance under a new name, sharing mod jars and packs with the
+//! source through copy-on-write clones or hardlinks when possible.
+
+use super::{ICON_FILE, Instance, InstanceManager};
+use crate::util::fs::copy_dir_linked;
+use anyhow::Result;
+use std::path::Path;
+
+/// Folders holding jars and packs that are replaced rather than edited
+const SHARED_DIRS: &[&str] = &["mods", "resourcepacks", "shaderpacks"];
+
+/// Game directory entries never copied to a clone
+const CLONE_EXCLUDES: &[&str] = &["logs", "crash-reports"];
+
+/// Options for [`InstanceManager::clone_instance`]
+#[derive(Debug, Clone)]
+pub struct CloneOptions {
+    /// Copy worlds in `saves/`
+    pub include_saves: bool,
+    /// Hardlink mod jars and packs when copy-on-write clones are unavailable
+    pub link_files: bool,
+}
+
+impl Default for CloneOptions {
+    fn default() -> Self {
+        Self {
+            include_saves: true,
+            link_files: true,
+        }
+    }
+}
+
+impl InstanceManager {
+    /// Duplicate an instance under a new name
+    ///
+    /// The copy gets its own name, ID and `created_at`, and is not a
+    /// favourite; everything else in `instance.toml` is kept.
+    pub fn clone_instance(&self, src: &str, dst: &str, options: &CloneOptions) -> Result<Instance> {
+        let source = self.load(src)?;
+        let mut instance = self.create(
+            dst,
+            &source.info.version,
+            source.info.loader.clone(),
+            source.info.loader_version.clone(),
+        )?;
+        instance.java = source.java;
+        instance.game = source.game;
+        instance.info.group = source.info.group;
+        instance.info.tags = source.info.tags;
+        instance.info.icon = source.info.icon;
+
+        let mut exclude = CLONE_EXCLUDES.to_vec();
+        if !options.include_saves {
+            exclude.push("saves");
+        }
+        let can_hardlink = |relative: &Path| options.link_files && is_shared_file(relative);
+
+        let result = self.save(&instance).and_then(|_| {
+            let icon = self.get_instance_dir(&source.info.id).join(ICON_FILE);
+            if icon.is_file() {
+                std::fs::copy(
+                    &icon,
+                    self.get_instance_dir(&instance.info.id).join(ICON_FILE),
+                )?;
+            }
+            let source_dir = self.get_game_dir(&source.info.id);
+            if source_dir.exists() {
+                copy_dir_linked(
+                    &source_dir,
+                    &self.get_game_dir(&instance.info.id),
+                    &exclude,
+                    &can_hardlink,
+                )?;
+            }
+            Ok(())
+        });
+
+        match result {
+            Ok(()) => {
+                tracing::info!("Cloned instance: {} -> {}", src, dst);
+                Ok(instance)
+            }
+            Err(e) => {
+                if let Err(cleanup) = self.delete(&instance.info.id) {
+                    tracing::warn!("Failed to remove partial instance {}: {}", dst, cleanup);
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+/// Whether a game directory file is a jar or pack that may be shared
+fn is_shared_file(relative: &Path) -> bool {
+    let in_dir = relative
+        .components()
+        .next()
+        .is_some_and(|c| SHARED_DIRS.iter().any(|d| c.as_os_str() == *d));
+    let ext = relative.extension().and_then(|e| e.to_str());
+    in_dir && matches!(ext, Some("jar") | Some("zip") | Some("disabled"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::ModLoader;
+    use crate::util::temp::TempDir;
+
+    #[test]
+    fn test_clone_instance() {
+        let dir = TempDir::new("clone");
+        let manager = InstanceManager::with_dir(&dir);
+
+        let mut source = manager
+            .create("Pack", "1.20.1", ModLoader::Fabric, Some("0.15.3".into()))
+            .unwrap();
+        source.java.max_memory = "6G".to_string();
+        source.info.created_at = chrono::DateTime::UNIX_EPOCH;
+        manager.save(&source).unwrap();
+
+        let game_dir = manager.get_game_dir(&source.info.id);
+        for sub in ["mods", "config", "saves/World", "logs"] {
+            std::fs::create_dir_all(game_dir.join(sub)).unwrap();
+        }
+        std::fs::write(game_dir.join("mods/sodium.jar"), b"sodium").unwrap();
+        std::fs::write(game_dir.join("config/sodium.json"), b"{}").unwrap();
+        std::fs::write(game_dir.join("saves/World/level.dat"), b"w").unwrap();
+        std::fs::write(game_dir.join("logs/latest.log"), b"l").unwrap();
+
+        let options = CloneOptions {
+            include_saves: false,
+            ..Default::default()
+        };
+        let clone = manager
+            .clone_instance("Pack", "Pack Test", &options)
+            .unwrap();
+        assert_eq!(clone.info.name, "Pack Test");
+        assert_ne!(clone.info.created_at, source.info.created_at);
+
+        let loaded = manager.load("Pack Test").unwrap();
+        assert_eq!(loaded.info.name, "Pack Test");
+        assert_eq!(loaded.info.loader_version.as_deref(), Some("0.15.3"));
+        assert_eq!(loaded.java.max_memory, "6G");
+
+        let clone_dir = manager.get_game_dir(&clone.info.id);
+        assert_eq!(
+            std::fs::read(clone_dir.join("mods/sodium.jar")).unwrap(),
+            b"sodium"
+        );
+        assert!(clone_dir.join("config/sodium.json").exists());
+        assert!(!clone_dir.join("saves").exists());
+        assert!(!clone_dir.join("logs").exists());
+
+        // Editing the clone's config leaves the source alone
+        std::fs::write(clone_dir.join("config/sodium.json"), b"changed").unwrap();
+        assert_eq!(
+            std::fs::read(game_dir.join("config/sodium.json")).unwrap(),
+            b"{}"
+        );
+
+        assert!(
+            manager
+                .clone_instance("Pack", "Pack Test", &options)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_is_shared_file() {
+        assert!(is_shared_file(Path::new("mods/sodium.jar")));
+        assert!(is_shared_file(Path::new("resourcepacks/faithful.zip")));
+        assert!(!is_shared_file(Path::new("config/sodium.json")));
+        assert!(!is_shared_file(Path::new("options.txt")));
+    }
+}
@@ -0,0 +1,138 @@
+//! Instance icons
+//!
+//! Either one of the built-in emoji icons or a custom image stored as
+//! `icon.png` in the instance directory.
+
+use super::{Instance, InstanceInfo, InstanceManager};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Custom icon file in the instance directory
+pub const ICON_FILE: &str = "icon.png";
+
+/// `InstanceInfo.icon` value for a custom icon
+pub const CUSTOM_ICON: &str = "custom";
+
+/// Custom icons larger than this are scaled down
+const MAX_ICON_SIZE: u32 = 128;
+
+/// Built-in icons: name stored in `instance.toml` and the emoji shown
+pub const BUILTIN_ICONS: &[(&str, &str)] = &[
+    ("chest", "📦"),
+    ("grass", "🌱"),
+    ("tree", "🌲"),
+    ("pickaxe", "⛏"),
+    ("sword", "🗡"),
+    ("diamond", "💎"),
+    ("fire", "🔥"),
+    ("water", "🌊"),
+    ("castle", "🏰"),
+    ("gear", "⚙"),
+    ("rocket", "🚀"),
+    ("skull", "💀"),
+];
+
+impl InstanceInfo {
+    /// Emoji for the instance's built-in icon, the chest for anything else
+    pub fn icon_emoji(&self) -> &'static str {
+        BUILTIN_ICONS
+            .iter()
+            .find(|(name, _)| *name == self.icon)
+            .map_or(BUILTIN_ICONS[0].1, |(_, emoji)| emoji)
+    }
+}
+
+impl InstanceManager {
+    /// Custom icon file, if the instance uses one
+    pub fn icon_path(&self, instance: &Instance) -> Option<PathBuf> {
+        let path = self.get_instance_dir(&instance.info.id).join(ICON_FILE);
+        (instance.info.icon == CUSTOM_ICON && path.is_file()).then_some(path)
+    }
+
+    /// Use a built-in icon, removing any custom one
+    pub fn set_builtin_icon(&self, id: &str, icon: &str) -> Result<Instance> {
+        if !BUILTIN_ICONS.iter().any(|(name, _)| *name == icon) {
+            anyhow::bail!("Unknown icon: {}", icon);
+        }
+        let mut instance = self.load_id(id)?;
+        let _ = std::fs::remove_file(self.get_instance_dir(&instance.info.id).join(ICON_FILE));
+        instance.info.icon = icon.to_string();
+        self.save(&instance)?;
+        Ok(instance)
+    }
+
+    /// Use an image file as the instance icon
+    pub fn set_icon_file(&self, id: &str, path: &Path) -> Result<Instance> {
+        let bytes =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        self.set_icon_image(id, &bytes)
+    }
+
+    /// Use image data as the instance icon
+    ///
+    /// The image is decoded, scaled down to at most 128px and stored as
+    /// `icon.png` in the instance directory.
+    pub fn set_icon_image(&self, id: &str, bytes: &[u8]) -> Result<Instance> {
+        let image = image::load_from_memory(bytes).context("Icon is not a valid image")?;
+        let image = if image.width() > MAX_ICON_SIZE || image.height() > MAX_ICON_SIZE {
+            image.thumbnail(MAX_ICON_SIZE, MAX_ICON_SIZE)
+        } else {
+            image
+        };
+
+        let mut instance = self.load_id(id)?;
+        let path = self.get_instance_dir(&instance.info.id).join(ICON_FILE);
+        image
+            .save_with_format(&path, image::ImageFormat::Png)
+            .context("Failed to save icon")?;
+        instance.info.icon = CUSTOM_ICON.to_string();
+        self.save(&instance)?;
+        Ok(instance)
+    }
+}
+
+/// Decode a custom icon to RGBA
+pub fn load_icon_rgba(path: &Path) -> Result<image::RgbaImage> {
+    let image = image::open(path).with_context(|| format!("Failed to load {}", path.display()))?;
+    Ok(image.to_rgba8())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::ModLoader;
+    use crate::util::temp::TempDir;
+
+    #[test]
+    fn test_icons() {
+        let dir = TempDir::new("icon");
+        let manager = InstanceManager::with_dir(&dir);
+        let instance = manager
+            .create("Pack", "1.20.1", ModLoader::Vanilla, None)
+            .unwrap();
+        assert_eq!(instance.info.icon_emoji(), "📦");
+        assert!(manager.icon_path(&instance).is_none());
+
+        let mut png = std::io::Cursor::new(Vec::new());
+        image::RgbaImage::new(512, 256)
+            .write_to(&mut png, image::ImageFormat::Png)
+            .unwrap();
+        let instance = manager
+            .set_icon_image(&instance.info.id, png.get_ref())
+            .unwrap();
+        let path = manager.icon_path(&instance).unwrap();
+        assert_eq!(load_icon_rgba(&path).unwrap().dimensions(), (128, 64));
+        assert!(
+            manager
+                .set_icon_image(&instance.info.id, b"not an image")
+                .is_err()
+        );
+
+        let instance = manager
+            .set_builtin_icon(&instance.info.id, "diamond")
+            .unwrap();
+        assert_eq!(instance.info.icon_emoji(), "💎");
+        assert!(!path.exists());
+        assert!(manager.set_builtin_icon(&instance.info.id, "nope").is_err());
+    }
+}
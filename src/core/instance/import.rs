@@ -0,0 +1,563 @@
+//! Import instances from other launchers
+//!
+//! Reads MultiMC/Prism Launcher instances (`instance.cfg` + `mmc-pack.json`)
+//! and official launcher profiles (`launcher_profiles.json`) and converts
+//! them into gLauncher instances.
+
+use super::{Instance, InstanceJavaConfig, InstanceManager, ModLoader};
+use crate::util::fs::{copy_dir, link_dir};
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Official launcher files and folders that are not part of a profile
+const OFFICIAL_LAUNCHER_FILES: &[&str] = &[
+    "assets",
+    "libraries",
+    "versions",
+    "runtime",
+    "webcache2",
+    "logs",
+    "launcher_accounts.json",
+    "launcher_accounts_microsoft_store.json",
+    "launcher_cef_log.txt",
+    "launcher_entitlements.json",
+    "launcher_log.txt",
+    "launcher_msa_credentials.bin",
+    "launcher_product_state.json",
+    "launcher_profiles.json",
+    "launcher_settings.json",
+    "launcher_skins.json",
+    "launcher_ui_state.json",
+    "treatment_tags.json",
+];
+
+/// How the game directory is brought over
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum GameDirMode {
+    /// Copy files into the new instance
+    #[default]
+    Copy,
+    /// Point the new instance's `.minecraft` at the original folder
+    Symlink,
+}
+
+/// Instance description read from another launcher
+#[derive(Debug, Clone)]
+pub struct ExternalInstance {
+    pub name: String,
+    pub version: String,
+    pub loader: ModLoader,
+    pub loader_version: Option<String>,
+    pub java: InstanceJavaConfig,
+    /// Folder holding mods, saves, options.txt, ...
+    pub game_dir: PathBuf,
+    /// Top-level entries in `game_dir` to leave behind when copying
+    pub exclude: &'static [&'static str],
+    /// Icon image data
+    pub icon: Option<Vec<u8>>,
+}
+
+/// A profile in `launcher_profiles.json`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LauncherProfile {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub last_version_id: String,
+    pub game_dir: Option<PathBuf>,
+    pub java_dir: Option<PathBuf>,
+    pub java_args: Option<String>,
+    /// Built-in icon name or a `data:image/png;base64,` URL
+    pub icon: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LauncherProfiles {
+    #[serde(default)]
+    profiles: BTreeMap<String, LauncherProfile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MmcPack {
+    #[serde(default)]
+    components: Vec<MmcComponent>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MmcComponent {
+    uid: String,
+    version: Option<String>,
+    cached_version: Option<String>,
+}
+
+/// Read another launcher's instance from a path
+///
+/// `path` is either a MultiMC/Prism instance folder or an official launcher
+/// folder (`.minecraft`); for the latter `profile` selects the profile by
+/// name or ID and may be omitted when there is only one.
+pub fn read_external(path: &Path, profile: Option<&str>) -> Result<ExternalInstance> {
+    if path.join("instance.cfg").exists() {
+        read_multimc(path)
+    } else if path.join("launcher_profiles.json").exists() {
+        read_official(path, profile)
+    } else {
+        anyhow::bail!(
+            "{} is not a MultiMC/Prism instance or official launcher folder",
+            path.display()
+        )
+    }
+}
+
+/// Import another launcher's instance as a new gLauncher instance
+pub fn import_external(
+    manager: &InstanceManager,
+    external: &ExternalInstance,
+    name: Option<&str>,
+    mode: GameDirMode,
+) -> Result<Instance> {
+    let name = manager.unique_name(name.unwrap_or(&external.name));
+    let mut instance = manager.create(
+        &name,
+        &external.version,
+        external.loader.clone(),
+        external.loader_version.clone(),
+    )?;
+    instance.java = external.java.clone();
+
+    let result = manager.save(&instance).and_then(|_| {
+        let game_dir = manager.get_game_dir(&instance.info.id);
+        if !external.game_dir.exists() {
+            return Ok(());
+        }
+        match mode {
+            GameDirMode::Copy => {
+                copy_dir(&external.game_dir, &game_dir, external.exclude).map(|_| ())
+            }
+            GameDirMode::Symlink => {
+                std::fs::remove_dir(&game_dir)?;
+                let target = external.game_dir.canonicalize()?;
+                link_dir(&target, &game_dir)
+            }
+        }
+    });
+
+    match result {
+        Ok(()) => {
+            tracing::info!("Imported {} as {}", external.game_dir.display(), name);
+            if let Some(icon) = &external.icon {
+                match manager.set_icon_image(&instance.info.id, icon) {
+                    Ok(with_icon) => instance = with_icon,
+                    Err(e) => tracing::warn!("Failed to import icon: {}", e),
+                }
+            }
+            Ok(instance)
+        }
+        Err(e) => {
+            if let Err(cleanup) = manager.delete(&instance.info.id) {
+                tracing::warn!("Failed to remove partial instance {}: {}", name, cleanup);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Read a MultiMC or Prism Launcher instance folder
+pub fn read_multimc(dir: &Path) -> Result<ExternalInstance> {
+    let cfg =
+        std::fs::read_to_string(dir.join("instance.cfg")).context("Failed to read instance.cfg")?;
+    let cfg = parse_cfg(&cfg);
+
+    let pack = std::fs::read_to_string(dir.join("mmc-pack.json"))
+        .context("Failed to read mmc-pack.json")?;
+    let pack: MmcPack = serde_json::from_str(&pack).context("Failed to parse mmc-pack.json")?;
+
+    let mut version = None;
+    let mut loader = (ModLoader::Vanilla, None);
+    for component in &pack.components {
+        let component_version = component
+            .version
+            .clone()
+            .or_else(|| component.cached_version.clone());
+        match component.uid.as_str() {
+            "net.minecraft" => version = component_version,
+            "net.fabricmc.fabric-loader" => loader = (ModLoader::Fabric, component_version),
+            "org.quiltmc.quilt-loader" => loader = (ModLoader::Quilt, component_version),
+            "net.minecraftforge" => loader = (ModLoader::Forge, component_version),
+            "net.neoforged" => loader = (ModLoader::NeoForge, component_version),
+            _ => {}
+        }
+    }
+    let version = version.context("mmc-pack.json has no net.minecraft component")?;
+
+    let flag = |key: &str| cfg.get(key).is_some_and(|v| v == "true");
+    let mut java = InstanceJavaConfig::default();
+    if flag("OverrideJavaLocation")
+        && let Some(path) = cfg.get("JavaPath").filter(|p| !p.is_empty())
+    {
+        java.override_global = true;
+        java.path = path.clone();
+    }
+    if flag("OverrideMemory") {
+        java.min_memory = cfg
+            .get("MinMemAlloc")
+            .map(|m| format!("{}M", m))
+            .unwrap_or_default();
+        java.max_memory = cfg
+            .get("MaxMemAlloc")
+            .map(|m| format!("{}M", m))
+            .unwrap_or_default();
+    }
+    if flag("OverrideJavaArgs")
+        && let Some(args) = cfg.get("JvmArgs")
+    {
+        java.extra_args = split_args(args);
+    }
+
+    // Prism uses `minecraft`, MultiMC `.minecraft`
+    let game_dir = [".minecraft", "minecraft"]
+        .iter()
+        .map(|d| dir.join(d))
+        .find(|d| d.is_dir())
+        .unwrap_or_else(|| dir.join(".minecraft"));
+
+    let fallback_name = dir
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Imported".to_string());
+
+    Ok(ExternalInstance {
+        name: cfg.get("name").cloned().unwrap_or(fallback_name),
+        version,
+        loader: loader.0,
+        loader_version: loader.1,
+        java,
+        game_dir,
+        exclude: &[],
+        icon: cfg.get("iconKey").and_then(|key| multimc_icon(dir, key)),
+    })
+}
+
+/// Custom icon from the launcher's `icons` folder, next to `instances`
+fn multimc_icon(instance_dir: &Path, key: &str) -> Option<Vec<u8>> {
+    let icons = instance_dir.parent()?.parent()?.join("icons");
+    ["png", "jpg", "jpeg"]
+        .iter()
+        .find_map(|ext| std::fs::read(icons.join(format!("{}.{}", key, ext))).ok())
+}
+
+/// Read a profile from an official launcher folder
+pub fn read_official(dir: &Path, profile: Option<&str>) -> Result<ExternalInstance> {
+    let profiles = official_profiles(dir)?;
+    let profile = match profile {
+        Some(wanted) => profiles
+            .get(wanted)
+            .or_else(|| profiles.values().find(|p| p.name == wanted))
+            .with_context(|| format!("Profile '{}' not found in launcher_profiles.json", wanted))?,
+        None if profiles.len() == 1 => profiles.values().next().unwrap(),
+        None => {
+            let names: Vec<_> = profiles
+                .iter()
+                .map(|(id, p)| profile_label(id, p))
+                .collect();
+            anyhow::bail!("Choose a profile to import: {}", names.join(", "))
+        }
+    };
+
+    let (version, loader, loader_version) = parse_version_id(dir, &profile.last_version_id)?;
+
+    let mut java = InstanceJavaConfig::default();
+    if let Some(java_dir) = &profile.java_dir {
+        java.override_global = true;
+        java.path = java_dir.to_string_lossy().to_string();
+    }
+    for arg in split_args(profile.java_args.as_deref().unwrap_or_default()) {
+        if let Some(max) = arg.strip_prefix("-Xmx") {
+            java.max_memory = max.to_string();
+        } else if let Some(min) = arg.strip_prefix("-Xms") {
+            java.min_memory = min.to_string();
+        } else {
+            java.extra_args.push(arg);
+        }
+    }
+
+    // Without a custom game dir the profile shares the launcher folder itself
+    let (game_dir, exclude) = match &profile.game_dir {
+        Some(game_dir) => (game_dir.clone(), &[][..]),
+        None => (dir.to_path_buf(), OFFICIAL_LAUNCHER_FILES),
+    };
+
+    let name = if profile.name.is_empty() {
+        format!("Minecraft {}", version)
+    } else {
+        profile.name.clone()
+    };
+
+    Ok(ExternalInstance {
+        name,
+        version,
+        loader,
+        loader_version,
+        java,
+        game_dir,
+        exclude,
+        icon: profile.icon.as_deref().and_then(data_url_image),
+    })
+}
+
+/// Image data from a `data:image/...;base64,` URL
+fn data_url_image(url: &str) -> Option<Vec<u8>> {
+    use base64::Engine;
+    let (_, data) = url.strip_prefix("data:image/")?.split_once(";base64,")?;
+    base64::engine::general_purpose::STANDARD.decode(data).ok()
+}
+
+/// Profiles in `launcher_profiles.json`, keyed by ID
+pub fn official_profiles(dir: &Path) -> Result<BTreeMap<String, LauncherProfile>> {
+    let content = std::fs::read_to_string(dir.join("launcher_profiles.json"))
+        .context("Failed to read launcher_profiles.json")?;
+    let profiles: LauncherProfiles =
+        serde_json::from_str(&content).context("Failed to parse launcher_profiles.json")?;
+    Ok(profiles.profiles)
+}
+
+fn profile_label(id: &str, profile: &LauncherProfile) -> String {
+    if profile.name.is_empty() {
+        id.to_string()
+    } else {
+        format!("{} ({})", profile.name, id)
+    }
+}
+
+/// Minecraft version and loader from an official launcher version ID
+///
+/// Handles `1.20.1`, `fabric-loader-0.15.3-1.20.1`, `quilt-loader-0.23.0-1.20.1`,
+/// `1.20.1-forge-47.2.0` and `neoforge-20.4.80`. Other IDs fall back to the
+/// `inheritsFrom` field of the installed version JSON.
+fn parse_version_id(dir: &Path, id: &str) -> Result<(String, ModLoader, Option<String>)> {
+    if id.is_empty() || id.starts_with("latest-") {
+        anyhow::bail!("Profile does not pin a game version ({})", id);
+    }
+
+    for (prefix, loader) in [
+        ("fabric-loader-", ModLoader::Fabric),
+        ("quilt-loader-", ModLoader::Quilt),
+    ] {
+        if let Some(rest) = id.strip_prefix(prefix)
+            && let Some((loader_version, mc)) = rest.split_once('-')
+        {
+            return Ok((mc.to_string(), loader, Some(loader_version.to_string())));
+        }
+    }
+    if let Some((mc, forge)) = id.split_once("-forge-") {
+        return Ok((mc.to_string(), ModLoader::Forge, Some(forge.to_string())));
+    }
+    if let Some(neoforge) = id.strip_prefix("neoforge-") {
+        let mc = neoforge_mc_version(neoforge)
+            .with_context(|| format!("Unrecognised NeoForge version: {}", neoforge))?;
+        return Ok((mc, ModLoader::NeoForge, Some(neoforge.to_string())));
+    }
+
+    let json = dir.join("versions").join(id).join(format!("{}.json", id));
+    if let Ok(content) = std::fs::read_to_string(&json) {
+        let value: HashMap<String, serde_json::Value> = serde_json::from_str(&content)?;
+        if let Some(parent) = value.get("inheritsFrom").and_then(|v| v.as_str()) {
+            tracing::warn!(
+                "Unknown loader in version {}, importing as vanilla {}",
+                id,
+                parent
+            );
+            return Ok((parent.to_string(), ModLoader::Vanilla, None));
+        }
+    }
+    Ok((id.to_string(), ModLoader::Vanilla, None))
+}
+
+/// Minecraft version for a NeoForge version (`20.4.80` → `1.20.4`, `21.0.x` → `1.21`)
+fn neoforge_mc_version(version: &str) -> Option<String> {
+    let mut parts = version.split('.');
+    let major: u32 = parts.next()?.parse().ok()?;
+    let minor: u32 = parts.next()?.parse().ok()?;
+    Some(if minor == 0 {
+        format!("1.{}", major)
+    } else {
+        format!("1.{}.{}", major, minor)
+    })
+}
+
+/// Parse a Qt-style `key=value` config, ignoring sections
+fn parse_cfg(content: &str) -> HashMap<String, String> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.starts_with('[') && !l.starts_with('#'))
+        .filter_map(|l| l.split_once('='))
+        .map(|(k, v)| {
+            let v = v.trim();
+            let v = v
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .unwrap_or(v);
+            (k.trim().to_string(), v.to_string())
+        })
+        .collect()
+}
+
+/// Split a JVM argument string on whitespace, keeping quoted parts together
+fn split_args(args: &str) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+
+    for c in args.chars() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, c) if c.is_whitespace() => {
+                if !current.is_empty() {
+                    result.push(std::mem::take(&mut current));
+                }
+            }
+            (_, c) => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        result.push(current);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::temp::TempDir;
+
+    #[test]
+    fn test_import_prism_instance() {
+        let dir = TempDir::new("import-prism");
+        let source = dir.join("prism/All The Mods");
+        std::fs::create_dir_all(source.join("minecraft/mods")).unwrap();
+        std::fs::write(source.join("minecraft/mods/jei.jar"), b"jei").unwrap();
+        std::fs::write(
+            source.join("instance.cfg"),
+            "[General]\nInstanceType=OneSix\nname=\"ATM 9\"\nOverrideMemory=true\n\
+             MinMemAlloc=1024\nMaxMemAlloc=8192\nOverrideJavaArgs=true\n\
+             JvmArgs=-XX:+UseG1GC -Dfoo=\"a b\"\nOverrideJavaLocation=false\nJavaPath=/usr/bin/java\n\
+             iconKey=atm\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(dir.join("icons")).unwrap();
+        image::RgbaImage::new(32, 32)
+            .save(dir.join("icons/atm.png"))
+            .unwrap();
+        std::fs::write(
+            source.join("mmc-pack.json"),
+            r#"{ "formatVersion": 1, "components": [
+                { "uid": "org.lwjgl3", "version": "3.3.1" },
+                { "uid": "net.minecraft", "version": "1.20.1" },
+                { "uid": "net.minecraftforge", "version": "47.2.0" }
+            ] }"#,
+        )
+        .unwrap();
+
+        let external = read_external(&source, None).unwrap();
+        assert_eq!(external.name, "ATM 9");
+        assert_eq!(external.loader, ModLoader::Forge);
+        assert_eq!(external.loader_version.as_deref(), Some("47.2.0"));
+        assert_eq!(external.java.max_memory, "8192M");
+        assert_eq!(external.java.extra_args, vec!["-XX:+UseG1GC", "-Dfoo=a b"]);
+        assert!(!external.java.override_global);
+
+        let manager = InstanceManager::with_dir(&dir.join("instances"));
+        let instance = import_external(&manager, &external, None, GameDirMode::Copy).unwrap();
+        assert_eq!(instance.info.version, "1.20.1");
+        let loaded = manager.load("ATM 9").unwrap();
+        assert!(manager.icon_path(&loaded).is_some());
+        assert_eq!(loaded.java.min_memory, "1024M");
+        assert!(
+            manager
+                .get_game_dir(&instance.info.id)
+                .join("mods/jei.jar")
+                .exists()
+        );
+
+        let linked = import_external(&manager, &external, None, GameDirMode::Symlink).unwrap();
+        assert_eq!(linked.info.name, "ATM 9 (2)");
+        let game_dir = manager.get_game_dir(&linked.info.id);
+        assert!(
+            game_dir
+                .symlink_metadata()
+                .unwrap()
+                .file_type()
+                .is_symlink()
+        );
+        assert!(game_dir.join("mods/jei.jar").exists());
+    }
+
+    #[test]
+    fn test_read_official_profile() {
+        let root = TempDir::new("import-official");
+        let dir = root.join(".minecraft");
+        std::fs::create_dir_all(dir.join("versions")).unwrap();
+        std::fs::write(dir.join("options.txt"), b"fov:70").unwrap();
+        std::fs::write(
+            dir.join("launcher_profiles.json"),
+            r#"{ "profiles": {
+                "abc": { "name": "Fabric", "type": "custom",
+                         "lastVersionId": "fabric-loader-0.15.3-1.20.1",
+                         "javaDir": "/opt/java/bin/java",
+                         "javaArgs": "-Xmx4G -Xms1G -XX:+UseG1GC" },
+                "def": { "name": "", "type": "latest-release", "lastVersionId": "latest-release" }
+            } }"#,
+        )
+        .unwrap();
+
+        assert!(read_external(&dir, None).is_err());
+        let external = read_external(&dir, Some("Fabric")).unwrap();
+        assert_eq!(external.version, "1.20.1");
+        assert_eq!(external.loader, ModLoader::Fabric);
+        assert_eq!(external.loader_version.as_deref(), Some("0.15.3"));
+        assert_eq!(external.java.path, "/opt/java/bin/java");
+        assert!(external.java.override_global);
+        assert_eq!(external.java.max_memory, "4G");
+        assert_eq!(external.java.min_memory, "1G");
+        assert_eq!(external.java.extra_args, vec!["-XX:+UseG1GC"]);
+
+        let manager = InstanceManager::with_dir(&root.join("instances"));
+        let instance = import_external(&manager, &external, None, GameDirMode::Copy).unwrap();
+        let game_dir = manager.get_game_dir(&instance.info.id);
+        assert!(game_dir.join("options.txt").exists());
+        assert!(!game_dir.join("versions").exists());
+        assert!(!game_dir.join("launcher_profiles.json").exists());
+
+        assert_eq!(
+            data_url_image("data:image/png;base64,aGk="),
+            Some(b"hi".to_vec())
+        );
+        assert_eq!(data_url_image("Grass"), None);
+    }
+
+    #[test]
+    fn test_parse_version_id() {
+        let dir = Path::new("/nonexistent");
+        let parse = |id| parse_version_id(dir, id).unwrap();
+        assert_eq!(parse("1.20.1"), ("1.20.1".into(), ModLoader::Vanilla, None));
+        assert_eq!(
+            parse("1.20.1-forge-47.2.0"),
+            ("1.20.1".into(), ModLoader::Forge, Some("47.2.0".into()))
+        );
+        assert_eq!(
+            parse("neoforge-20.4.80-beta"),
+            (
+                "1.20.4".into(),
+                ModLoader::NeoForge,
+                Some("20.4.80-beta".into())
+            )
+        );
+        assert_eq!(parse("neoforge-21.0.10").0, "1.21");
+        assert!(parse_version_id(dir, "latest-release").is_err());
+    }
+}
@@ -2,10 +2,23 @@
 //!
 //! Create, configure, and manage Minecraft instances.
 
+mod clone;
+mod icon;
+mod import;
+mod organize;
+mod session;
+
+pub use clone::CloneOptions;
+pub use icon::{BUILTIN_ICONS, CUSTOM_ICON, ICON_FILE, load_icon_rgba};
+pub use import::{GameDirMode, import_external, read_external};
+pub use organize::{groups, parse_tags, sort_instances, tags};
+pub use session::{GameSession, format_playtime, record_game_pid, running_game_pid};
+
 use crate::config;
 use crate::core::version::VersionDetails;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::path::PathBuf;
 
 /// Instance configuration
@@ -18,14 +31,36 @@ pub struct Instance {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct InstanceInfo {
+    /// Directory name, stable across renames
+    #[serde(default)]
+    pub id: String,
+    /// Display name
     pub name: String,
     pub version: String,
     pub loader: ModLoader,
     pub loader_version: Option<String>,
     pub created_at: chrono::DateTime<chrono::Utc>,
+    /// Group shown as a collapsible section (empty = ungrouped)
+    #[serde(default)]
+    pub group: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default)]
+    pub favorite: bool,
+    /// Built-in icon name, or `custom` for `icon.png` (empty = default)
+    #[serde(default)]
+    pub icon: String,
+    /// When a game session last ended
+    #[serde(default)]
+    pub last_played: Option<chrono::DateTime<chrono::Utc>>,
+    /// Total time the game has run, in seconds
+    #[serde(default)]
+    pub playtime_seconds: u64,
+    #[serde(default)]
+    pub launch_count: u32,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
 #[serde(rename_all = "lowercase")]
 pub enum ModLoader {
     #[default]
@@ -74,7 +109,7 @@ impl Default for InstanceJavaConfig {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameConfig {
     #[serde(default = "default_width")]
     pub resolution_width: u32,
@@ -113,32 +148,70 @@ impl InstanceManager {
         }
     }
 
+    /// Create a manager for another instances directory
+    pub fn with_dir(instances_dir: &Path) -> Self {
+        Self {
+            instances_dir: instances_dir.to_path_buf(),
+        }
+    }
+
     /// Get instances directory
     pub fn instances_dir(&self) -> &Path {
         &self.instances_dir
     }
 
     /// Get instance directory
-    pub fn get_instance_dir(&self, name: &str) -> PathBuf {
-        self.instances_dir.join(name)
+    pub fn get_instance_dir(&self, id: &str) -> PathBuf {
+        self.instances_dir.join(id)
     }
 
     /// Get instance game directory (.minecraft)
-    pub fn get_game_dir(&self, name: &str) -> PathBuf {
-        self.get_instance_dir(name).join(".minecraft")
+    pub fn get_game_dir(&self, id: &str) -> PathBuf {
+        self.get_instance_dir(id).join(".minecraft")
     }
 
     /// Get instance natives directory
-    pub fn get_natives_dir(&self, name: &str) -> PathBuf {
-        self.get_instance_dir(name).join("natives")
+    pub fn get_natives_dir(&self, id: &str) -> PathBuf {
+        self.get_instance_dir(id).join("natives")
+    }
+
+    /// Check if an instance with this ID or name exists
+    pub fn exists(&self, key: &str) -> bool {
+        self.load(key).is_ok()
     }
 
-    /// Check if instance exists
-    pub fn exists(&self, name: &str) -> bool {
-        self.get_instance_dir(name).join("instance.toml").exists()
+    /// First free instance name based on `name` ("Pack", "Pack (2)", ...)
+    pub fn unique_name(&self, name: &str) -> String {
+        let taken: HashSet<String> = self
+            .list()
+            .unwrap_or_default()
+            .into_iter()
+            .map(|i| i.info.name)
+            .collect();
+        let mut candidate = name.to_string();
+        let mut n = 2;
+        while taken.contains(&candidate) {
+            candidate = format!("{} ({})", name, n);
+            n += 1;
+        }
+        candidate
+    }
+
+    /// First free directory ID based on `base` ("pack", "pack-2", ...)
+    fn unique_id(&self, base: &str) -> String {
+        let mut candidate = base.to_string();
+        let mut n = 2;
+        while self.get_instance_dir(&candidate).exists() {
+            candidate = format!("{}-{}", base, n);
+            n += 1;
+        }
+        candidate
     }
 
     /// Create a new instance
+    ///
+    /// The directory gets an ID derived from the name, so the display name
+    /// can be anything printable.
     pub fn create(
         &self,
         name: &str,
@@ -146,23 +219,32 @@ impl InstanceManager {
         loader: ModLoader,
         loader_version: Option<String>,
     ) -> Result<Instance> {
-        let instance_dir = self.get_instance_dir(name);
-
-        if instance_dir.exists() {
+        validate_name(name)?;
+        if self.list()?.iter().any(|i| i.info.name == name) {
             anyhow::bail!("Instance '{}' already exists", name);
         }
 
+        let id = self.unique_id(&slugify(name));
+        validate_id(&id)?;
+
         // Create directories
-        std::fs::create_dir_all(&instance_dir)?;
-        std::fs::create_dir_all(self.get_game_dir(name))?;
+        std::fs::create_dir_all(self.get_game_dir(&id))?;
 
         let instance = Instance {
             info: InstanceInfo {
+                id,
                 name: name.to_string(),
                 version: version.to_string(),
                 loader,
                 loader_version,
                 created_at: chrono::Utc::now(),
+                group: String::new(),
+                tags: Vec::new(),
+                favorite: false,
+                icon: String::new(),
+                last_played: None,
+                playtime_seconds: 0,
+                launch_count: 0,
             },
             java: InstanceJavaConfig::default(),
             game: GameConfig::default(),
@@ -170,60 +252,149 @@ impl InstanceManager {
 
         self.save(&instance)?;
 
-        tracing::info!("Created instance: {}", name);
+        tracing::info!("Created instance: {} ({})", name, instance.info.id);
         Ok(instance)
     }
 
     /// Save instance configuration
     pub fn save(&self, instance: &Instance) -> Result<()> {
+        validate_id(&instance.info.id)?;
         let config_path = self
-            .get_instance_dir(&instance.info.name)
+            .get_instance_dir(&instance.info.id)
             .join("instance.toml");
         let content = toml::to_string_pretty(instance)?;
         std::fs::write(&config_path, content)?;
         Ok(())
     }
 
-    /// Load instance configuration
-    pub fn load(&self, name: &str) -> Result<Instance> {
-        let config_path = self.get_instance_dir(name).join("instance.toml");
+    /// Load an instance by display name or ID
+    ///
+    /// Display names win, since a name can look like another instance's ID.
+    pub fn load(&self, key: &str) -> Result<Instance> {
+        if let Some(instance) = self.list()?.into_iter().find(|i| i.info.name == key) {
+            return Ok(instance);
+        }
+        if validate_id(key).is_ok() && self.get_instance_dir(key).join("instance.toml").exists() {
+            return self.load_dir(key);
+        }
+        anyhow::bail!("Instance '{}' not found", key)
+    }
+
+    /// Load an instance by directory ID only
+    pub fn load_id(&self, id: &str) -> Result<Instance> {
+        validate_id(id)?;
+        self.load_dir(id)
+    }
+
+    /// Load the instance stored in a directory
+    fn load_dir(&self, dir_name: &str) -> Result<Instance> {
+        let config_path = self.get_instance_dir(dir_name).join("instance.toml");
         let content = std::fs::read_to_string(&config_path)
-            .context(format!("Instance '{}' not found", name))?;
-        let instance: Instance = toml::from_str(&content)?;
+            .context(format!("Instance '{}' not found", dir_name))?;
+        let mut instance: Instance = toml::from_str(&content)?;
+
+        if instance.info.id.is_empty() {
+            // Not yet migrated: the directory name is the display name
+            instance.info.name = dir_name.to_string();
+        }
+        // A directory copied by hand still carries the original's ID
+        instance.info.id = dir_name.to_string();
         Ok(instance)
     }
 
-    /// Delete an instance
-    pub fn delete(&self, name: &str) -> Result<()> {
-        let instance_dir = self.get_instance_dir(name);
-        if !instance_dir.exists() {
-            anyhow::bail!("Instance '{}' not found", name);
+    /// Give every instance from before IDs existed its own ID
+    ///
+    /// Runs as a separate pass so listing and loading never move directories.
+    pub fn migrate_legacy(&self) -> Result<()> {
+        if !self.instances_dir.exists() {
+            return Ok(());
+        }
+
+        // Collect first; migrating renames directories
+        let mut legacy = Vec::new();
+        for entry in std::fs::read_dir(&self.instances_dir)? {
+            let path = entry?.path();
+            let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let Ok(content) = std::fs::read_to_string(path.join("instance.toml")) else {
+                continue;
+            };
+            match toml::from_str::<Instance>(&content) {
+                Ok(instance) if instance.info.id.is_empty() => {
+                    legacy.push((dir_name.to_string(), instance));
+                }
+                Ok(_) => {}
+                Err(e) => tracing::warn!("Failed to load instance {}: {}", dir_name, e),
+            }
+        }
+
+        for (dir_name, instance) in legacy {
+            if let Err(e) = self.migrate(&dir_name, instance) {
+                tracing::warn!("Failed to migrate instance {}: {}", dir_name, e);
+            }
         }
-        std::fs::remove_dir_all(&instance_dir)?;
-        tracing::info!("Deleted instance: {}", name);
         Ok(())
     }
 
-    /// Rename an instance
-    pub fn rename(&self, old_name: &str, new_name: &str) -> Result<()> {
-        if old_name == new_name {
-            return Ok(());
+    /// Give an instance from before IDs existed its own ID
+    ///
+    /// Older versions named the directory after the instance and `rename`
+    /// only moved the directory, so the directory name is the display name.
+    fn migrate(&self, dir_name: &str, mut instance: Instance) -> Result<Instance> {
+        instance.info.name = dir_name.to_string();
+        instance.info.id = dir_name.to_string();
+
+        let old_dir = self.get_instance_dir(dir_name);
+        let id = slugify(dir_name);
+        if id != dir_name {
+            if let Some(pid) = running_game_pid(&old_dir) {
+                tracing::warn!("Not moving {} while the game runs (PID {})", dir_name, pid);
+            } else {
+                let id = self.unique_id(&id);
+                match std::fs::rename(&old_dir, self.get_instance_dir(&id)) {
+                    Ok(()) => instance.info.id = id,
+                    Err(e) => tracing::warn!("Failed to move instance {}: {}", dir_name, e),
+                }
+            }
         }
 
-        let old_dir = self.get_instance_dir(old_name);
-        let new_dir = self.get_instance_dir(new_name);
+        self.save(&instance)?;
+        tracing::info!("Migrated instance {} to ID {}", dir_name, instance.info.id);
+        Ok(instance)
+    }
 
-        if !old_dir.exists() {
-            anyhow::bail!("Instance '{}' not found", old_name);
+    /// Delete an instance by ID
+    pub fn delete(&self, id: &str) -> Result<()> {
+        let instance = self.load_id(id)?;
+        std::fs::remove_dir_all(self.get_instance_dir(&instance.info.id))?;
+        tracing::info!("Deleted instance: {}", instance.info.name);
+        Ok(())
+    }
+
+    /// Change an instance's display name
+    ///
+    /// The directory keeps its ID, so paths to the game and natives folders
+    /// stay valid.
+    pub fn rename(&self, key: &str, new_name: &str) -> Result<Instance> {
+        validate_name(new_name)?;
+        let mut instance = self.load(key)?;
+        if instance.info.name == new_name {
+            return Ok(instance);
         }
 
-        if new_dir.exists() {
+        let taken = self
+            .list()?
+            .iter()
+            .any(|i| i.info.name == new_name && i.info.id != instance.info.id);
+        if taken {
             anyhow::bail!("Instance '{}' already exists", new_name);
         }
 
-        std::fs::rename(&old_dir, &new_dir)?;
+        let old_name = std::mem::replace(&mut instance.info.name, new_name.to_string());
+        self.save(&instance)?;
         tracing::info!("Renamed instance: {} -> {}", old_name, new_name);
-        Ok(())
+        Ok(instance)
     }
 
     /// List all instances
@@ -238,12 +409,12 @@ impl InstanceManager {
             let entry = entry?;
             let path = entry.path();
 
-            if path.is_dir() {
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    match self.load(name) {
-                        Ok(instance) => instances.push(instance),
-                        Err(e) => tracing::warn!("Failed to load instance {}: {}", name, e),
-                    }
+            if path.join("instance.toml").is_file()
+                && let Some(name) = path.file_name().and_then(|n| n.to_str())
+            {
+                match self.load_dir(name) {
+                    Ok(instance) => instances.push(instance),
+                    Err(e) => tracing::warn!("Failed to load instance {}: {}", name, e),
                 }
             }
         }
@@ -255,6 +426,70 @@ impl InstanceManager {
     }
 }
 
+/// Directory ID for a display name: lowercase ASCII letters, digits and dashes
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+        if slug.len() >= 48 {
+            break;
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "instance".to_string()
+    } else if is_reserved_name(slug) {
+        format!("{}-instance", slug)
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Check a display name
+pub fn validate_name(name: &str) -> Result<()> {
+    if name.trim().is_empty() {
+        anyhow::bail!("Instance name cannot be empty");
+    }
+    if name.trim() != name {
+        anyhow::bail!("Instance name cannot start or end with whitespace");
+    }
+    if name.chars().any(char::is_control) {
+        anyhow::bail!("Instance name cannot contain control characters");
+    }
+    Ok(())
+}
+
+/// Check that an instance ID is a single directory name that is safe on
+/// every platform
+pub fn validate_id(id: &str) -> Result<()> {
+    let invalid = id.is_empty()
+        || id == "."
+        || id == ".."
+        || id.ends_with(['.', ' '])
+        || id.chars().any(|c| {
+            c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+        })
+        || is_reserved_name(id);
+    if invalid {
+        anyhow::bail!("Invalid instance ID: {}", id);
+    }
+    Ok(())
+}
+
+/// Device names Windows reserves regardless of extension
+fn is_reserved_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).to_ascii_uppercase();
+    matches!(stem.as_str(), "CON" | "PRN" | "AUX" | "NUL")
+        || ((stem.starts_with("COM") || stem.starts_with("LPT"))
+            && stem.len() == 4
+            && stem.as_bytes()[3].is_ascii_digit()
+            && stem.as_bytes()[3] != b'0')
+}
+
 impl Default for InstanceManager {
     fn default() -> Self {
         Self::new()
@@ -262,3 +497,139 @@ impl Default for InstanceManager {
 }
 
 use std::path::Path;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::temp::TempDir;
+
+    fn manager(tag: &str) -> (TempDir, InstanceManager) {
+        let dir = TempDir::new(tag);
+        let manager = InstanceManager::with_dir(&dir);
+        (dir, manager)
+    }
+
+    #[test]
+    fn test_rename_keeps_directory() {
+        let (_dir, manager) = manager("instance-rename");
+        let instance = manager
+            .create("My Pack", "1.20.1", ModLoader::Fabric, None)
+            .unwrap();
+        assert_eq!(instance.info.id, "my-pack");
+        let game_dir = manager.get_game_dir(&instance.info.id);
+
+        manager.rename("My Pack", "Survival: Season 2").unwrap();
+        let loaded = manager.load("Survival: Season 2").unwrap();
+        assert_eq!(loaded.info.id, "my-pack");
+        assert_eq!(loaded.info.name, "Survival: Season 2");
+        assert_eq!(
+            manager.load("my-pack").unwrap().info.name,
+            "Survival: Season 2"
+        );
+        assert!(manager.load("My Pack").is_err());
+        assert!(game_dir.exists());
+
+        manager
+            .create("Other", "1.20.1", ModLoader::Vanilla, None)
+            .unwrap();
+        assert!(manager.rename("Other", "Survival: Season 2").is_err());
+        assert!(
+            manager
+                .create("Survival: Season 2", "1.20.1", ModLoader::Vanilla, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_load_prefers_display_name() {
+        let (_dir, manager) = manager("instance-load-name");
+        let first = manager
+            .create("My Pack", "1.20.1", ModLoader::Vanilla, None)
+            .unwrap();
+        let second = manager
+            .create("my-pack", "1.20.1", ModLoader::Vanilla, None)
+            .unwrap();
+        assert_eq!(first.info.id, "my-pack");
+        assert_eq!(second.info.id, "my-pack-2");
+
+        assert_eq!(manager.load("my-pack").unwrap().info.id, "my-pack-2");
+        assert_eq!(manager.load("My Pack").unwrap().info.id, "my-pack");
+        assert_eq!(manager.load("my-pack-2").unwrap().info.name, "my-pack");
+    }
+
+    #[test]
+    fn test_unsafe_names() {
+        let (dir, manager) = manager("instance-unsafe");
+        for name in ["../escape", "a/b\\c", "CON", "lpt1.txt", ".."] {
+            let instance = manager
+                .create(name, "1.20.1", ModLoader::Vanilla, None)
+                .unwrap();
+            assert!(
+                validate_id(&instance.info.id).is_ok(),
+                "{}",
+                instance.info.id
+            );
+            assert_eq!(
+                manager.get_instance_dir(&instance.info.id).parent(),
+                Some(&*dir)
+            );
+            assert_eq!(manager.load(name).unwrap().info.name, name);
+        }
+        assert_eq!(slugify("CON"), "con-instance");
+        assert_eq!(slugify("../escape"), "escape");
+        assert_eq!(slugify(".."), "instance");
+        assert!(!dir.parent().unwrap().join("escape").exists());
+
+        for name in ["", "  ", "tab\there"] {
+            assert!(
+                manager
+                    .create(name, "1.20.1", ModLoader::Vanilla, None)
+                    .is_err()
+            );
+        }
+        for id in ["..", "a/b", "a\\b", "nul", "COM3.log", "dot."] {
+            assert!(validate_id(id).is_err(), "{}", id);
+        }
+    }
+
+    #[test]
+    fn test_migrate_legacy_instance() {
+        let (dir, manager) = manager("instance-migrate");
+        // Renamed by an older version: the directory moved, the TOML did not
+        let legacy = dir.join("New Name");
+        std::fs::create_dir_all(legacy.join(".minecraft/mods")).unwrap();
+        std::fs::write(
+            legacy.join("instance.toml"),
+            r#"
+[info]
+name = "Old Name"
+version = "1.20.1"
+loader = "fabric"
+created_at = "2024-01-01T00:00:00Z"
+
+[java]
+
+[game]
+"#,
+        )
+        .unwrap();
+
+        // Listing reads the legacy layout without moving anything
+        let instances = manager.list().unwrap();
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].info.name, "New Name");
+        assert!(legacy.exists());
+
+        manager.migrate_legacy().unwrap();
+        let instances = manager.list().unwrap();
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].info.name, "New Name");
+        assert_eq!(instances[0].info.id, "new-name");
+        assert!(!legacy.exists());
+        assert!(manager.get_game_dir("new-name").join("mods").exists());
+
+        // The ID is persisted, so loading again does not move anything
+        let reloaded = manager.load("new-name").unwrap();
+        assert_eq!(reloaded.info.name, "New Name");
+    }
+}
@@ -0,0 +1,145 @@
+//! Instance organisation
+//!
+//! Sorting, grouping and tag helpers for instance lists.
+
+use super::{Instance, InstanceInfo};
+use crate::config::InstanceSort;
+use crate::core::mods::compare_versions;
+use std::cmp::Ordering;
+
+/// Sort instances for display, favourites first
+pub fn sort_instances(instances: &mut [Instance], sort: InstanceSort) {
+    instances.sort_by(|a, b| {
+        let (a, b) = (&a.info, &b.info);
+        b.favorite
+            .cmp(&a.favorite)
+            .then_with(|| compare(a, b, sort))
+            .then_with(|| compare_names(a, b))
+    });
+}
+
+fn compare(a: &InstanceInfo, b: &InstanceInfo, sort: InstanceSort) -> Ordering {
+    match sort {
+        InstanceSort::Created => b.created_at.cmp(&a.created_at),
+        InstanceSort::Name => compare_names(a, b),
+        // Never played sorts last
+        InstanceSort::LastPlayed => b.last_played.cmp(&a.last_played),
+        InstanceSort::Version => compare_versions(&b.version, &a.version),
+        InstanceSort::Loader => a.loader.to_string().cmp(&b.loader.to_string()),
+    }
+}
+
+fn compare_names(a: &InstanceInfo, b: &InstanceInfo) -> Ordering {
+    a.name.to_lowercase().cmp(&b.name.to_lowercase())
+}
+
+impl InstanceInfo {
+    /// Whether the instance has a tag, ignoring case
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag))
+    }
+
+    /// Whether the instance is in a group, ignoring case
+    pub fn in_group(&self, group: &str) -> bool {
+        self.group.eq_ignore_ascii_case(group)
+    }
+}
+
+/// Group names in use, sorted
+pub fn groups(instances: &[Instance]) -> Vec<String> {
+    let mut groups: Vec<String> = instances
+        .iter()
+        .map(|i| i.info.group.clone())
+        .filter(|g| !g.is_empty())
+        .collect();
+    groups.sort_by_key(|g| g.to_lowercase());
+    groups.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    groups
+}
+
+/// Tags in use, sorted
+pub fn tags(instances: &[Instance]) -> Vec<String> {
+    let mut tags: Vec<String> = instances
+        .iter()
+        .flat_map(|i| i.info.tags.iter().cloned())
+        .collect();
+    tags.sort_by_key(|t| t.to_lowercase());
+    tags.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    tags
+}
+
+/// Parse comma-separated tags, dropping blanks and duplicates
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = Vec::new();
+    for tag in input.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+        if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+            tags.push(tag.to_string());
+        }
+    }
+    tags
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::{GameConfig, InstanceJavaConfig, ModLoader};
+
+    fn instance(name: &str, version: &str, loader: ModLoader, favorite: bool) -> Instance {
+        Instance {
+            info: InstanceInfo {
+                id: name.to_lowercase(),
+                name: name.to_string(),
+                version: version.to_string(),
+                loader,
+                loader_version: None,
+                created_at: chrono::Utc::now(),
+                group: String::new(),
+                tags: Vec::new(),
+                favorite,
+                icon: String::new(),
+                last_played: None,
+                playtime_seconds: 0,
+                launch_count: 0,
+            },
+            java: InstanceJavaConfig::default(),
+            game: GameConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_sort_and_tags() {
+        let mut instances = vec![
+            instance("beta", "1.8.9", ModLoader::Forge, false),
+            instance("Alpha", "1.20.1", ModLoader::Fabric, false),
+            instance("gamma", "1.12.2", ModLoader::Vanilla, true),
+        ];
+        let names = |list: &[Instance]| -> Vec<String> {
+            list.iter().map(|i| i.info.name.clone()).collect()
+        };
+
+        sort_instances(&mut instances, InstanceSort::Name);
+        assert_eq!(names(&instances), ["gamma", "Alpha", "beta"]);
+        sort_instances(&mut instances, InstanceSort::Version);
+        assert_eq!(names(&instances), ["gamma", "Alpha", "beta"]);
+        instances[0].info.favorite = false;
+        sort_instances(&mut instances, InstanceSort::Version);
+        assert_eq!(names(&instances), ["Alpha", "gamma", "beta"]);
+        sort_instances(&mut instances, InstanceSort::Loader);
+        assert_eq!(names(&instances), ["Alpha", "beta", "gamma"]);
+        instances[2].info.last_played = Some(chrono::Utc::now());
+        instances[0].info.last_played = Some(chrono::DateTime::UNIX_EPOCH);
+        sort_instances(&mut instances, InstanceSort::LastPlayed);
+        assert_eq!(names(&instances), ["gamma", "Alpha", "beta"]);
+
+        instances[0].info.tags = parse_tags(" pvp, Tech ,, pvp , PVP");
+        instances[1].info.tags = vec!["tech".to_string()];
+        assert_eq!(instances[0].info.tags, ["pvp", "Tech"]);
+        assert!(instances[1].info.has_tag("TECH"));
+        assert_eq!(tags(&instances), ["pvp", "Tech"]);
+
+        instances[2].info.group = "Modded".to_string();
+        instances[0].info.group = "modded".to_string();
+        assert_eq!(groups(&instances), ["modded"]);
+        assert!(instances[2].info.in_group("MODDED"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Running game sessions
+//!
+//! Records the PID of a launched game in the instance directory so other
+//! operations (world backups, restores) can tell whether it is in use, and
+//! adds each finished session to the instance's playtime.
+
+use super::{Instance, InstanceInfo, InstanceManager};
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::time::{Duration, Instant};
+
+/// PID file inside the instance directory
+const PID_FILE: &str = "game.pid";
+
+/// Remember the game process started for an instance
+pub fn record_game_pid(instance_dir: &Path, pid: u32) -> Result<()> {
+    std::fs::write(instance_dir.join(PID_FILE), pid.to_string())
+        .context("Failed to record game process")
+}
+
+/// PID of the instance's game if it is still running
+///
+/// A stale PID file left by a finished game is removed.
+pub fn running_game_pid(instance_dir: &Path) -> Option<u32> {
+    let path = instance_dir.join(PID_FILE);
+    let pid: u32 = std::fs::read_to_string(&path).ok()?.trim().parse().ok()?;
+    if process_alive(pid) {
+        Some(pid)
+    } else {
+        let _ = std::fs::remove_file(&path);
+        None
+    }
+}
+
+/// A running game started by the launcher
+pub struct GameSession {
+    child: Child,
+    instances_dir: PathBuf,
+    instance_id: String,
+    started: Instant,
+}
+
+impl GameSession {
+    pub fn new(child: Child, manager: &InstanceManager, instance_id: &str) -> Self {
+        Self {
+            child,
+            instances_dir: manager.instances_dir().to_path_buf(),
+            instance_id: instance_id.to_string(),
+            started: Instant::now(),
+        }
+    }
+
+    /// Process ID of the game
+    pub fn pid(&self) -> u32 {
+        self.child.id()
+    }
+
+    /// Wait for the game to exit and add the session to the instance stats
+    ///
+    /// Failing to save the stats is logged; it does not fail the session.
+    pub fn wait(mut self) -> Result<Duration> {
+        self.child.wait().context("Failed to wait for Minecraft")?;
+        let played = self.started.elapsed();
+
+        let manager = InstanceManager::with_dir(&self.instances_dir);
+        let instance_dir = manager.get_instance_dir(&self.instance_id);
+        let _ = std::fs::remove_file(instance_dir.join(PID_FILE));
+        if let Err(e) = manager.record_session(&self.instance_id, played) {
+            tracing::warn!("Failed to record playtime: {}", e);
+        }
+        Ok(played)
+    }
+}
+
+impl InstanceManager {
+    /// Add a finished game session to an instance's stats
+    pub fn record_session(&self, id: &str, played: Duration) -> Result<Instance> {
+        let mut instance = self.load_id(id)?;
+        instance.info.launch_count += 1;
+        instance.info.playtime_seconds += played.as_secs();
+        instance.info.last_played = Some(chrono::Utc::now());
+        self.save(&instance)?;
+        Ok(instance)
+    }
+}
+
+impl InstanceInfo {
+    /// One-line playtime summary ("3h 12m · 14 launches · last 2024-05-01")
+    pub fn play_stats(&self) -> String {
+        let Some(last_played) = self.last_played else {
+            return "Never played".to_string();
+        };
+        format!(
+            "{} · {} launch{} · last {}",
+            format_playtime(self.playtime_seconds),
+            self.launch_count,
+            if self.launch_count == 1 { "" } else { "es" },
+            last_played
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+        )
+    }
+}
+
+/// Human-readable playtime ("45m", "3h 12m")
+pub fn format_playtime(seconds: u64) -> String {
+    let minutes = seconds / 60;
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn process_alive(pid: u32) -> bool {
+    // A game the GUI never waited on lingers as a zombie until the launcher exits
+    std::fs::read_to_string(format!("/proc/{}/stat", pid))
+        .ok()
+        .and_then(|stat| {
+            let state = stat.rsplit_once(')')?.1.trim_start().chars().next()?;
+            Some(state != 'Z' && state != 'X')
+        })
+        .unwrap_or(false)
+}
+
+#[cfg(all(unix, not(target_os = "linux")))]
+fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("kill")
+        .args(["-0", &pid.to_string()])
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|s| s.success())
+}
+
+#[cfg(windows)]
+fn process_alive(pid: u32) -> bool {
+    std::process::Command::new("tasklist")
+        .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+        .output()
+        .is_ok_and(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::temp::TempDir;
+
+    #[test]
+    #[cfg(unix)]
+    fn test_running_game_pid() {
+        let dir = TempDir::new("session");
+
+        assert_eq!(running_game_pid(&dir), None);
+
+        record_game_pid(&dir, std::process::id()).unwrap();
+        assert_eq!(running_game_pid(&dir), Some(std::process::id()));
+
+        let mut child = std::process::Command::new("true").spawn().unwrap();
+        let pid = child.id();
+        child.wait().unwrap();
+        record_game_pid(&dir, pid).unwrap();
+        assert_eq!(running_game_pid(&dir), None);
+        assert!(!dir.join(PID_FILE).exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_session_records_playtime() {
+        let dir = TempDir::new("playtime");
+        let manager = InstanceManager::with_dir(&dir);
+        let instance = manager
+            .create("Pack", "1.20.1", super::super::ModLoader::Vanilla, None)
+            .unwrap();
+        assert_eq!(instance.info.play_stats(), "Never played");
+
+        manager
+            .record_session(&instance.info.id, Duration::from_secs(3 * 3600 + 12 * 60))
+            .unwrap();
+        let child = std::process::Command::new("true").spawn().unwrap();
+        GameSession::new(child, &manager, &instance.info.id)
+            .wait()
+            .unwrap();
+
+        let loaded = manager.load("Pack").unwrap();
+        assert_eq!(loaded.info.launch_count, 2);
+        assert_eq!(loaded.info.playtime_seconds, 3 * 3600 + 12 * 60);
+        assert!(loaded.info.last_played.is_some());
+        assert!(
+            loaded
+                .info
+                .play_stats()
+                .starts_with("3h 12m · 2 launches · last ")
+        );
+        assert_eq!(format_playtime(59), "0m");
+    }
+}
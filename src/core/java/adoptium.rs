@@ -0,0 +1,436 @@
+//! Adoptium provider
+//!
+//! Downloads Temurin JREs from the Adoptium API, verifies their SHA256
+//! checksum and installs them atomically.
+
+use super::{JavaProvider, RuntimeRequest};
+use crate::util::hash::sha256_bytes;
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use serde::Deserialize;
+use std::io::Cursor;
+use std::path::{Component, Path, PathBuf};
+
+/// Adoptium API base URL
+const ADOPTIUM_API: &str = "https://api.adoptium.net/v3";
+
+/// Temurin runtimes from Adoptium, installed as `java/<major>`
+pub struct AdoptiumProvider {
+    java_dir: PathBuf,
+}
+
+impl AdoptiumProvider {
+    pub fn new(java_dir: &Path) -> Self {
+        Self {
+            java_dir: java_dir.to_path_buf(),
+        }
+    }
+
+    /// Get the Java home directory for a specific version
+    fn get_java_home(&self, major_version: u32) -> PathBuf {
+        self.java_dir.join(major_version.to_string())
+    }
+
+    /// Get the java executable path for a specific version
+    fn get_java_executable(&self, major_version: u32) -> PathBuf {
+        let java_home = self.get_java_home(major_version);
+        #[cfg(target_os = "windows")]
+        {
+            java_home.join("bin").join("java.exe")
+        }
+        #[cfg(target_os = "macos")]
+        {
+            // macOS JRE has Contents/Home structure
+            java_home
+                .join("Contents")
+                .join("Home")
+                .join("bin")
+                .join("java")
+        }
+        #[cfg(target_os = "linux")]
+        {
+            java_home.join("bin").join("java")
+        }
+    }
+
+    /// Get the API URL listing the latest Adoptium JRE for a version
+    fn get_download_url(&self, major_version: u32) -> String {
+        let os = if cfg!(target_os = "macos") {
+            "mac"
+        } else if cfg!(target_os = "linux") {
+            "linux"
+        } else {
+            "windows"
+        };
+
+        let arch = if cfg!(target_arch = "aarch64") {
+            "aarch64"
+        } else {
+            "x64"
+        };
+
+        format!(
+            "{}/assets/latest/{}/hotspot?architecture={}&image_type=jre&os={}&vendor=eclipse",
+            ADOPTIUM_API, major_version, arch, os
+        )
+    }
+
+    /// Look up the archive to download for a version
+    async fn fetch_package(&self, major_version: u32) -> Result<AdoptiumPackage> {
+        let url = self.get_download_url(major_version);
+        let response = reqwest::get(&url)
+            .await
+            .context("Failed to query Adoptium")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to query Adoptium: HTTP {}", response.status());
+        }
+
+        let assets: Vec<AdoptiumAsset> = response
+            .json()
+            .await
+            .context("Failed to parse Adoptium response")?;
+
+        assets
+            .into_iter()
+            .next()
+            .map(|asset| asset.binary.package)
+            .context(format!(
+                "Adoptium has no Java {} JRE for this platform",
+                major_version
+            ))
+    }
+
+    /// Download and install Java from Adoptium
+    ///
+    /// The archive is checked against its SHA256 checksum and extracted to a
+    /// temporary directory, which is only renamed into place once complete.
+    async fn download(
+        &self,
+        major_version: u32,
+        progress_callback: &(dyn Fn(&str) + Sync),
+    ) -> Result<()> {
+        let package = self.fetch_package(major_version).await?;
+        let java_home = self.get_java_home(major_version);
+
+        progress_callback(&format!("Downloading Java {}...", major_version));
+
+        let response = reqwest::get(&package.link)
+            .await
+            .context("Failed to download Java")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Failed to download Java: HTTP {}", response.status());
+        }
+
+        let bytes = response.bytes().await?;
+
+        let actual = sha256_bytes(&bytes);
+        if !actual.eq_ignore_ascii_case(&package.checksum) {
+            anyhow::bail!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                package.name,
+                package.checksum,
+                actual
+            );
+        }
+
+        progress_callback(&format!("Extracting Java {}...", major_version));
+
+        // Extract next to the final location so the rename stays on one filesystem
+        let temp_home = self.java_dir.join(format!(".{}.tmp", major_version));
+        if temp_home.exists() {
+            std::fs::remove_dir_all(&temp_home)?;
+        }
+        std::fs::create_dir_all(&temp_home)?;
+
+        let extracted = if package.name.ends_with(".zip") {
+            extract_zip(&bytes, &temp_home)
+        } else {
+            extract_tar_gz(&bytes, &temp_home)
+        };
+        if let Err(e) = extracted {
+            let _ = std::fs::remove_dir_all(&temp_home);
+            return Err(e.context("Failed to extract Java archive"));
+        }
+
+        // Swap the finished install into place
+        if java_home.exists() {
+            std::fs::remove_dir_all(&java_home)?;
+        }
+        std::fs::rename(&temp_home, &java_home).context("Failed to install Java")?;
+
+        progress_callback(&format!("Java {} installed!", major_version));
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumAsset {
+    binary: AdoptiumBinary,
+}
+
+#[derive(Debug, Deserialize)]
+struct AdoptiumBinary {
+    package: AdoptiumPackage,
+}
+
+/// Downloadable archive of a release
+#[derive(Debug, Deserialize)]
+struct AdoptiumPackage {
+    name: String,
+    link: String,
+    /// SHA256 of the archive
+    checksum: String,
+}
+
+/// Archive entry path without its top-level directory
+///
+/// Returns `None` for the top-level directory itself and for paths that
+/// would escape the destination.
+fn strip_top_level(path: &Path) -> Option<PathBuf> {
+    let mut components = path.components();
+    components.next()?;
+
+    let mut stripped = PathBuf::new();
+    for component in components {
+        match component {
+            Component::Normal(part) => stripped.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!stripped.as_os_str().is_empty()).then_some(stripped)
+}
+
+/// Whether a symlink at `entry` (relative to the destination) pointing to
+/// `link` resolves inside the destination
+fn link_stays_inside(entry: &Path, link: &Path) -> bool {
+    let mut depth = entry.components().count().saturating_sub(1);
+    for component in link.components() {
+        match component {
+            Component::Normal(_) => depth += 1,
+            Component::CurDir => {}
+            Component::ParentDir if depth > 0 => depth -= 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+/// Extract a `.tar.gz` archive, preserving permissions and symlinks
+///
+/// Links that point outside the destination are rejected.
+fn extract_tar_gz(bytes: &[u8], dest: &Path) -> Result<()> {
+    let mut archive = tar::Archive::new(GzDecoder::new(bytes));
+    archive.set_preserve_permissions(true);
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+        let Some(relative) = strip_top_level(&path) else {
+            continue;
+        };
+
+        let target = dest.join(&relative);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        match entry.header().entry_type() {
+            tar::EntryType::Symlink => {
+                let link = entry.link_name()?.unwrap_or_default();
+                if !link_stays_inside(&relative, &link) {
+                    anyhow::bail!("Unsafe symlink in archive: {}", path.display());
+                }
+            }
+            tar::EntryType::Link => {
+                // Hard link targets are archive paths, not relative to the entry
+                let source = entry
+                    .link_name()?
+                    .as_deref()
+                    .and_then(strip_top_level)
+                    .with_context(|| format!("Unsafe hard link in archive: {}", path.display()))?;
+                std::fs::hard_link(dest.join(source), &target)
+                    .with_context(|| format!("Failed to extract {}", path.display()))?;
+                continue;
+            }
+            _ => {}
+        }
+
+        entry
+            .unpack(&target)
+            .with_context(|| format!("Failed to extract {}", path.display()))?;
+    }
+
+    Ok(())
+}
+
+/// Extract a `.zip` archive, preserving Unix permissions when present
+fn extract_zip(bytes: &[u8], dest: &Path) -> Result<()> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))?;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(relative) = entry.enclosed_name().as_deref().and_then(strip_top_level) else {
+            continue;
+        };
+
+        let path = dest.join(relative);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&path)?;
+            continue;
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut outfile = std::fs::File::create(&path)?;
+        std::io::copy(&mut entry, &mut outfile)?;
+
+        #[cfg(unix)]
+        if let Some(mode) = entry.unix_mode() {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(mode))?;
+        }
+    }
+
+    Ok(())
+}
+
+impl JavaProvider for AdoptiumProvider {
+    fn name(&self) -> &'static str {
+        "Adoptium"
+    }
+
+    fn installed_executable(&self, request: &RuntimeRequest) -> Option<PathBuf> {
+        let java_exe = self.get_java_executable(request.major_version);
+        java_exe.exists().then_some(java_exe)
+    }
+
+    async fn install(
+        &self,
+        request: &RuntimeRequest,
+        progress: &(dyn Fn(&str) + Sync),
+    ) -> Result<PathBuf> {
+        self.download(request.major_version, progress).await?;
+
+        let java_exe = self.get_java_executable(request.major_version);
+        if java_exe.exists() {
+            Ok(java_exe)
+        } else {
+            anyhow::bail!("Java installation failed: executable not found")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::temp::TempDir;
+
+    #[test]
+    fn test_download_url() {
+        let provider = AdoptiumProvider::new(Path::new("/tmp"));
+        let url = provider.get_download_url(17);
+        assert!(url.contains("adoptium"));
+        assert!(url.contains("/17/"));
+    }
+
+    #[test]
+    fn test_strip_top_level() {
+        assert_eq!(
+            strip_top_level(Path::new("jdk-17.0.10+7-jre/bin/java")),
+            Some(PathBuf::from("bin/java"))
+        );
+        assert_eq!(strip_top_level(Path::new("jdk-17.0.10+7-jre/")), None);
+        assert_eq!(strip_top_level(Path::new("jdk/../../etc/passwd")), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_tar_gz_preserves_permissions_and_symlinks() {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::fast()));
+
+        let java = b"#!/bin/sh\n";
+        let mut header = tar::Header::new_gnu();
+        header.set_size(java.len() as u64);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "jdk-17-jre/bin/java", &java[..])
+            .unwrap();
+
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Symlink);
+        header.set_size(0);
+        header.set_mode(0o777);
+        builder
+            .append_link(&mut header, "jdk-17-jre/lib/java-link", "../bin/java")
+            .unwrap();
+
+        let bytes = builder.into_inner().unwrap().finish().unwrap();
+
+        let dest = TempDir::new("tar-test");
+        extract_tar_gz(&bytes, &dest).unwrap();
+
+        let mode = std::fs::metadata(dest.join("bin/java"))
+            .unwrap()
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o755);
+
+        let link = std::fs::read_link(dest.join("lib/java-link")).unwrap();
+        assert_eq!(link, PathBuf::from("../bin/java"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_tar_gz_rejects_escaping_links() {
+        use flate2::Compression;
+        use flate2::write::GzEncoder;
+
+        let archive = |kind: tar::EntryType, target: &str| {
+            let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::fast()));
+            let mut header = tar::Header::new_gnu();
+            header.set_size(4);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, "jdk-17-jre/release", &b"17\n\n"[..])
+                .unwrap();
+
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(kind);
+            header.set_size(0);
+            header.set_mode(0o777);
+            builder
+                .append_link(&mut header, "jdk-17-jre/lib/link", target)
+                .unwrap();
+            builder.into_inner().unwrap().finish().unwrap()
+        };
+
+        let dest = TempDir::new("tar-links");
+        for (kind, target) in [
+            (tar::EntryType::Symlink, "../../outside"),
+            (tar::EntryType::Symlink, "/etc/passwd"),
+            (tar::EntryType::Link, "jdk-17-jre/../../outside"),
+        ] {
+            let _ = std::fs::remove_dir_all(&dest);
+            assert!(
+                extract_tar_gz(&archive(kind, target), &dest).is_err(),
+                "{}",
+                target
+            );
+        }
+
+        let _ = std::fs::remove_dir_all(&dest);
+        extract_tar_gz(&archive(tar::EntryType::Link, "jdk-17-jre/release"), &dest).unwrap();
+        assert_eq!(std::fs::read(dest.join("lib/link")).unwrap(), b"17\n\n");
+    }
+}
@@ -1,346 +1,389 @@
 // Java Runtime Manager
-// Handles downloading and managing Java runtimes from Adoptium
+// Handles downloading and managing Java runtimes from Mojang and Adoptium
 
+mod adoptium;
+mod mojang;
+mod probe;
+mod registry;
+
+pub use adoptium::AdoptiumProvider;
+pub use mojang::MojangProvider;
+pub use probe::JavaProbe;
+pub use registry::{JavaInstallation, JavaRegistry};
+
+use crate::config::JavaProviderKind;
+use crate::core::version::JavaVersion;
 use anyhow::{Context, Result};
+use std::future::Future;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-
-/// Adoptium API base URL
-const ADOPTIUM_API: &str = "https://api.adoptium.net/v3";
 
 /// Java version requirements for Minecraft
+///
+/// Only used when a version JSON has no `javaVersion` entry.
 #[derive(Debug, Clone, Copy)]
 pub struct JavaRequirement {
     pub major_version: u32,
-    pub mc_version_pattern: &'static str,
+    /// First release needing this Java, as (minor, patch) of `1.minor.patch`
+    pub since_release: (u32, u32),
+    /// First snapshot needing this Java, as (year, week) of `YYwWWa`
+    pub since_snapshot: (u32, u32),
 }
 
-/// Known Java requirements for Minecraft versions
+/// Known Java requirements for Minecraft versions, newest first
 pub const JAVA_REQUIREMENTS: &[JavaRequirement] = &[
-    // Minecraft 1.21+ requires Java 21
+    // Minecraft 1.20.5+ requires Java 21
     JavaRequirement {
         major_version: 21,
-        mc_version_pattern: "1.21",
-    },
-    // Minecraft 1.18-1.20 requires Java 17
-    JavaRequirement {
-        major_version: 17,
-        mc_version_pattern: "1.20",
-    },
-    JavaRequirement {
-        major_version: 17,
-        mc_version_pattern: "1.19",
-    },
-    JavaRequirement {
-        major_version: 17,
-        mc_version_pattern: "1.18",
+        since_release: (20, 5),
+        since_snapshot: (24, 14),
     },
-    // Minecraft 1.17 requires Java 16+
+    // Minecraft 1.17-1.20.4 requires Java 16+ (we use 17)
     JavaRequirement {
         major_version: 17,
-        mc_version_pattern: "1.17",
-    },
-    // Older versions work with Java 8
-    JavaRequirement {
-        major_version: 8,
-        mc_version_pattern: "1.16",
+        since_release: (17, 0),
+        since_snapshot: (21, 19),
     },
 ];
 
+/// Java used by versions older than every known requirement
+const LEGACY_JAVA: u32 = 8;
+
+/// Minecraft version id, as far as Java requirements are concerned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum McVersion {
+    /// `1.minor.patch`, including pre-releases and release candidates
+    Release(u32, u32),
+    /// Weekly snapshot `YYwWWa`
+    Snapshot(u32, u32),
+    /// Pre-1.0 alpha, beta and classic versions
+    Ancient,
+}
+
+impl McVersion {
+    fn parse(id: &str) -> Option<Self> {
+        if let Some(rest) = id.strip_prefix("1.") {
+            let numeric: String = rest
+                .chars()
+                .take_while(|c| c.is_ascii_digit() || *c == '.')
+                .collect();
+            let mut parts = numeric.split('.');
+            let minor = parts.next()?.parse().ok()?;
+            let patch = parts.next().and_then(|p| p.parse().ok()).unwrap_or(0);
+            return Some(McVersion::Release(minor, patch));
+        }
+
+        let bytes = id.as_bytes();
+        if bytes.len() >= 5
+            && bytes[..2].iter().all(u8::is_ascii_digit)
+            && bytes[2] == b'w'
+            && bytes[3..5].iter().all(u8::is_ascii_digit)
+        {
+            let year = id[..2].parse().ok()?;
+            let week = id[3..5].parse().ok()?;
+            return Some(McVersion::Snapshot(year, week));
+        }
+
+        let ancient = ["a", "b", "c", "rd-", "inf-"];
+        if ancient.iter().any(|prefix| {
+            id.strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        }) {
+            return Some(McVersion::Ancient);
+        }
+
+        None
+    }
+}
+
+/// Java runtime needed by a Minecraft version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeRequest {
+    /// Java major version (e.g. 17)
+    pub major_version: u32,
+    /// Mojang runtime component (e.g. `java-runtime-gamma`)
+    pub component: String,
+}
+
+impl RuntimeRequest {
+    /// Request a major version, using the component Mojang ships it as
+    pub fn from_major(major_version: u32) -> Self {
+        let component = match major_version {
+            8 => "jre-legacy",
+            16 => "java-runtime-alpha",
+            17 => "java-runtime-gamma",
+            _ => "java-runtime-delta",
+        };
+        Self {
+            major_version,
+            component: component.to_string(),
+        }
+    }
+}
+
+impl From<&JavaVersion> for RuntimeRequest {
+    fn from(java_version: &JavaVersion) -> Self {
+        Self {
+            major_version: java_version.major_version,
+            component: java_version.component.clone(),
+        }
+    }
+}
+
+/// Source of downloadable Java runtimes
+pub trait JavaProvider {
+    /// Provider name for logs and messages
+    fn name(&self) -> &'static str;
+
+    /// Get the java executable of an installed runtime
+    fn installed_executable(&self, request: &RuntimeRequest) -> Option<PathBuf>;
+
+    /// Download and install a runtime, returning its java executable
+    fn install(
+        &self,
+        request: &RuntimeRequest,
+        progress: &(dyn Fn(&str) + Sync),
+    ) -> impl Future<Output = Result<PathBuf>> + Send;
+}
+
 /// Manages Java runtime downloads and installations
 pub struct JavaManager {
     java_dir: PathBuf,
+    mojang: MojangProvider,
+    adoptium: AdoptiumProvider,
+    provider: JavaProviderKind,
 }
 
 impl JavaManager {
     /// Create a new JavaManager
     pub fn new(data_dir: &Path) -> Self {
+        let java_dir = data_dir.join("java");
         Self {
-            java_dir: data_dir.join("java"),
+            mojang: MojangProvider::new(&java_dir),
+            adoptium: AdoptiumProvider::new(&java_dir),
+            provider: JavaProviderKind::default(),
+            java_dir,
         }
     }
 
+    /// Select the preferred provider for downloads
+    pub fn with_provider(mut self, provider: JavaProviderKind) -> Self {
+        self.provider = provider;
+        self
+    }
+
     /// Get the required Java major version for a Minecraft version
+    ///
+    /// Fallback for version JSONs without `javaVersion`. Versions we can't
+    /// place (year-based ids like `25.1`, unknown formats) get the newest
+    /// known requirement.
     pub fn get_required_version(mc_version: &str) -> u32 {
-        for req in JAVA_REQUIREMENTS {
-            if mc_version.starts_with(req.mc_version_pattern) {
-                return req.major_version;
-            }
-        }
-        // Default to Java 8 for very old versions
-        8
+        let newest = JAVA_REQUIREMENTS[0].major_version;
+        let requirement = match McVersion::parse(mc_version) {
+            Some(McVersion::Release(minor, patch)) => JAVA_REQUIREMENTS
+                .iter()
+                .find(|req| (minor, patch) >= req.since_release),
+            Some(McVersion::Snapshot(year, week)) => JAVA_REQUIREMENTS
+                .iter()
+                .find(|req| (year, week) >= req.since_snapshot),
+            Some(McVersion::Ancient) => return LEGACY_JAVA,
+            None => return newest,
+        };
+        requirement.map_or(LEGACY_JAVA, |req| req.major_version)
     }
 
-    /// Get the Java home directory for a specific version
-    fn get_java_home(&self, major_version: u32) -> PathBuf {
-        self.java_dir.join(major_version.to_string())
+    /// Describe a problem with a user-selected Java
+    ///
+    /// Warns when its version can't be read, when it is built for another
+    /// CPU architecture, or when its major version differs from the
+    /// requirement. Returns `None` when it matches.
+    pub fn forced_java_warning(java_path: &Path, required_major: u32) -> Option<String> {
+        let Some(probe) = JavaProbe::run(java_path) else {
+            return Some(format!(
+                "Could not determine the version of Java at {}; Minecraft needs Java {}",
+                java_path.display(),
+                required_major
+            ));
+        };
+        let actual = probe.major_version;
+
+        if !probe.matches_host_arch() {
+            Some(format!(
+                "Java at {} is a {} JVM but this machine is {}; native libraries will not load",
+                java_path.display(),
+                probe.arch,
+                probe::host_arch()
+            ))
+        } else if actual < required_major {
+            Some(format!(
+                "Java at {} is version {}, but this Minecraft version needs Java {} or newer; \
+                 the game will likely fail to start",
+                java_path.display(),
+                actual,
+                required_major
+            ))
+        } else if actual > required_major {
+            Some(format!(
+                "Java at {} is version {}, newer than the Java {} this Minecraft version \
+                 was built for; older mod loaders may not work",
+                java_path.display(),
+                actual,
+                required_major
+            ))
+        } else {
+            None
+        }
     }
 
-    /// Get the java executable path for a specific version
-    fn get_java_executable(&self, major_version: u32) -> PathBuf {
-        let java_home = self.get_java_home(major_version);
-        #[cfg(target_os = "windows")]
-        {
-            java_home.join("bin").join("java.exe")
-        }
-        #[cfg(target_os = "macos")]
-        {
-            // macOS JRE has Contents/Home structure
-            java_home
-                .join("Contents")
-                .join("Home")
-                .join("bin")
-                .join("java")
-        }
-        #[cfg(target_os = "linux")]
-        {
-            java_home.join("bin").join("java")
+    /// Get a managed runtime from any provider, preferred provider first
+    fn installed_executable(&self, request: &RuntimeRequest) -> Option<PathBuf> {
+        match self.provider {
+            JavaProviderKind::Mojang => self
+                .mojang
+                .installed_executable(request)
+                .or_else(|| self.adoptium.installed_executable(request)),
+            JavaProviderKind::Adoptium => self
+                .adoptium
+                .installed_executable(request)
+                .or_else(|| self.mojang.installed_executable(request)),
         }
     }
 
-    /// Check if a Java version is installed locally
-    pub fn is_installed(&self, major_version: u32) -> bool {
-        let java_exe = self.get_java_executable(major_version);
-        java_exe.exists()
+    /// Scan for Java installations, reusing cached probe results
+    pub fn registry(&self) -> JavaRegistry {
+        JavaRegistry::scan(&self.java_dir)
     }
 
     /// Find system-installed Java of a specific version
     fn find_system_java(&self, major_version: u32) -> Option<PathBuf> {
-        // Check common locations
-        let paths_to_check = if cfg!(target_os = "macos") {
-            vec![
-                format!("/opt/homebrew/opt/openjdk@{}/bin/java", major_version),
-                format!("/usr/local/opt/openjdk@{}/bin/java", major_version),
-                format!(
-                    "/Library/Java/JavaVirtualMachines/temurin-{}.jdk/Contents/Home/bin/java",
-                    major_version
-                ),
-                format!(
-                    "/Library/Java/JavaVirtualMachines/adoptopenjdk-{}.jdk/Contents/Home/bin/java",
-                    major_version
-                ),
-            ]
-        } else if cfg!(target_os = "linux") {
-            vec![
-                format!("/usr/lib/jvm/java-{}-openjdk/bin/java", major_version),
-                format!("/usr/lib/jvm/temurin-{}-jdk/bin/java", major_version),
-            ]
-        } else {
-            vec![]
-        };
-
-        for path in paths_to_check {
-            let p = PathBuf::from(&path);
-            if p.exists() {
-                // Verify it's the right version
-                if self.check_java_version(&p, major_version) {
-                    return Some(p);
-                }
-            }
-        }
-
-        None
-    }
-
-    /// Check if a java executable is the required version
-    fn check_java_version(&self, java_path: &Path, required_major: u32) -> bool {
-        let output = Command::new(java_path).arg("-version").output();
-
-        if let Ok(output) = output {
-            let version_str = String::from_utf8_lossy(&output.stderr);
-            // Parse version like "openjdk version \"17.0.1\" 2021-10-19"
-            // or "openjdk version \"1.8.0_312\""
-            for line in version_str.lines() {
-                if line.contains("version") {
-                    if let Some(start) = line.find('"') {
-                        if let Some(end) = line[start + 1..].find('"') {
-                            let version = &line[start + 1..start + 1 + end];
-                            // Handle 1.8.x format
-                            if version.starts_with("1.8") && required_major == 8 {
-                                return true;
-                            }
-                            // Handle modern format like 17.0.x
-                            if let Some(major) = version.split('.').next() {
-                                if let Ok(v) = major.parse::<u32>() {
-                                    return v == required_major;
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        false
-    }
-
-    /// Get the download URL for Adoptium JRE
-    fn get_download_url(&self, major_version: u32) -> String {
-        let os = if cfg!(target_os = "macos") {
-            "mac"
-        } else if cfg!(target_os = "linux") {
-            "linux"
-        } else {
-            "windows"
-        };
-
-        let arch = if cfg!(target_arch = "aarch64") {
-            "aarch64"
-        } else {
-            "x64"
-        };
-
-        format!(
-            "{}/binary/latest/{}/ga/{}/{}/jre/hotspot/normal/adoptium",
-            ADOPTIUM_API, major_version, os, arch
-        )
+        self.registry()
+            .find_system(major_version)
+            .map(|installation| installation.path.clone())
     }
 
-    /// Download and install Java from Adoptium
-    pub async fn download(
+    /// Download a runtime, falling back to the other provider on failure
+    async fn install(
         &self,
-        major_version: u32,
-        progress_callback: impl Fn(&str),
+        request: &RuntimeRequest,
+        progress: &(dyn Fn(&str) + Sync),
     ) -> Result<PathBuf> {
-        let url = self.get_download_url(major_version);
-        let java_home = self.get_java_home(major_version);
-
-        progress_callback(&format!("Downloading Java {}...", major_version));
-
-        // Create temp directory for download
-        let temp_dir = self.java_dir.join("temp");
-        std::fs::create_dir_all(&temp_dir)?;
-
-        let archive_ext = if cfg!(target_os = "windows") {
-            "zip"
-        } else {
-            "tar.gz"
+        let result = match self.provider {
+            JavaProviderKind::Mojang => install_with(&self.mojang, request, progress).await,
+            JavaProviderKind::Adoptium => install_with(&self.adoptium, request, progress).await,
         };
-        let archive_path = temp_dir.join(format!("jre-{}.{}", major_version, archive_ext));
-
-        // Download the archive
-        let client = reqwest::Client::new();
-        let response = client
-            .get(&url)
-            .send()
-            .await
-            .context("Failed to download Java")?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to download Java: HTTP {}", response.status());
-        }
-
-        let bytes = response.bytes().await?;
-        std::fs::write(&archive_path, &bytes)?;
 
-        progress_callback(&format!("Extracting Java {}...", major_version));
-
-        // Extract the archive
-        std::fs::create_dir_all(&java_home)?;
-
-        #[cfg(not(target_os = "windows"))]
-        {
-            // Use tar command for extraction on Unix
-            let output = Command::new("tar")
-                .args([
-                    "-xzf",
-                    archive_path.to_str().unwrap(),
-                    "-C",
-                    java_home.to_str().unwrap(),
-                    "--strip-components=1",
-                ])
-                .output()
-                .context("Failed to extract Java archive")?;
-
-            if !output.status.success() {
-                anyhow::bail!(
-                    "Failed to extract Java: {}",
-                    String::from_utf8_lossy(&output.stderr)
-                );
-            }
-        }
-
-        #[cfg(target_os = "windows")]
-        {
-            // Use zip extraction on Windows
-            let file = std::fs::File::open(&archive_path)?;
-            let mut archive = zip::ZipArchive::new(file)?;
-
-            for i in 0..archive.len() {
-                let mut entry = archive.by_index(i)?;
-                let name = entry.name().to_string();
-
-                // Skip the top-level directory
-                let parts: Vec<&str> = name.splitn(2, '/').collect();
-                if parts.len() < 2 || parts[1].is_empty() {
-                    continue;
-                }
-
-                let path = java_home.join(parts[1]);
-                if entry.is_dir() {
-                    std::fs::create_dir_all(&path)?;
-                } else {
-                    if let Some(parent) = path.parent() {
-                        std::fs::create_dir_all(parent)?;
+        match result {
+            Ok(java_exe) => Ok(java_exe),
+            Err(e) => {
+                tracing::warn!("Java download failed ({}), trying fallback provider", e);
+                match self.provider {
+                    JavaProviderKind::Mojang => {
+                        install_with(&self.adoptium, request, progress).await
+                    }
+                    JavaProviderKind::Adoptium => {
+                        install_with(&self.mojang, request, progress).await
                     }
-                    let mut outfile = std::fs::File::create(&path)?;
-                    std::io::copy(&mut entry, &mut outfile)?;
                 }
             }
         }
+    }
 
-        // Clean up
-        let _ = std::fs::remove_file(&archive_path);
-        let _ = std::fs::remove_dir(&temp_dir);
-
-        // Make java executable on Unix
-        #[cfg(not(target_os = "windows"))]
-        {
-            let java_exe = self.get_java_executable(major_version);
-            let _ = Command::new("chmod")
-                .args(["+x", java_exe.to_str().unwrap()])
-                .output();
+    /// Ensure a runtime is available, downloading if necessary
+    pub async fn ensure_runtime(
+        &self,
+        request: &RuntimeRequest,
+        progress_callback: impl Fn(&str) + Sync,
+    ) -> Result<PathBuf> {
+        // First check our managed Java installations
+        if let Some(java_exe) = self.installed_executable(request) {
+            tracing::info!(
+                "Using managed Java {} at {:?}",
+                request.major_version,
+                java_exe
+            );
+            return Ok(java_exe);
         }
 
-        progress_callback(&format!("Java {} installed!", major_version));
+        // Check system-installed Java
+        if let Some(system_java) = self.find_system_java(request.major_version) {
+            tracing::info!(
+                "Using system Java {} at {:?}",
+                request.major_version,
+                system_java
+            );
+            return Ok(system_java);
+        }
 
-        Ok(java_home)
+        // Need to download
+        self.install(request, &progress_callback).await
     }
 
-    /// Ensure Java is available, downloading if necessary
-    pub async fn ensure_java(
+    /// Install a managed runtime for a major version, even if a system Java exists
+    pub async fn install_managed(
         &self,
         major_version: u32,
-        progress_callback: impl Fn(&str),
+        progress_callback: impl Fn(&str) + Sync,
     ) -> Result<PathBuf> {
-        // First check our managed Java installations
-        if self.is_installed(major_version) {
-            let java_exe = self.get_java_executable(major_version);
-            tracing::info!("Using managed Java {} at {:?}", major_version, java_exe);
+        let request = RuntimeRequest::from_major(major_version);
+        if let Some(java_exe) = self.installed_executable(&request) {
             return Ok(java_exe);
         }
+        self.install(&request, &progress_callback).await
+    }
 
-        // Check system-installed Java
-        if let Some(system_java) = self.find_system_java(major_version) {
-            tracing::info!("Using system Java {} at {:?}", major_version, system_java);
-            return Ok(system_java);
+    /// Remove managed runtimes of a major version, returning removed directories
+    pub fn remove_managed(&self, major_version: u32) -> Result<Vec<PathBuf>> {
+        let mut removed = Vec::new();
+
+        for installation in self.registry().installations() {
+            if !installation.managed || installation.major_version != major_version {
+                continue;
+            }
+            let Some(home) = self.managed_home(&installation.path) else {
+                continue;
+            };
+            if removed.contains(&home) {
+                continue;
+            }
+            std::fs::remove_dir_all(&home)
+                .with_context(|| format!("Failed to remove {}", home.display()))?;
+            removed.push(home);
         }
 
-        // Need to download
-        tracing::info!(
-            "Java {} not found, downloading from Adoptium...",
-            major_version
-        );
-        self.download(major_version, progress_callback).await?;
+        // Drop removed runtimes from the cache
+        self.registry();
+        Ok(removed)
+    }
+
+    /// Top-level runtime directory of a managed java executable
+    fn managed_home(&self, java_exe: &Path) -> Option<PathBuf> {
+        let java_dir = self.java_dir.canonicalize().ok()?;
+        let relative = java_exe.strip_prefix(&java_dir).ok()?;
+        let mut components = relative.components();
+        let first = components.next()?.as_os_str();
 
-        let java_exe = self.get_java_executable(major_version);
-        if java_exe.exists() {
-            Ok(java_exe)
+        // Mojang runtimes live one level deeper, per component
+        if first == "mojang" {
+            Some(java_dir.join(first).join(components.next()?.as_os_str()))
         } else {
-            anyhow::bail!("Java installation failed: executable not found")
+            Some(java_dir.join(first))
         }
     }
 }
 
+/// Install a runtime with a specific provider
+async fn install_with<P: JavaProvider>(
+    provider: &P,
+    request: &RuntimeRequest,
+    progress: &(dyn Fn(&str) + Sync),
+) -> Result<PathBuf> {
+    tracing::info!(
+        "Java {} not found, downloading from {}...",
+        request.major_version,
+        provider.name()
+    );
+    provider.install(request, progress).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -351,13 +394,34 @@ mod tests {
         assert_eq!(JavaManager::get_required_version("1.20.4"), 17);
         assert_eq!(JavaManager::get_required_version("1.19.2"), 17);
         assert_eq!(JavaManager::get_required_version("1.16.5"), 8);
+        assert_eq!(JavaManager::get_required_version("1.20.5"), 21);
+        assert_eq!(JavaManager::get_required_version("1.20.5-pre1"), 21);
+        assert_eq!(JavaManager::get_required_version("1.18 Pre-release 1"), 17);
+        assert_eq!(JavaManager::get_required_version("1.8.9"), 8);
     }
 
     #[test]
-    fn test_download_url() {
-        let manager = JavaManager::new(Path::new("/tmp"));
-        let url = manager.get_download_url(17);
-        assert!(url.contains("adoptium"));
-        assert!(url.contains("/17/"));
+    fn test_get_required_version_snapshots_and_future() {
+        assert_eq!(JavaManager::get_required_version("24w14a"), 21);
+        assert_eq!(JavaManager::get_required_version("24w13a"), 17);
+        assert_eq!(JavaManager::get_required_version("21w19a"), 17);
+        assert_eq!(JavaManager::get_required_version("20w14infinite"), 8);
+        assert_eq!(JavaManager::get_required_version("1.22"), 21);
+        assert_eq!(JavaManager::get_required_version("25.1"), 21);
+        assert_eq!(JavaManager::get_required_version("b1.7.3"), 8);
+        assert_eq!(JavaManager::get_required_version("rd-132211"), 8);
+    }
+
+    #[test]
+    fn test_runtime_request_component() {
+        assert_eq!(RuntimeRequest::from_major(8).component, "jre-legacy");
+        assert_eq!(
+            RuntimeRequest::from_major(17).component,
+            "java-runtime-gamma"
+        );
+        assert_eq!(
+            RuntimeRequest::from_major(21).component,
+            "java-runtime-delta"
+        );
     }
 }
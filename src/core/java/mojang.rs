@@ -0,0 +1,354 @@
+//! Mojang runtime provider
+//!
+//! Downloads the Java runtimes the official launcher uses, file by file,
+//! from Mojang's `java-runtime` manifest.
+
+use super::{JavaProvider, RuntimeRequest};
+use crate::core::version::{Arch, Os, Platform};
+use crate::util::hash::sha1_bytes;
+use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Index of all runtimes by platform and component
+const JAVA_RUNTIME_MANIFEST_URL: &str = "https://launchermeta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
+
+/// Marker written after a runtime is completely installed
+const VERSION_MARKER: &str = ".version";
+
+/// Runtime index: platform -> component -> available builds
+pub type RuntimeIndex = HashMap<String, HashMap<String, Vec<RuntimeEntry>>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeEntry {
+    pub manifest: RuntimeDownload,
+    pub version: RuntimeVersion,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeDownload {
+    pub sha1: String,
+    pub size: u64,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeVersion {
+    pub name: String,
+    pub released: String,
+}
+
+/// File list of a single runtime build
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeManifest {
+    pub files: HashMap<String, RuntimeFile>,
+}
+
+/// Entry in a runtime file list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum RuntimeFile {
+    Directory,
+    File {
+        #[serde(default)]
+        executable: bool,
+        downloads: RuntimeFileDownloads,
+    },
+    Link {
+        target: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuntimeFileDownloads {
+    pub raw: RuntimeDownload,
+}
+
+/// Mojang runtimes, installed as `java/mojang/<component>`
+pub struct MojangProvider {
+    runtimes_dir: PathBuf,
+}
+
+impl MojangProvider {
+    pub fn new(java_dir: &Path) -> Self {
+        Self {
+            runtimes_dir: java_dir.join("mojang"),
+        }
+    }
+
+    /// Platform key used in the runtime index
+    pub fn platform_key(platform: &Platform) -> Option<&'static str> {
+        match (platform.os, platform.arch) {
+            (Os::Linux, Arch::X86_64) => Some("linux"),
+            (Os::Linux, Arch::X86) => Some("linux-i386"),
+            (Os::Osx, Arch::X86_64) => Some("mac-os"),
+            (Os::Osx, Arch::Arm64) => Some("mac-os-arm64"),
+            (Os::Windows, Arch::X86_64) => Some("windows-x64"),
+            (Os::Windows, Arch::X86) => Some("windows-x86"),
+            (Os::Windows, Arch::Arm64) => Some("windows-arm64"),
+            _ => None,
+        }
+    }
+
+    /// Get the install directory of a component
+    fn get_runtime_dir(&self, component: &str) -> PathBuf {
+        self.runtimes_dir.join(component)
+    }
+
+    /// Locate the java executable inside a runtime directory
+    fn find_executable(runtime_dir: &Path) -> Option<PathBuf> {
+        let exe = if cfg!(target_os = "windows") {
+            "java.exe"
+        } else {
+            "java"
+        };
+        [
+            runtime_dir.join("bin").join(exe),
+            // macOS runtimes are shipped as a bundle
+            runtime_dir
+                .join("jre.bundle")
+                .join("Contents")
+                .join("Home")
+                .join("bin")
+                .join(exe),
+        ]
+        .into_iter()
+        .find(|p| p.exists())
+    }
+
+    /// Fetch the runtime index
+    pub async fn fetch_index() -> Result<RuntimeIndex> {
+        let response = reqwest::get(JAVA_RUNTIME_MANIFEST_URL)
+            .await
+            .context("Failed to fetch Java runtime index")?;
+        let index: RuntimeIndex = response.json().await?;
+        Ok(index)
+    }
+
+    /// Find the build of a component for a platform
+    pub fn find_runtime<'a>(
+        index: &'a RuntimeIndex,
+        platform: &Platform,
+        component: &str,
+    ) -> Option<&'a RuntimeEntry> {
+        let key = Self::platform_key(platform)?;
+        index.get(key)?.get(component)?.first()
+    }
+
+    /// Download and verify the file list of a runtime build
+    async fn fetch_manifest(entry: &RuntimeEntry) -> Result<RuntimeManifest> {
+        let response = reqwest::get(&entry.manifest.url)
+            .await
+            .context("Failed to fetch Java runtime manifest")?;
+        let bytes = response.bytes().await?;
+
+        if sha1_bytes(&bytes) != entry.manifest.sha1 {
+            anyhow::bail!("SHA1 mismatch for Java runtime manifest");
+        }
+
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Install every file of a runtime manifest into a directory
+    async fn install_files(
+        &self,
+        manifest: &RuntimeManifest,
+        runtime_dir: &Path,
+        progress: &(dyn Fn(&str) + Sync),
+    ) -> Result<()> {
+        // Directories first, so files and links have a parent
+        for (path, file) in &manifest.files {
+            if matches!(file, RuntimeFile::Directory) {
+                std::fs::create_dir_all(runtime_dir.join(path))?;
+            }
+        }
+
+        // Files that are missing or don't match their checksum
+        let downloads: Vec<(PathBuf, RuntimeDownload, bool)> = manifest
+            .files
+            .iter()
+            .filter_map(|(path, file)| match file {
+                RuntimeFile::File {
+                    executable,
+                    downloads,
+                } => Some((runtime_dir.join(path), downloads.raw.clone(), *executable)),
+                _ => None,
+            })
+            .filter(|(dest, raw, _)| {
+                std::fs::read(dest)
+                    .map(|bytes| sha1_bytes(&bytes) != raw.sha1)
+                    .unwrap_or(true)
+            })
+            .collect();
+
+        let total = downloads.len();
+        progress(&format!("Downloading {} Java runtime files...", total));
+
+        // Number of concurrent downloads
+        const CONCURRENT_DOWNLOADS: usize = 8;
+
+        let results: Vec<Result<()>> = stream::iter(downloads)
+            .map(|(dest, raw, executable)| async move {
+                let response = reqwest::get(&raw.url)
+                    .await
+                    .context("Failed to download Java runtime file")?;
+                let bytes = response.bytes().await?;
+
+                if sha1_bytes(&bytes) != raw.sha1 {
+                    anyhow::bail!("SHA1 mismatch for Java runtime file: {:?}", dest);
+                }
+
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::write(&dest, &bytes)?;
+
+                #[cfg(unix)]
+                if executable {
+                    use std::os::unix::fs::PermissionsExt;
+                    std::fs::set_permissions(&dest, std::fs::Permissions::from_mode(0o755))?;
+                }
+                #[cfg(not(unix))]
+                let _ = executable;
+
+                Ok(())
+            })
+            .buffer_unordered(CONCURRENT_DOWNLOADS)
+            .collect()
+            .await;
+
+        for result in results {
+            result?;
+        }
+
+        // Links last, once their targets exist
+        for (path, file) in &manifest.files {
+            if let RuntimeFile::Link { target } = file {
+                let link = runtime_dir.join(path);
+                if std::fs::symlink_metadata(&link).is_ok() {
+                    std::fs::remove_file(&link)?;
+                }
+                if let Some(parent) = link.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+
+                #[cfg(unix)]
+                std::os::unix::fs::symlink(target, &link)?;
+
+                // Windows runtimes don't use links; copy the target if one appears
+                #[cfg(not(unix))]
+                if let Some(parent) = link.parent() {
+                    let _ = std::fs::copy(parent.join(target), &link);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl JavaProvider for MojangProvider {
+    fn name(&self) -> &'static str {
+        "Mojang"
+    }
+
+    fn installed_executable(&self, request: &RuntimeRequest) -> Option<PathBuf> {
+        let runtime_dir = self.get_runtime_dir(&request.component);
+        if !runtime_dir.join(VERSION_MARKER).exists() {
+            return None;
+        }
+        Self::find_executable(&runtime_dir)
+    }
+
+    async fn install(
+        &self,
+        request: &RuntimeRequest,
+        progress: &(dyn Fn(&str) + Sync),
+    ) -> Result<PathBuf> {
+        let platform = Platform::current();
+
+        progress(&format!("Fetching Java runtime {}...", request.component));
+        let index = Self::fetch_index().await?;
+        let entry = Self::find_runtime(&index, platform, &request.component).context(format!(
+            "Mojang provides no {} runtime for this platform",
+            request.component
+        ))?;
+
+        let manifest = Self::fetch_manifest(entry).await?;
+        let runtime_dir = self.get_runtime_dir(&request.component);
+
+        // Invalidate the marker until every file is in place
+        let marker = runtime_dir.join(VERSION_MARKER);
+        let _ = std::fs::remove_file(&marker);
+        std::fs::create_dir_all(&runtime_dir)?;
+
+        self.install_files(&manifest, &runtime_dir, progress)
+            .await?;
+        std::fs::write(&marker, &entry.version.name)?;
+
+        progress(&format!(
+            "Java {} ({}) installed!",
+            entry.version.name, request.component
+        ));
+
+        Self::find_executable(&runtime_dir)
+            .context("Java installation failed: executable not found")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_runtime_manifest() {
+        let json = r#"{"files":{
+            "bin":{"type":"directory"},
+            "bin/java":{"type":"file","executable":true,"downloads":{"raw":{"sha1":"a","size":1,"url":"https://x/java"}}},
+            "lib/libjli.dylib":{"type":"link","target":"../jli/libjli.dylib"}
+        }}"#;
+        let manifest: RuntimeManifest = serde_json::from_str(json).unwrap();
+
+        assert!(matches!(manifest.files["bin"], RuntimeFile::Directory));
+        assert!(matches!(
+            manifest.files["bin/java"],
+            RuntimeFile::File {
+                executable: true,
+                ..
+            }
+        ));
+        assert!(
+            matches!(&manifest.files["lib/libjli.dylib"], RuntimeFile::Link { target } if target == "../jli/libjli.dylib")
+        );
+    }
+
+    #[test]
+    fn test_find_runtime_by_platform() {
+        let json = r#"{"linux":{"java-runtime-delta":[{"manifest":{"sha1":"a","size":1,"url":"u"},"version":{"name":"21.0.3","released":"2024-04-23"}}]},
+                       "mac-os-arm64":{"java-runtime-delta":[]}}"#;
+        let index: RuntimeIndex = serde_json::from_str(json).unwrap();
+        let linux = Platform {
+            os: Os::Linux,
+            os_version: String::new(),
+            arch: Arch::X86_64,
+        };
+        let mac = Platform {
+            os: Os::Osx,
+            os_version: String::new(),
+            arch: Arch::Arm64,
+        };
+        let linux_arm = Platform {
+            os: Os::Linux,
+            os_version: String::new(),
+            arch: Arch::Arm64,
+        };
+
+        let entry = MojangProvider::find_runtime(&index, &linux, "java-runtime-delta").unwrap();
+        assert_eq!(entry.version.name, "21.0.3");
+        assert!(MojangProvider::find_runtime(&index, &linux, "jre-legacy").is_none());
+        assert!(MojangProvider::find_runtime(&index, &mac, "java-runtime-delta").is_none());
+        assert!(MojangProvider::find_runtime(&index, &linux_arm, "java-runtime-delta").is_none());
+    }
+}
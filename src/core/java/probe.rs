@@ -0,0 +1,370 @@
+//! Java executable probe
+//!
+//! Reads version, vendor and architecture of a java executable, from the
+//! `release` file of its Java home when there is one, otherwise from the
+//! JVM's own system properties.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// What a java executable reports about itself
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JavaProbe {
+    /// Full version string (e.g. `17.0.8` or `1.8.0_392`)
+    pub version: String,
+    /// Major version (e.g. 17 or 8)
+    pub major_version: u32,
+    /// Runtime vendor/distribution name
+    pub vendor: String,
+    /// Normalized JVM architecture (`x86_64`, `x86`, `aarch64` or `arm`)
+    pub arch: String,
+}
+
+impl JavaProbe {
+    /// Probe a java executable
+    ///
+    /// Tries the `release` file first since it needs no process, then a
+    /// system properties dump, then plain `java -version`.
+    pub fn run(java_path: &Path) -> Option<Self> {
+        if let Some(probe) = java_home(java_path)
+            .and_then(|home| std::fs::read_to_string(home.join("release")).ok())
+            .and_then(|content| Self::parse_release(&content))
+        {
+            return Some(probe);
+        }
+
+        let output = Command::new(java_path)
+            .args(["-XshowSettings:properties", "-version"])
+            .output()
+            .ok()?;
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Self::parse_properties(&stderr).or_else(|| Self::parse_version_output(&stderr))
+    }
+
+    /// Whether this JVM runs natively on the current machine
+    pub fn matches_host_arch(&self) -> bool {
+        self.arch == host_arch()
+    }
+
+    /// Parse a Java home `release` file
+    pub fn parse_release(content: &str) -> Option<Self> {
+        let values: HashMap<&str, &str> = content
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim().trim_matches('"')))
+            .collect();
+
+        let version = values.get("JAVA_VERSION")?.to_string();
+        let major_version = major_from_version(&version)?;
+        let arch = normalize_arch(values.get("OS_ARCH")?);
+        let vendor = values
+            .get("IMPLEMENTOR")
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+
+        Some(Self {
+            version,
+            major_version,
+            vendor,
+            arch,
+        })
+    }
+
+    /// Parse `java -XshowSettings:properties -version` output
+    pub fn parse_properties(output: &str) -> Option<Self> {
+        let values: HashMap<&str, &str> = output
+            .lines()
+            .filter_map(|line| line.split_once(" = "))
+            .map(|(key, value)| (key.trim(), value.trim()))
+            .collect();
+
+        let version = values.get("java.version")?.to_string();
+        let major_version = major_from_version(&version)?;
+        let arch = normalize_arch(values.get("os.arch")?);
+        let vendor = values
+            .get("java.vendor")
+            .map(|v| v.to_string())
+            .unwrap_or_default();
+
+        Some(Self {
+            version,
+            major_version,
+            vendor,
+            arch,
+        })
+    }
+
+    /// Parse plain `java -version` output
+    ///
+    /// Least precise: the vendor is the runtime name and the architecture
+    /// is guessed from the "64-Bit" marker.
+    pub fn parse_version_output(output: &str) -> Option<Self> {
+        let mut lines = output
+            .lines()
+            .skip_while(|line| !line.contains(" version \""));
+        let version_line = lines.next()?;
+        let version = quoted(version_line)?;
+        let major_version = major_from_version(&version)?;
+
+        let vendor = lines
+            .next()
+            .and_then(|line| line.split(" (build").next())
+            .map(|name| name.trim().to_string())
+            .unwrap_or_default();
+
+        let arch = if output.contains("64-Bit") {
+            // Assume a 64-bit JVM matches the machine it runs on
+            match host_arch().as_str() {
+                "x86" => "x86_64".to_string(),
+                "arm" => "aarch64".to_string(),
+                host => host.to_string(),
+            }
+        } else {
+            match host_arch().as_str() {
+                "x86_64" => "x86".to_string(),
+                "aarch64" => "arm".to_string(),
+                host => host.to_string(),
+            }
+        };
+
+        Some(Self {
+            version,
+            major_version,
+            vendor,
+            arch,
+        })
+    }
+}
+
+/// Java home of an executable (`<home>/bin/java`)
+fn java_home(java_path: &Path) -> Option<PathBuf> {
+    let java_path = java_path.canonicalize().ok()?;
+    let home = java_path.parent()?.parent()?;
+    // Java 8 JDKs keep the JRE in a subdirectory; the release file is one up
+    if home.file_name().is_some_and(|name| name == "jre") && !home.join("release").exists() {
+        return home.parent().map(Path::to_path_buf);
+    }
+    Some(home.to_path_buf())
+}
+
+/// First double-quoted string in a line
+fn quoted(line: &str) -> Option<String> {
+    let start = line.find('"')? + 1;
+    let end = start + line[start..].find('"')?;
+    Some(line[start..end].to_string())
+}
+
+/// Major version from a Java version string
+///
+/// Handles both `17.0.1` and legacy `1.8.0_312` formats.
+pub fn major_from_version(version: &str) -> Option<u32> {
+    let mut parts = version.split(|c: char| !c.is_ascii_digit());
+    let first: u32 = parts.next()?.parse().ok()?;
+    if first == 1 {
+        parts.next()?.parse().ok()
+    } else {
+        Some(first)
+    }
+}
+
+/// Normalize the many names JVMs use for an architecture
+pub fn normalize_arch(arch: &str) -> String {
+    match arch.to_lowercase().as_str() {
+        "amd64" | "x86_64" | "x64" | "x86-64" => "x86_64",
+        "x86" | "i386" | "i486" | "i586" | "i686" => "x86",
+        "aarch64" | "arm64" => "aarch64",
+        "arm" | "aarch32" | "armv7" | "armv7l" => "arm",
+        other => return other.to_string(),
+    }
+    .to_string()
+}
+
+/// Architecture of this launcher process
+pub fn host_arch() -> String {
+    normalize_arch(std::env::consts::ARCH)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Real `java -version` outputs: (output, version, major, vendor)
+    const VERSION_OUTPUTS: &[(&str, &str, u32, &str)] = &[
+        (
+            "openjdk version \"21.0.2\" 2024-01-16 LTS\n\
+             OpenJDK Runtime Environment Temurin-21.0.2+13 (build 21.0.2+13-LTS)\n\
+             OpenJDK 64-Bit Server VM Temurin-21.0.2+13 (build 21.0.2+13-LTS, mixed mode, sharing)",
+            "21.0.2",
+            21,
+            "OpenJDK Runtime Environment Temurin-21.0.2+13",
+        ),
+        (
+            "openjdk version \"1.8.0_392\"\n\
+             OpenJDK Runtime Environment (build 1.8.0_392-8u392-ga-1~22.04-b08)\n\
+             OpenJDK 64-Bit Server VM (build 25.392-b08, mixed mode)",
+            "1.8.0_392",
+            8,
+            "OpenJDK Runtime Environment",
+        ),
+        (
+            "java version \"1.8.0_401\"\n\
+             Java(TM) SE Runtime Environment (build 1.8.0_401-b10)\n\
+             Java HotSpot(TM) Client VM (build 25.401-b10, mixed mode)",
+            "1.8.0_401",
+            8,
+            "Java(TM) SE Runtime Environment",
+        ),
+        (
+            "openjdk version \"17.0.10\" 2024-01-16 LTS\n\
+             OpenJDK Runtime Environment Zulu17.48+15-CA (build 17.0.10+7-LTS)\n\
+             OpenJDK 64-Bit Server VM Zulu17.48+15-CA (build 17.0.10+7-LTS, mixed mode, sharing)",
+            "17.0.10",
+            17,
+            "OpenJDK Runtime Environment Zulu17.48+15-CA",
+        ),
+        (
+            "Picked up _JAVA_OPTIONS: -Dawt.useSystemAAFontSettings=on\n\
+             openjdk version \"17\" 2021-09-14\n\
+             OpenJDK Runtime Environment (build 17+35-2724)\n\
+             OpenJDK 64-Bit Server VM (build 17+35-2724, mixed mode, sharing)",
+            "17",
+            17,
+            "OpenJDK Runtime Environment",
+        ),
+        (
+            "openjdk version \"22-ea\" 2024-03-19\n\
+             OpenJDK Runtime Environment (build 22-ea+27-2262)\n\
+             OpenJDK 64-Bit Server VM (build 22-ea+27-2262, mixed mode, sharing)",
+            "22-ea",
+            22,
+            "OpenJDK Runtime Environment",
+        ),
+        (
+            "openjdk version \"16.0.1\" 2021-04-20\n\
+             OpenJDK Runtime Environment Microsoft-22268 (build 16.0.1+9)\n\
+             OpenJDK 64-Bit Server VM Microsoft-22268 (build 16.0.1+9, mixed mode)",
+            "16.0.1",
+            16,
+            "OpenJDK Runtime Environment Microsoft-22268",
+        ),
+        (
+            "openjdk version \"11.0.22\" 2024-01-16\n\
+             IBM Semeru Runtime Open Edition 11.0.22.0 (build 11.0.22+7)\n\
+             Eclipse OpenJ9 VM 11.0.22.0 (build openj9-0.43.0, JRE 11 Linux amd64-64-Bit)",
+            "11.0.22",
+            11,
+            "IBM Semeru Runtime Open Edition 11.0.22.0",
+        ),
+    ];
+
+    #[test]
+    fn test_parse_version_outputs() {
+        for (output, version, major, vendor) in VERSION_OUTPUTS {
+            let probe = JavaProbe::parse_version_output(output)
+                .unwrap_or_else(|| panic!("failed to parse: {}", output));
+            assert_eq!(probe.version, *version);
+            assert_eq!(probe.major_version, *major, "{}", output);
+            assert_eq!(probe.vendor, *vendor);
+        }
+    }
+
+    #[test]
+    fn test_parse_version_output_bitness() {
+        let client_vm = VERSION_OUTPUTS[2].0;
+        let probe = JavaProbe::parse_version_output(client_vm).unwrap();
+        assert_ne!(probe.arch, "x86_64");
+        assert_ne!(probe.arch, "aarch64");
+
+        let server_vm = VERSION_OUTPUTS[0].0;
+        let probe = JavaProbe::parse_version_output(server_vm).unwrap();
+        assert!(probe.arch == "x86_64" || probe.arch == "aarch64");
+    }
+
+    #[test]
+    fn test_parse_version_output_invalid() {
+        assert_eq!(JavaProbe::parse_version_output(""), None);
+        assert_eq!(
+            JavaProbe::parse_version_output("bash: java: command not found"),
+            None
+        );
+        assert_eq!(
+            JavaProbe::parse_version_output(
+                "Error: could not open `/usr/lib/jvm/broken/lib/jvm.cfg'"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_properties() {
+        let output = "Property settings:\n    \
+             file.encoding = UTF-8\n    \
+             java.home = /usr/lib/jvm/java-17-openjdk-amd64\n    \
+             java.runtime.name = OpenJDK Runtime Environment\n    \
+             java.vendor = Eclipse Adoptium\n    \
+             java.version = 17.0.10\n    \
+             os.arch = amd64\n    \
+             os.name = Linux\n\n\
+             openjdk version \"17.0.10\" 2024-01-16\n";
+        let probe = JavaProbe::parse_properties(output).unwrap();
+        assert_eq!(probe.version, "17.0.10");
+        assert_eq!(probe.major_version, 17);
+        assert_eq!(probe.vendor, "Eclipse Adoptium");
+        assert_eq!(probe.arch, "x86_64");
+
+        let legacy = "Property settings:\n    \
+             java.vendor = Oracle Corporation\n    \
+             java.version = 1.8.0_401\n    \
+             os.arch = x86\n";
+        let probe = JavaProbe::parse_properties(legacy).unwrap();
+        assert_eq!(probe.major_version, 8);
+        assert_eq!(probe.arch, "x86");
+
+        let mac = "Property settings:\n    \
+             java.vendor = Azul Systems, Inc.\n    \
+             java.version = 21.0.2\n    \
+             os.arch = aarch64\n";
+        let probe = JavaProbe::parse_properties(mac).unwrap();
+        assert_eq!(probe.vendor, "Azul Systems, Inc.");
+        assert_eq!(probe.arch, "aarch64");
+    }
+
+    #[test]
+    fn test_parse_release() {
+        let release = "IMPLEMENTOR=\"Eclipse Adoptium\"\n\
+             IMPLEMENTOR_VERSION=\"Temurin-21.0.2+13\"\n\
+             JAVA_VERSION=\"21.0.2\"\n\
+             JAVA_VERSION_DATE=\"2024-01-16\"\n\
+             OS_ARCH=\"aarch64\"\n\
+             OS_NAME=\"Darwin\"\n";
+        let probe = JavaProbe::parse_release(release).unwrap();
+        assert_eq!(probe.version, "21.0.2");
+        assert_eq!(probe.major_version, 21);
+        assert_eq!(probe.vendor, "Eclipse Adoptium");
+        assert_eq!(probe.arch, "aarch64");
+
+        let legacy = "JAVA_VERSION=\"1.8.0_392\"\n\
+             OS_NAME=\"Windows\"\n\
+             OS_ARCH=\"i586\"\n\
+             JAVA_RUNTIME_VERSION=\"1.8.0_392-b08\"\n";
+        let probe = JavaProbe::parse_release(legacy).unwrap();
+        assert_eq!(probe.major_version, 8);
+        assert_eq!(probe.vendor, "");
+        assert_eq!(probe.arch, "x86");
+
+        // Missing architecture: fall back to running the JVM
+        assert_eq!(JavaProbe::parse_release("JAVA_VERSION=\"17.0.1\"\n"), None);
+    }
+
+    #[test]
+    fn test_normalize_arch() {
+        assert_eq!(normalize_arch("amd64"), "x86_64");
+        assert_eq!(normalize_arch("X86_64"), "x86_64");
+        assert_eq!(normalize_arch("i686"), "x86");
+        assert_eq!(normalize_arch("arm64"), "aarch64");
+        assert_eq!(normalize_arch("armv7l"), "arm");
+        assert_eq!(normalize_arch("ppc64le"), "ppc64le");
+    }
+}
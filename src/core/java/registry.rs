@@ -0,0 +1,241 @@
+//! Java installation registry
+//!
+//! Discovers Java installations (JAVA_HOME, PATH, system JVM directories,
+//! SDKMAN, asdf and managed runtimes) and caches what each one is, so
+//! executables are only probed again when they change.
+
+use super::probe::{JavaProbe, host_arch};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Registry cache file name, inside the java directory
+const REGISTRY_FILE: &str = "registry.json";
+
+/// A discovered Java installation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JavaInstallation {
+    /// Canonical path to the java executable
+    pub path: PathBuf,
+    /// Full version string
+    pub version: String,
+    /// Major version
+    pub major_version: u32,
+    /// Runtime vendor/distribution name
+    pub vendor: String,
+    /// JVM architecture
+    pub arch: String,
+    /// Installed by the launcher
+    #[serde(default)]
+    pub managed: bool,
+    /// Executable modification time, to detect changes
+    #[serde(default)]
+    pub modified: u64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct RegistryFile {
+    #[serde(default)]
+    installations: Vec<JavaInstallation>,
+}
+
+/// Cached list of Java installations
+pub struct JavaRegistry {
+    java_dir: PathBuf,
+    installations: Vec<JavaInstallation>,
+}
+
+impl JavaRegistry {
+    /// Load the cached registry without scanning
+    pub fn load(java_dir: &Path) -> Self {
+        let installations = std::fs::read_to_string(java_dir.join(REGISTRY_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str::<RegistryFile>(&content).ok())
+            .map(|file| file.installations)
+            .unwrap_or_default();
+
+        Self {
+            java_dir: java_dir.to_path_buf(),
+            installations,
+        }
+    }
+
+    /// Load the registry and rescan for installations
+    pub fn scan(java_dir: &Path) -> Self {
+        let mut registry = Self::load(java_dir);
+        if let Err(e) = registry.refresh() {
+            tracing::warn!("Failed to save Java registry: {}", e);
+        }
+        registry
+    }
+
+    /// All known installations, newest Java first
+    pub fn installations(&self) -> &[JavaInstallation] {
+        &self.installations
+    }
+
+    /// Rescan the system, probing only new or changed executables
+    pub fn refresh(&mut self) -> Result<()> {
+        let mut found: Vec<JavaInstallation> = Vec::new();
+        let java_dir = self
+            .java_dir
+            .canonicalize()
+            .unwrap_or_else(|_| self.java_dir.clone());
+
+        for candidate in candidate_executables(&self.java_dir) {
+            let Ok(path) = candidate.canonicalize() else {
+                continue;
+            };
+            if found.iter().any(|i| i.path == path) {
+                continue;
+            }
+
+            let modified = modified_secs(&path);
+            let cached = self
+                .installations
+                .iter()
+                .find(|i| i.path == path && i.modified == modified)
+                .cloned();
+
+            let installation = match cached {
+                Some(installation) => installation,
+                None => {
+                    let Some(probe) = JavaProbe::run(&path) else {
+                        tracing::debug!("Ignoring unusable Java at {:?}", path);
+                        continue;
+                    };
+                    JavaInstallation {
+                        managed: path.starts_with(&java_dir),
+                        path,
+                        version: probe.version,
+                        major_version: probe.major_version,
+                        vendor: probe.vendor,
+                        arch: probe.arch,
+                        modified,
+                    }
+                }
+            };
+            found.push(installation);
+        }
+
+        found.sort_by(|a, b| {
+            b.major_version
+                .cmp(&a.major_version)
+                .then(b.managed.cmp(&a.managed))
+                .then(a.path.cmp(&b.path))
+        });
+        self.installations = found;
+        self.save()
+    }
+
+    /// Find a system (not launcher-managed) installation of a major version
+    ///
+    /// Only JVMs matching this machine's architecture are considered, since
+    /// natives are selected for the host.
+    pub fn find_system(&self, major_version: u32) -> Option<&JavaInstallation> {
+        let host_arch = host_arch();
+        self.installations
+            .iter()
+            .find(|i| !i.managed && i.major_version == major_version && i.arch == host_arch)
+    }
+
+    /// Save the registry cache
+    fn save(&self) -> Result<()> {
+        std::fs::create_dir_all(&self.java_dir)?;
+        let file = RegistryFile {
+            installations: self.installations.clone(),
+        };
+        let content = serde_json::to_string_pretty(&file)?;
+        std::fs::write(self.java_dir.join(REGISTRY_FILE), content)
+            .context("Failed to write Java registry")
+    }
+}
+
+/// Find the java executable inside a Java home directory
+pub fn find_executable_in_home(home: &Path) -> Option<PathBuf> {
+    let exe = if cfg!(target_os = "windows") {
+        "java.exe"
+    } else {
+        "java"
+    };
+
+    [
+        home.join("bin"),
+        home.join("Contents").join("Home").join("bin"),
+        home.join("jre.bundle")
+            .join("Contents")
+            .join("Home")
+            .join("bin"),
+    ]
+    .into_iter()
+    .map(|bin| bin.join(exe))
+    .find(|java| java.is_file())
+}
+
+/// Every place a java executable might be
+fn candidate_executables(java_dir: &Path) -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    // JAVA_HOME
+    if let Some(java_home) = std::env::var_os("JAVA_HOME") {
+        candidates.extend(find_executable_in_home(Path::new(&java_home)));
+    }
+
+    // PATH
+    if let Some(path) = std::env::var_os("PATH") {
+        for dir in std::env::split_paths(&path) {
+            candidates.extend(find_executable_in_home(dir.parent().unwrap_or(&dir)));
+        }
+    }
+
+    // Directories that contain one Java home per entry
+    let mut roots: Vec<PathBuf> = Vec::new();
+    if cfg!(target_os = "linux") {
+        roots.push(PathBuf::from("/usr/lib/jvm"));
+        roots.push(PathBuf::from("/usr/lib64/jvm"));
+        roots.push(PathBuf::from("/opt/java"));
+    } else if cfg!(target_os = "macos") {
+        roots.push(PathBuf::from("/Library/Java/JavaVirtualMachines"));
+        roots.push(PathBuf::from("/opt/homebrew/opt"));
+        roots.push(PathBuf::from("/usr/local/opt"));
+    } else if cfg!(target_os = "windows") {
+        roots.push(PathBuf::from(r"C:\Program Files\Java"));
+        roots.push(PathBuf::from(r"C:\Program Files\Eclipse Adoptium"));
+        roots.push(PathBuf::from(r"C:\Program Files\Microsoft"));
+    }
+    if let Some(home) = dirs::home_dir() {
+        // SDKMAN and asdf
+        roots.push(home.join(".sdkman").join("candidates").join("java"));
+        roots.push(home.join(".asdf").join("installs").join("java"));
+    }
+
+    // Managed runtimes (Adoptium per major, Mojang per component)
+    roots.push(java_dir.to_path_buf());
+    roots.push(java_dir.join("mojang"));
+
+    for root in roots {
+        let Ok(entries) = std::fs::read_dir(&root) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            // Skip in-progress installs
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            candidates.extend(find_executable_in_home(&entry.path()));
+        }
+    }
+
+    candidates
+}
+
+/// Modification time of a file in seconds since the epoch
+fn modified_secs(path: &Path) -> u64 {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
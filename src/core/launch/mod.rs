@@ -10,14 +10,14 @@ use crate::core::asset::AssetManager;
 use crate::core::auth::Account;
 use crate::core::instance::{Instance, InstanceManager};
 use crate::core::library::LibraryManager;
-use crate::core::version::VersionDetails;
+use crate::core::version::{FeatureSet, Platform, VersionDetails, evaluate_rules};
+use crate::util::hash::verify_sha1;
 use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
 
 /// Minecraft launcher
 pub struct Launcher {
-    java_path: PathBuf,
     libraries_dir: PathBuf,
     assets_dir: PathBuf,
     versions_dir: PathBuf,
@@ -27,141 +27,13 @@ impl Launcher {
     pub fn new() -> Self {
         let data_dir = config::config_dir();
 
-        // Default to Java 21 (latest LTS)
-        let java_path = Self::find_java_for_version(21).unwrap_or_else(|| PathBuf::from("java"));
-
         Self {
-            java_path,
             libraries_dir: data_dir.join("libraries"),
             assets_dir: data_dir.join("assets"),
             versions_dir: data_dir.join("versions"),
         }
     }
 
-    /// Find Java executable for a specific major version
-    pub fn find_java_for_version(major_version: u32) -> Option<PathBuf> {
-        println!("Looking for Java {} ...", major_version);
-
-        // Check JAVA_HOME first
-        if let Ok(java_home) = std::env::var("JAVA_HOME") {
-            let java = PathBuf::from(&java_home).join("bin").join("java");
-            if java.exists() {
-                // Check version
-                if Self::check_java_version(&java, major_version) {
-                    println!("Found Java {} via JAVA_HOME: {:?}", major_version, java);
-                    return Some(java);
-                }
-            }
-        }
-
-        // Check common locations on macOS
-        #[cfg(target_os = "macos")]
-        {
-            // Homebrew paths - check specific version first
-            let homebrew_paths = [
-                format!("/opt/homebrew/opt/openjdk@{}/bin/java", major_version),
-                format!("/usr/local/opt/openjdk@{}/bin/java", major_version),
-            ];
-
-            for path in homebrew_paths {
-                let java = PathBuf::from(&path);
-                if java.exists() {
-                    println!("Found Java {} at: {:?}", major_version, java);
-                    return Some(java);
-                }
-            }
-
-            // Temurin/Adoptium paths
-            let temurin_paths = [
-                format!(
-                    "/Library/Java/JavaVirtualMachines/temurin-{}.jdk/Contents/Home/bin/java",
-                    major_version
-                ),
-                format!(
-                    "/Library/Java/JavaVirtualMachines/adoptopenjdk-{}.jdk/Contents/Home/bin/java",
-                    major_version
-                ),
-            ];
-
-            for path in temurin_paths {
-                let java = PathBuf::from(&path);
-                if java.exists() {
-                    println!("Found Java {} at: {:?}", major_version, java);
-                    return Some(java);
-                }
-            }
-
-            // Fallback: Check all JDKs and find one with matching version
-            let jvm_dir = Path::new("/Library/Java/JavaVirtualMachines");
-            if jvm_dir.exists() {
-                if let Ok(entries) = std::fs::read_dir(jvm_dir) {
-                    for entry in entries.flatten() {
-                        let java = entry.path().join("Contents/Home/bin/java");
-                        if java.exists() && Self::check_java_version(&java, major_version) {
-                            println!("Found Java {} at: {:?}", major_version, java);
-                            return Some(java);
-                        }
-                    }
-                }
-            }
-        }
-
-        // Fall back to PATH and check version
-        let java = PathBuf::from("java");
-        if Self::check_java_version(&java, major_version) {
-            println!("Using Java {} from PATH", major_version);
-            return Some(java);
-        }
-
-        println!("Java {} not found!", major_version);
-        None
-    }
-
-    /// Check if a Java executable is the required version
-    fn check_java_version(java_path: &Path, required_major: u32) -> bool {
-        let output = std::process::Command::new(java_path)
-            .arg("-version")
-            .output();
-
-        if let Ok(output) = output {
-            let version_str = String::from_utf8_lossy(&output.stderr);
-            // Parse version like "openjdk version \"21.0.1\"" or "17.0.17"
-            // The major version is usually the first number
-            for line in version_str.lines() {
-                if line.contains("version") {
-                    // Extract numbers
-                    let parts: Vec<&str> = line.split(|c: char| !c.is_ascii_digit()).collect();
-                    for part in parts {
-                        if let Ok(ver) = part.parse::<u32>() {
-                            if ver == required_major {
-                                return true;
-                            }
-                            // For Java 9+, first number IS the major version
-                            if ver >= 9 {
-                                return ver == required_major;
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        false
-    }
-
-    /// Get Java path for a specific version details
-    pub fn get_java_for_version(&self, details: &VersionDetails) -> Result<PathBuf> {
-        let required_version = details
-            .java_version
-            .as_ref()
-            .map(|jv| jv.major_version)
-            .unwrap_or(8); // Default to Java 8 for old versions
-
-        Self::find_java_for_version(required_version).context(format!(
-            "Java {} not found. Please install it:\n  brew install openjdk@{}",
-            required_version, required_version
-        ))
-    }
-
     /// Get version JAR path
     pub fn get_version_jar(&self, version_id: &str) -> PathBuf {
         self.versions_dir
@@ -196,6 +68,43 @@ impl Launcher {
         Ok(jar_path)
     }
 
+    /// Get log4j configuration file path
+    pub fn get_logging_config_path(&self, id: &str) -> PathBuf {
+        self.assets_dir.join("log_configs").join(id)
+    }
+
+    /// Download client logging configuration if missing or invalid
+    pub async fn ensure_logging_config(&self, details: &VersionDetails) -> Result<Option<PathBuf>> {
+        let Some(logging) = details.logging.as_ref().and_then(|l| l.client.as_ref()) else {
+            return Ok(None);
+        };
+
+        let config_path = self.get_logging_config_path(&logging.file.id);
+
+        if config_path.exists() && verify_sha1(&config_path, &logging.file.sha1).unwrap_or(false) {
+            return Ok(Some(config_path));
+        }
+
+        tracing::info!("Downloading logging config: {}", logging.file.id);
+
+        if let Some(parent) = config_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let response = reqwest::get(&logging.file.url)
+            .await
+            .context("Failed to download logging config")?;
+        let bytes = response.bytes().await?;
+        std::fs::write(&config_path, &bytes)?;
+
+        if !verify_sha1(&config_path, &logging.file.sha1)? {
+            std::fs::remove_file(&config_path)?;
+            anyhow::bail!("SHA1 mismatch for logging config: {}", logging.file.id);
+        }
+
+        Ok(Some(config_path))
+    }
+
     /// Build JVM arguments
     fn build_jvm_args(
         &self,
@@ -231,6 +140,7 @@ impl Launcher {
         args.push("-XstartOnFirstThread".to_string());
 
         // Add JVM args from version JSON (important for Forge)
+        let features = Self::feature_set(instance);
         if let Some(ref arguments) = details.arguments {
             for arg in &arguments.jvm {
                 match arg {
@@ -244,7 +154,7 @@ impl Launcher {
                     }
                     crate::core::version::ArgumentValue::Conditional(cond) => {
                         // Check if rules allow this argument
-                        if self.check_rules(&cond.rules) {
+                        if evaluate_rules(&cond.rules, Platform::current(), &features) {
                             match &cond.value {
                                 crate::core::version::StringOrVec::Single(s) => {
                                     let processed = self.replace_jvm_placeholders(
@@ -271,12 +181,31 @@ impl Launcher {
             }
         }
 
+        // Apply log4j configuration (patched configs mitigate Log4Shell on 1.7-1.18)
+        if let Some(logging) = details.logging.as_ref().and_then(|l| l.client.as_ref()) {
+            let config_path = self.get_logging_config_path(&logging.file.id);
+            if config_path.exists() {
+                args.push(
+                    logging
+                        .argument
+                        .replace("${path}", &config_path.display().to_string()),
+                );
+            }
+        }
+
         // Add extra JVM args from instance config
         args.extend(instance.java.extra_args.clone());
 
         args
     }
 
+    /// Launcher features used to evaluate argument rules
+    fn feature_set(instance: &Instance) -> FeatureSet {
+        FeatureSet::new()
+            .with(FeatureSet::IS_DEMO_USER, false)
+            .with(FeatureSet::HAS_CUSTOM_RESOLUTION, !instance.game.fullscreen)
+    }
+
     /// Replace placeholders in JVM arguments
     fn replace_jvm_placeholders(
         &self,
@@ -299,45 +228,13 @@ impl Launcher {
             .replace(
                 "${natives_directory}",
                 &InstanceManager::new()
-                    .get_natives_dir(&instance.info.name)
+                    .get_natives_dir(&instance.info.id)
                     .display()
                     .to_string(),
             )
             .replace("${version_type}", &details.version_type)
     }
 
-    /// Check if rules allow an argument
-    fn check_rules(&self, rules: &[crate::core::version::Rule]) -> bool {
-        for rule in rules {
-            let action_allow = rule.action == "allow";
-
-            if let Some(ref os) = rule.os {
-                let os_match = if let Some(ref os_name) = os.name {
-                    #[cfg(target_os = "macos")]
-                    let current_os = "osx";
-                    #[cfg(target_os = "windows")]
-                    let current_os = "windows";
-                    #[cfg(target_os = "linux")]
-                    let current_os = "linux";
-
-                    os_name == current_os
-                } else {
-                    true
-                };
-
-                if action_allow && !os_match {
-                    return false;
-                }
-                if !action_allow && os_match {
-                    return false;
-                }
-            } else if !action_allow {
-                return false;
-            }
-        }
-        true
-    }
-
     /// Build game arguments
     fn build_game_args(
         &self,
@@ -352,6 +249,7 @@ impl Launcher {
         }
 
         // Modern argument format
+        let features = Self::feature_set(instance);
         let mut raw_args = Vec::new();
 
         if let Some(ref arguments) = details.arguments {
@@ -363,9 +261,7 @@ impl Launcher {
                         );
                     }
                     crate::core::version::ArgumentValue::Conditional(c) => {
-                        // Check rules - skip demo mode and other conditional features
-                        let dominated_by_features = c.rules.iter().any(|r| r.features.is_some());
-                        if !dominated_by_features && c.rules.iter().all(|r| r.is_allowed()) {
+                        if evaluate_rules(&c.rules, Platform::current(), &features) {
                             match &c.value {
                                 crate::core::version::StringOrVec::Single(s) => {
                                     raw_args.push(self.replace_placeholders(
@@ -472,9 +368,14 @@ impl Launcher {
             .replace("${version_type}", &details.version_type)
             .replace("${clientid}", "")
             .replace("${auth_xuid}", "")
-            // Resolution - use reasonable defaults
-            .replace("${resolution_width}", "854")
-            .replace("${resolution_height}", "480")
+            .replace(
+                "${resolution_width}",
+                &instance.game.resolution_width.to_string(),
+            )
+            .replace(
+                "${resolution_height}",
+                &instance.game.resolution_height.to_string(),
+            )
             // Quick play options - not used
             .replace("${quickPlayPath}", "")
             .replace("${quickPlaySingleplayer}", "")
@@ -482,29 +383,6 @@ impl Launcher {
             .replace("${quickPlayRealms}", "")
     }
 
-    /// Check if argument should be included (filter out unresolved placeholders and demo)
-    fn should_include_arg(&self, arg: &str, prev_arg: Option<&str>) -> bool {
-        // Skip if still contains unresolved placeholder
-        if arg.contains("${") {
-            return false;
-        }
-        // Skip empty arguments
-        if arg.is_empty() {
-            return false;
-        }
-        // Skip demo mode
-        if arg == "--demo" {
-            return false;
-        }
-        // Skip argument if previous was a flag that requires a value but value is empty/placeholder
-        if let Some(prev) = prev_arg {
-            if prev.starts_with("--") && !prev.contains('=') && arg.is_empty() {
-                return false;
-            }
-        }
-        true
-    }
-
     /// Launch Minecraft
     pub fn launch(
         &self,
@@ -515,8 +393,8 @@ impl Launcher {
         java_path: &Path,
     ) -> Result<Child> {
         let instance_mgr = InstanceManager::new();
-        let game_dir = instance_mgr.get_game_dir(&instance.info.name);
-        let natives_dir = instance_mgr.get_natives_dir(&instance.info.name);
+        let game_dir = instance_mgr.get_game_dir(&instance.info.id);
+        let natives_dir = instance_mgr.get_natives_dir(&instance.info.id);
 
         // Ensure game directory exists
         std::fs::create_dir_all(&game_dir)?;
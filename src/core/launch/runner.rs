@@ -5,24 +5,98 @@
 use crate::config;
 use crate::core::asset::AssetManager;
 use crate::core::auth::Account;
+use crate::core::backup::BackupManager;
 use crate::core::fabric::FabricManager;
 use crate::core::forge::ForgeManager;
-use crate::core::instance::{Instance, InstanceManager, ModLoader};
-use crate::core::java::JavaManager;
+use crate::core::instance::{GameSession, Instance, InstanceManager, ModLoader, record_game_pid};
+use crate::core::java::{JavaManager, RuntimeRequest};
 use crate::core::launch::Launcher;
 use crate::core::library::LibraryManager;
-use crate::core::version::{self, ArgumentValue, Artifact, Library, LibraryDownloads};
+use crate::core::mods;
+use crate::core::version::{self, ArgumentValue, Artifact, Library, LibraryDownloads, Platform};
 use anyhow::{Context, Result};
-use std::process::Child;
 
 /// Result of game launch
 pub enum LaunchResult {
     /// Game started successfully
-    Success(Child),
+    Success(GameSession),
     /// Game exited early with code
     EarlyExit(Option<i32>),
 }
 
+/// Create a Java manager using the configured download provider
+fn java_manager(data_dir: &std::path::Path) -> JavaManager {
+    let config = config::load().unwrap_or_default();
+    JavaManager::new(data_dir).with_provider(config.java.provider)
+}
+
+/// Java runtime required by a version, from its JSON when available
+fn java_request(instance: &Instance, details: &version::VersionDetails) -> RuntimeRequest {
+    match details.java_version {
+        Some(ref java_version) => RuntimeRequest::from(java_version),
+        None => {
+            RuntimeRequest::from_major(JavaManager::get_required_version(&instance.info.version))
+        }
+    }
+}
+
+/// Java explicitly chosen by the user, per instance or globally
+fn forced_java(instance: &Instance) -> Option<std::path::PathBuf> {
+    if instance.java.override_global && !instance.java.path.is_empty() {
+        return Some(instance.java.path.clone().into());
+    }
+    let config = config::load().unwrap_or_default();
+    (!config.java.path.is_empty()).then(|| config.java.path.into())
+}
+
+/// Resolve the Java executable for an instance, downloading if necessary
+async fn resolve_java(
+    instance: &Instance,
+    details: &version::VersionDetails,
+    on_progress: impl Fn(&str) + Sync,
+) -> Result<std::path::PathBuf> {
+    let required_java = java_request(instance, details);
+    println!(
+        "Required Java version: {} ({})",
+        required_java.major_version, required_java.component
+    );
+
+    if let Some(java_path) = forced_java(instance) {
+        if let Some(warning) =
+            JavaManager::forced_java_warning(&java_path, required_java.major_version)
+        {
+            tracing::warn!("{}", warning);
+            on_progress(&format!("Warning: {}", warning));
+        }
+        return Ok(java_path);
+    }
+
+    let data_dir = config::config_dir();
+    java_manager(&data_dir)
+        .ensure_runtime(&required_java, on_progress)
+        .await
+}
+
+/// Back up the instance's worlds when enabled in the config
+///
+/// Failures are reported but never block the launch.
+fn backup_before_launch(instance: &Instance, on_progress: &(impl Fn(&str) + Sync)) {
+    let config = config::load().unwrap_or_default();
+    if !config.backups.before_launch {
+        return;
+    }
+
+    on_progress("Backing up worlds...");
+    let instance_dir = InstanceManager::new().get_instance_dir(&instance.info.id);
+    let result = BackupManager::new(&instance_dir)
+        .keep(config.backups.keep)
+        .backup_all();
+    if let Err(e) = result {
+        tracing::warn!("World backup failed: {}", e);
+        on_progress(&format!("Warning: world backup failed: {}", e));
+    }
+}
+
 /// Prepare and launch an instance
 ///
 /// This is the shared launch logic used by both CLI and GUI.
@@ -37,6 +111,8 @@ where
     println!("=== launch_instance_async START ===");
     println!("Account: {}", account.profile.name);
 
+    backup_before_launch(instance, &on_progress);
+
     on_progress("Fetching version manifest...");
 
     // Fetch version manifest
@@ -90,16 +166,13 @@ where
 
         // Get Java path for processor execution
         let data_dir = config::config_dir();
-        let java_manager = JavaManager::new(&data_dir);
 
         // Forge requires Java - use the version required for this MC version
-        let required_java = JavaManager::get_required_version(&instance.info.version);
-        let java_path = java_manager
-            .ensure_java(required_java, |msg| {
-                println!("Java: {}", msg);
-            })
-            .await
-            .context("Java is required for Forge. Please install Java first.")?;
+        let java_path = resolve_java(instance, &details, |msg| {
+            println!("Java: {}", msg);
+        })
+        .await
+        .context("Java is required for Forge. Please install Java first.")?;
 
         let forge_manager = ForgeManager::new(&data_dir, &java_path);
 
@@ -191,6 +264,9 @@ where
     println!("Data dir: {:?}", data_dir);
     println!("Libraries: {} total", details.libraries.len());
 
+    // Fill in natives for architectures the version JSON doesn't cover
+    LibraryManager::add_missing_arch_natives(&mut details.libraries, Platform::current());
+
     // Download libraries
     println!("Starting library download...");
     on_progress("Downloading libraries...");
@@ -233,11 +309,27 @@ where
     let game_jar = launcher.ensure_version_jar(&details).await?;
     println!("Client JAR: {:?}", game_jar);
 
+    // Download logging configuration
+    on_progress("Downloading logging config...");
+    if let Some(config_path) = launcher.ensure_logging_config(&details).await? {
+        println!("Logging config: {:?}", config_path);
+    }
+
+    // Check mods before starting; problems are reported, not fatal
+    if instance.info.loader != ModLoader::Vanilla {
+        on_progress("Checking mods...");
+        for issue in mods::check_instance(instance) {
+            tracing::warn!("Mod issue: {}", issue);
+            println!("⚠️  {}", issue);
+            on_progress(&format!("Warning: {}", issue));
+        }
+    }
+
     // Extract natives
     println!("Extracting natives...");
     on_progress("Extracting native libraries...");
     let instance_manager = InstanceManager::new();
-    let natives_dir = instance_manager.get_natives_dir(&instance.info.name);
+    let natives_dir = instance_manager.get_natives_dir(&instance.info.id);
     lib_manager.extract_natives(&details.libraries, &natives_dir)?;
     println!("Natives extracted to: {:?}", natives_dir);
 
@@ -248,15 +340,11 @@ where
     // Ensure Java is available (download if necessary)
     println!("Checking Java installation...");
     on_progress("Checking Java installation...");
-    let java_manager = JavaManager::new(&data_dir);
-    let required_java = JavaManager::get_required_version(&instance.info.version);
-    println!("Required Java version: {}", required_java);
-
-    let java_path = java_manager
-        .ensure_java(required_java, |msg| {
-            println!("{}", msg);
-        })
-        .await?;
+    let java_path = resolve_java(instance, &details, |msg| {
+        println!("{}", msg);
+        on_progress(msg);
+    })
+    .await?;
     println!("Java path: {:?}", java_path);
 
     // Launch!
@@ -278,7 +366,15 @@ where
         Ok(None) => {
             // Still running
             tracing::info!("Minecraft process is running");
-            Ok(LaunchResult::Success(child))
+            let instance_dir = instance_manager.get_instance_dir(&instance.info.id);
+            if let Err(e) = record_game_pid(&instance_dir, child.id()) {
+                tracing::warn!("{}", e);
+            }
+            Ok(LaunchResult::Success(GameSession::new(
+                child,
+                &instance_manager,
+                &instance.info.id,
+            )))
         }
         Err(e) => {
             anyhow::bail!("Failed to check process: {}", e)
@@ -2,7 +2,7 @@
 //!
 //! Download and manage Minecraft libraries.
 
-use crate::core::version::{Artifact, Library};
+use crate::core::version::{Artifact, Library, Platform};
 use crate::util::hash::verify_sha1;
 use anyhow::{Context, Result};
 use futures::stream::{self, StreamExt};
@@ -111,13 +111,23 @@ impl LibraryManager {
 
         let response = reqwest::get(&artifact.url)
             .await
-            .context("Failed to download library")?;
+            .context("Failed to download library")?
+            .error_for_status()
+            .with_context(|| format!("Failed to download library: {}", artifact.path))?;
         let bytes = response.bytes().await?;
 
+        // Derived natives have no SHA1 in the version JSON, but Maven Central
+        // publishes one next to each artifact
+        let sha1 = if is_derived_native(artifact) {
+            maven_sha1(&artifact.url).await?
+        } else {
+            artifact.sha1.clone()
+        };
+
         std::fs::write(&dest, &bytes)?;
 
         // Verify SHA1 (skip if empty - Fabric libraries don't have SHA1)
-        if !artifact.sha1.is_empty() && !verify_sha1(&dest, &artifact.sha1)? {
+        if !sha1.is_empty() && !verify_sha1(&dest, &sha1)? {
             std::fs::remove_file(&dest)?;
             anyhow::bail!("SHA1 mismatch for library: {}", artifact.path);
         }
@@ -146,14 +156,11 @@ impl LibraryManager {
         }
 
         // Combine all artifacts to download
-        let all_downloads: Vec<(&Library, &Artifact)> = missing
-            .into_iter()
-            .chain(missing_natives.into_iter())
-            .collect();
+        let all_downloads: Vec<(&Library, &Artifact)> =
+            missing.into_iter().chain(missing_natives).collect();
 
         // Use atomic counter for progress
         let completed = Arc::new(AtomicUsize::new(0));
-        let libraries_dir = self.libraries_dir.clone();
 
         // Number of concurrent downloads
         const CONCURRENT_DOWNLOADS: usize = 8;
@@ -164,34 +171,17 @@ impl LibraryManager {
         // Create download futures
         let download_results: Vec<Result<String>> = stream::iter(all_downloads)
             .map(|(lib, artifact)| {
-                let libraries_dir = libraries_dir.clone();
                 let completed = Arc::clone(&completed);
-                let lib_name = lib.name.clone();
-                let artifact_path = artifact.path.clone();
-                let artifact_url = artifact.url.clone();
-                let artifact_sha1 = artifact.sha1.clone();
-
                 async move {
-                    let dest = libraries_dir.join(&artifact_path);
-
-                    if let Some(parent) = dest.parent() {
-                        std::fs::create_dir_all(parent)?;
+                    match self.download_library(artifact).await {
+                        // LWJGL doesn't publish every architecture for every release
+                        Err(e) if is_derived_native(artifact) && is_not_found(&e) => {
+                            tracing::warn!("Skipping unpublished native {}", artifact.path);
+                        }
+                        result => result?,
                     }
-
-                    let response = reqwest::get(&artifact_url)
-                        .await
-                        .context("Failed to download library")?;
-                    let bytes = response.bytes().await?;
-                    std::fs::write(&dest, &bytes)?;
-
-                    // Verify SHA1 if provided
-                    if !artifact_sha1.is_empty() && !verify_sha1(&dest, &artifact_sha1)? {
-                        std::fs::remove_file(&dest)?;
-                        anyhow::bail!("SHA1 mismatch for library: {}", artifact_path);
-                    }
-
                     completed.fetch_add(1, Ordering::SeqCst);
-                    Ok(lib_name)
+                    Ok(lib.name.clone())
                 }
             })
             .buffer_unordered(CONCURRENT_DOWNLOADS)
@@ -229,6 +219,80 @@ impl LibraryManager {
         paths.join(separator)
     }
 
+    /// Add LWJGL natives for architectures Mojang doesn't ship (e.g. Linux ARM)
+    ///
+    /// Vanilla version JSONs only list x86_64 natives for some platforms. LWJGL
+    /// publishes ARM builds on Maven Central, so derive those artifacts from the
+    /// x86_64 ones when no native for the platform's architecture is listed.
+    /// Not every LWJGL release has every architecture, so derived natives
+    /// that turn out to be missing are skipped at download time.
+    pub fn add_missing_arch_natives(libraries: &mut Vec<Library>, platform: &Platform) {
+        let Some(suffix) = platform.arch.classifier_suffix() else {
+            return;
+        };
+        if !platform.arch.is_arm() {
+            return;
+        }
+
+        // Legacy format: add an arch-specific classifier next to the default one
+        for lib in libraries.iter_mut() {
+            if !lib.name.starts_with("org.lwjgl:") || !lib.should_include_on(platform) {
+                continue;
+            }
+            let Some(base) = lib.get_native_classifier_on(platform) else {
+                continue;
+            };
+            if base.ends_with(suffix) {
+                continue;
+            }
+            let Some(classifiers) = lib.downloads.as_mut().and_then(|d| d.classifiers.as_mut())
+            else {
+                continue;
+            };
+            if let Some(artifact) = classifiers.get(&base) {
+                let specific = format!("{}-{}", base, suffix);
+                let derived = maven_central_native(artifact, &base, &specific);
+                tracing::info!("Using {} from Maven Central for {}", specific, lib.name);
+                classifiers.insert(specific, derived);
+            }
+        }
+
+        // Modern format: add a separate library for the arch-specific classifier
+        let default_classifier = format!("natives-{}", platform.os.classifier_name());
+        let specific_classifier = format!("{}-{}", default_classifier, suffix);
+        let mut derived = Vec::new();
+
+        for lib in libraries.iter() {
+            if !lib.name.starts_with("org.lwjgl:") || lib.classifier() != Some(&default_classifier)
+            {
+                continue;
+            }
+
+            let specific_name = lib
+                .name
+                .replacen(&default_classifier, &specific_classifier, 1);
+            if libraries.iter().any(|l| l.name == specific_name) {
+                continue;
+            }
+
+            if let Some(artifact) = lib.get_artifact() {
+                let mut native = lib.clone();
+                native.name = specific_name;
+                if let Some(ref mut downloads) = native.downloads {
+                    downloads.artifact = Some(maven_central_native(
+                        artifact,
+                        &default_classifier,
+                        &specific_classifier,
+                    ));
+                }
+                tracing::info!("Using {} from Maven Central", native.name);
+                derived.push(native);
+            }
+        }
+
+        libraries.extend(derived);
+    }
+
     /// Extract native libraries to a directory
     pub fn extract_natives(&self, libraries: &[Library], natives_dir: &Path) -> Result<()> {
         // Clean and recreate natives directory
@@ -237,43 +301,23 @@ impl LibraryManager {
         }
         std::fs::create_dir_all(natives_dir)?;
 
-        // Determine current platform native suffix
-        let native_suffix = if cfg!(target_os = "macos") {
-            if cfg!(target_arch = "aarch64") {
-                "natives-macos-arm64"
-            } else {
-                "natives-macos"
-            }
-        } else if cfg!(target_os = "windows") {
-            if cfg!(target_arch = "x86_64") {
-                "natives-windows"
-            } else {
-                "natives-windows-x86"
-            }
-        } else {
-            "natives-linux"
-        };
-
-        println!("Looking for natives with suffix: {}", native_suffix);
-
         for lib in libraries.iter().filter(|l| l.should_include()) {
             // Method 1: Traditional natives field
-            if lib.natives.is_some() {
-                if let Some(native_path) = self.get_native_path(lib) {
-                    if native_path.exists() {
-                        self.extract_native_jar(&native_path, natives_dir, lib)?;
-                    }
-                }
+            if lib.natives.is_some()
+                && let Some(native_path) = self.get_native_path(lib)
+                && native_path.exists()
+            {
+                self.extract_native_jar(&native_path, natives_dir, lib)?;
             }
 
-            // Method 2: Modern format - library name contains natives
-            if lib.name.contains("natives") && lib.name.contains(native_suffix) {
-                if let Some(lib_path) = self.get_library_path(lib) {
-                    if lib_path.exists() {
-                        println!("Extracting modern native: {:?}", lib_path);
-                        self.extract_native_jar(&lib_path, natives_dir, lib)?;
-                    }
-                }
+            // Method 2: Modern format - classifier names the platform (already
+            // filtered to the current OS and architecture by should_include)
+            if lib.native_target().is_some()
+                && let Some(lib_path) = self.get_library_path(lib)
+                && lib_path.exists()
+            {
+                println!("Extracting modern native: {:?}", lib_path);
+                self.extract_native_jar(&lib_path, natives_dir, lib)?;
             }
         }
 
@@ -337,3 +381,296 @@ impl LibraryManager {
         Ok(())
     }
 }
+
+/// Maven Central repository (hosts all LWJGL native builds)
+const MAVEN_CENTRAL_URL: &str = "https://repo1.maven.org/maven2";
+
+/// Derive a native artifact with another classifier from Maven Central
+fn maven_central_native(artifact: &Artifact, classifier: &str, specific: &str) -> Artifact {
+    let path = artifact.path.replace(
+        &format!("-{}.jar", classifier),
+        &format!("-{}.jar", specific),
+    );
+    Artifact {
+        url: format!("{}/{}", MAVEN_CENTRAL_URL, path),
+        path,
+        // Not in the version JSON; download_library fetches the published .sha1
+        sha1: String::new(),
+        size: 0,
+    }
+}
+
+/// Whether an artifact was derived by [`maven_central_native`]
+fn is_derived_native(artifact: &Artifact) -> bool {
+    artifact.sha1.is_empty() && artifact.url.starts_with(MAVEN_CENTRAL_URL)
+}
+
+/// Whether a download failed because the server has no such file
+fn is_not_found(error: &anyhow::Error) -> bool {
+    error
+        .downcast_ref::<reqwest::Error>()
+        .and_then(|e| e.status())
+        == Some(reqwest::StatusCode::NOT_FOUND)
+}
+
+/// Fetch the `.sha1` checksum published next to a Maven artifact
+async fn maven_sha1(url: &str) -> Result<String> {
+    let text = reqwest::get(format!("{}.sha1", url))
+        .await
+        .context("Failed to download checksum")?
+        .error_for_status()
+        .with_context(|| format!("No checksum published for {}", url))?
+        .text()
+        .await?;
+    // Some repositories append the file name after the hash
+    let sha1 = text.split_whitespace().next().unwrap_or_default();
+    if sha1.len() != 40 || !sha1.chars().all(|c| c.is_ascii_hexdigit()) {
+        anyhow::bail!("Invalid checksum for {}", url);
+    }
+    Ok(sha1.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::version::{Arch, Os};
+    use crate::util::temp::TempDir;
+
+    fn platform(os: Os, arch: Arch) -> Platform {
+        Platform {
+            os,
+            os_version: String::new(),
+            arch,
+        }
+    }
+
+    fn artifact_json(path: &str) -> String {
+        format!(
+            r#"{{"path":"{}","sha1":"abc","size":1,"url":"https://libraries.minecraft.net/{}"}}"#,
+            path, path
+        )
+    }
+
+    fn modern_native(classifier: &str, os: &str) -> String {
+        let path = format!("org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-{}.jar", classifier);
+        format!(
+            r#"{{"name":"org.lwjgl:lwjgl:3.3.3:{}","downloads":{{"artifact":{}}},"rules":[{{"action":"allow","os":{{"name":"{}"}}}}]}}"#,
+            classifier,
+            artifact_json(&path),
+            os
+        )
+    }
+
+    /// Library list in the style of 1.19+ version JSONs
+    fn modern_libraries() -> Vec<Library> {
+        let entries = [
+            format!(
+                r#"{{"name":"org.lwjgl:lwjgl:3.3.3","downloads":{{"artifact":{}}}}}"#,
+                artifact_json("org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3.jar")
+            ),
+            modern_native("natives-linux", "linux"),
+            modern_native("natives-macos", "osx"),
+            modern_native("natives-macos-arm64", "osx"),
+            modern_native("natives-windows", "windows"),
+            modern_native("natives-windows-x86", "windows"),
+            modern_native("natives-windows-arm64", "windows"),
+        ];
+        serde_json::from_str(&format!("[{}]", entries.join(","))).unwrap()
+    }
+
+    /// Library list in the style of 1.13-1.18 version JSONs (`natives` map)
+    fn legacy_libraries() -> Vec<Library> {
+        let json = format!(
+            r#"[{{"name":"org.lwjgl:lwjgl:3.2.2","downloads":{{"artifact":{},"classifiers":{{"natives-linux":{},"natives-macos":{},"natives-windows":{}}}}},"natives":{{"linux":"natives-linux","osx":"natives-macos","windows":"natives-windows"}}}},
+              {{"name":"org.lwjgl.lwjgl:lwjgl-platform:2.9.4","downloads":{{"classifiers":{{"natives-windows-32":{},"natives-windows-64":{}}}}},"natives":{{"windows":"natives-windows-${{arch}}"}}}}]"#,
+            artifact_json("org/lwjgl/lwjgl/3.2.2/lwjgl-3.2.2.jar"),
+            artifact_json("org/lwjgl/lwjgl/3.2.2/lwjgl-3.2.2-natives-linux.jar"),
+            artifact_json("org/lwjgl/lwjgl/3.2.2/lwjgl-3.2.2-natives-macos.jar"),
+            artifact_json("org/lwjgl/lwjgl/3.2.2/lwjgl-3.2.2-natives-windows.jar"),
+            artifact_json("lwjgl-platform-2.9.4-natives-windows-32.jar"),
+            artifact_json("lwjgl-platform-2.9.4-natives-windows-64.jar"),
+        );
+        serde_json::from_str(&json).unwrap()
+    }
+
+    fn included_classifiers(libraries: &[Library], platform: &Platform) -> Vec<String> {
+        libraries
+            .iter()
+            .filter(|lib| lib.should_include_on(platform))
+            .filter_map(|lib| lib.classifier().map(str::to_string))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_download_checks_status_and_checksum() {
+        use crate::core::mods::mock::MockServer;
+
+        let sha1 = "a".repeat(40);
+        let server = MockServer::start(vec![
+            (
+                "GET",
+                "/ok.jar.sha1".to_string(),
+                200,
+                format!("{}  ok.jar\n", sha1).into_bytes(),
+            ),
+            ("GET", "/bad.jar.sha1".to_string(), 200, b"<html>".to_vec()),
+            (
+                "GET",
+                "/missing.jar".to_string(),
+                404,
+                b"not found".to_vec(),
+            ),
+        ])
+        .await;
+        assert_eq!(
+            maven_sha1(&format!("{}/ok.jar", server.base_url))
+                .await
+                .unwrap(),
+            sha1
+        );
+        assert!(
+            maven_sha1(&format!("{}/bad.jar", server.base_url))
+                .await
+                .is_err()
+        );
+        assert!(
+            maven_sha1(&format!("{}/none.jar", server.base_url))
+                .await
+                .is_err()
+        );
+
+        let dir = TempDir::new("libs");
+        let artifact = Artifact {
+            path: "org/lwjgl/missing.jar".to_string(),
+            url: format!("{}/missing.jar", server.base_url),
+            sha1: String::new(),
+            size: 0,
+        };
+        let err = LibraryManager::new(dir.to_path_buf())
+            .download_library(&artifact)
+            .await
+            .unwrap_err();
+        assert!(is_not_found(&err), "{:#}", err);
+        assert!(!dir.join(&artifact.path).exists());
+    }
+
+    #[test]
+    fn test_modern_native_selection() {
+        let libraries = modern_libraries();
+        let cases = [
+            (Os::Linux, Arch::X86_64, vec!["natives-linux"]),
+            (Os::Linux, Arch::Arm64, vec![]),
+            (Os::Linux, Arch::Arm32, vec![]),
+            (Os::Osx, Arch::X86_64, vec!["natives-macos"]),
+            (Os::Osx, Arch::Arm64, vec!["natives-macos-arm64"]),
+            (Os::Windows, Arch::X86_64, vec!["natives-windows"]),
+            (Os::Windows, Arch::X86, vec!["natives-windows-x86"]),
+            (Os::Windows, Arch::Arm64, vec!["natives-windows-arm64"]),
+        ];
+
+        for (os, arch, expected) in cases {
+            let platform = platform(os, arch);
+            assert_eq!(
+                included_classifiers(&libraries, &platform),
+                expected,
+                "{:?}/{:?}",
+                os,
+                arch
+            );
+            // The main LWJGL jar is always on the classpath
+            assert!(libraries[0].should_include_on(&platform));
+        }
+    }
+
+    #[test]
+    fn test_legacy_native_classifier() {
+        let libraries = legacy_libraries();
+        let cases = [
+            (Os::Linux, Arch::X86_64, Some("natives-linux"), None),
+            (Os::Linux, Arch::Arm64, Some("natives-linux"), None),
+            (Os::Osx, Arch::Arm64, Some("natives-macos"), None),
+            (
+                Os::Windows,
+                Arch::X86_64,
+                Some("natives-windows"),
+                Some("natives-windows-64"),
+            ),
+            (
+                Os::Windows,
+                Arch::X86,
+                Some("natives-windows"),
+                Some("natives-windows-32"),
+            ),
+        ];
+
+        for (os, arch, lwjgl3, lwjgl2) in cases {
+            let platform = platform(os, arch);
+            assert_eq!(
+                libraries[0].get_native_classifier_on(&platform).as_deref(),
+                lwjgl3,
+                "{:?}/{:?}",
+                os,
+                arch
+            );
+            assert_eq!(
+                libraries[1].get_native_classifier_on(&platform).as_deref(),
+                lwjgl2,
+                "{:?}/{:?}",
+                os,
+                arch
+            );
+        }
+    }
+
+    #[test]
+    fn test_add_missing_arch_natives_modern() {
+        let mut libraries = modern_libraries();
+        let linux_arm = platform(Os::Linux, Arch::Arm64);
+        LibraryManager::add_missing_arch_natives(&mut libraries, &linux_arm);
+
+        assert_eq!(
+            included_classifiers(&libraries, &linux_arm),
+            vec!["natives-linux-arm64"]
+        );
+        let derived = libraries.last().unwrap();
+        let artifact = derived.get_artifact().unwrap();
+        assert_eq!(
+            artifact.path,
+            "org/lwjgl/lwjgl/3.3.3/lwjgl-3.3.3-natives-linux-arm64.jar"
+        );
+        assert!(artifact.url.starts_with(MAVEN_CENTRAL_URL));
+
+        // macOS arm64 natives already exist, nothing is added
+        let mut libraries = modern_libraries();
+        let count = libraries.len();
+        LibraryManager::add_missing_arch_natives(&mut libraries, &platform(Os::Osx, Arch::Arm64));
+        assert_eq!(libraries.len(), count);
+
+        // x86_64 never needs derived natives
+        LibraryManager::add_missing_arch_natives(
+            &mut libraries,
+            &platform(Os::Linux, Arch::X86_64),
+        );
+        assert_eq!(libraries.len(), count);
+    }
+
+    #[test]
+    fn test_add_missing_arch_natives_legacy() {
+        let mut libraries = legacy_libraries();
+        let linux_arm = platform(Os::Linux, Arch::Arm32);
+        LibraryManager::add_missing_arch_natives(&mut libraries, &linux_arm);
+
+        assert_eq!(
+            libraries[0].get_native_classifier_on(&linux_arm).as_deref(),
+            Some("natives-linux-arm32")
+        );
+        let artifact = libraries[0].get_native_artifact_on(&linux_arm).unwrap();
+        assert_eq!(
+            artifact.path,
+            "org/lwjgl/lwjgl/3.2.2/lwjgl-3.2.2-natives-linux-arm32.jar"
+        );
+
+        // LWJGL 2 has no ARM builds and is left alone
+        assert_eq!(libraries[1].get_native_classifier_on(&linux_arm), None);
+    }
+}
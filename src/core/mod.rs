@@ -4,12 +4,16 @@
 
 pub mod asset;
 pub mod auth;
+pub mod backup;
 pub mod fabric;
 pub mod forge;
 pub mod instance;
 pub mod java;
 pub mod launch;
 pub mod library;
+pub mod modpack;
 pub mod mods;
+pub mod snapshot;
+pub mod template;
 pub mod update;
 pub mod version;
@@ -0,0 +1,298 @@
+//! Portable instance archives (.zip)
+//!
+//! A zip holding `instance.toml` and a chosen subset of the game directory
+//! under `.minecraft/`, for sharing complete instances between machines.
+
+use super::{ExportReport, apply_pack_icon, archive_path, extract_prefix, safe_join};
+use crate::core::instance::{ICON_FILE, Instance, InstanceJavaConfig, InstanceManager};
+use anyhow::{Context, Result};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+use zip::write::SimpleFileOptions;
+
+/// Instance config entry inside the archive
+pub(super) const INSTANCE_FILE: &str = "instance.toml";
+
+/// Folder inside the archive holding game files
+const GAME_DIR_PREFIX: &str = ".minecraft";
+
+/// Game directory entries exported when no selection is given
+pub const DEFAULT_ARCHIVE_CONTENT: &[&str] = &[
+    "mods",
+    "config",
+    "resourcepacks",
+    "shaderpacks",
+    "saves",
+    "options.txt",
+];
+
+/// Entries never exported, at any depth
+const ARCHIVE_EXCLUDES: &[&str] = &[
+    "logs",
+    "crash-reports",
+    "natives",
+    ".cache",
+    ".fabric",
+    ".mixin.out",
+];
+
+/// Export an instance with the selected top-level game directory entries
+pub fn export_archive(
+    manager: &InstanceManager,
+    name: &str,
+    output: &Path,
+    include: &[&str],
+    on_progress: &(dyn Fn(&str) + Sync),
+) -> Result<ExportReport> {
+    let instance = manager.load(name)?;
+    let game_dir = manager.get_game_dir(&instance.info.id);
+
+    on_progress("Scanning files...");
+    let mut files = Vec::new();
+    for entry in include {
+        if ARCHIVE_EXCLUDES.contains(entry) {
+            continue;
+        }
+        let relative = PathBuf::from(entry);
+        safe_join(&game_dir, entry)?;
+        let path = game_dir.join(&relative);
+        if path.is_dir() {
+            collect_files(&game_dir, &path, &mut files)?;
+        } else if path.is_file() {
+            files.push(relative);
+        }
+    }
+    files.sort();
+    files.dedup();
+
+    on_progress("Writing archive...");
+    let tmp = output.with_extension("zip.part");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&tmp)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file(INSTANCE_FILE, options)?;
+    zip.write_all(toml::to_string_pretty(&instance)?.as_bytes())?;
+    if let Some(icon) = manager.icon_path(&instance) {
+        zip.start_file(ICON_FILE, options)?;
+        zip.write_all(&std::fs::read(icon)?)?;
+    }
+
+    for (i, relative) in files.iter().enumerate() {
+        on_progress(&format!(
+            "Adding {}/{}: {}",
+            i + 1,
+            files.len(),
+            relative.display()
+        ));
+        zip.start_file(
+            format!("{}/{}", GAME_DIR_PREFIX, archive_path(relative)),
+            options,
+        )?;
+        let mut file = std::fs::File::open(game_dir.join(relative))?;
+        std::io::copy(&mut file, &mut zip)?;
+    }
+    zip.finish()?;
+    std::fs::rename(&tmp, output).context("Failed to write archive")?;
+
+    Ok(ExportReport {
+        indexed: 0,
+        overrides: files.len(),
+    })
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        if ARCHIVE_EXCLUDES.iter().any(|e| entry.file_name() == *e) {
+            continue;
+        }
+        let path = entry.path();
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if file_type.is_file() {
+            files.push(path.strip_prefix(root)?.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+/// Read `instance.toml` and check every entry before anything is extracted
+fn validate_archive<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>) -> Result<Instance> {
+    let prefix = format!("{}/", GAME_DIR_PREFIX);
+    for i in 0..archive.len() {
+        let entry = archive.by_index(i)?;
+        let name = entry.name();
+        if name == INSTANCE_FILE || name == ICON_FILE || name == prefix {
+            continue;
+        }
+        let Some(relative) = name.strip_prefix(&prefix) else {
+            anyhow::bail!("Unexpected entry in instance archive: {}", name);
+        };
+        if entry.enclosed_name().is_none() || entry.is_symlink() {
+            anyhow::bail!("Unsafe path in archive: {}", name);
+        }
+        safe_join(Path::new(""), relative.trim_end_matches('/'))?;
+    }
+
+    let mut contents = String::new();
+    archive
+        .by_name(INSTANCE_FILE)
+        .context("Not an instance archive (instance.toml missing)")?
+        .read_to_string(&mut contents)?;
+    toml::from_str(&contents).context("Failed to parse instance.toml in archive")
+}
+
+/// Import an instance archive, renaming the instance on collision
+pub fn import_archive(
+    manager: &InstanceManager,
+    path: &Path,
+    name: Option<&str>,
+    on_progress: &(dyn Fn(&str) + Sync),
+) -> Result<Instance> {
+    let file = std::fs::File::open(path).context("Failed to open archive")?;
+    let mut archive = ZipArchive::new(file).context("Archive is not a valid zip")?;
+    let exported = validate_archive(&mut archive)?;
+
+    let name = manager.unique_name(name.unwrap_or(&exported.info.name));
+    on_progress(&format!("Creating instance {}...", name));
+    let mut instance = manager.create(
+        &name,
+        &exported.info.version,
+        exported.info.loader.clone(),
+        exported.info.loader_version.clone(),
+    )?;
+    // Keep the portable settings; the Java path and play history belong to
+    // the exporter's machine
+    instance.java = InstanceJavaConfig {
+        override_global: false,
+        path: String::new(),
+        ..exported.java
+    };
+    instance.game = exported.game;
+    instance.info.group = exported.info.group;
+    instance.info.tags = exported.info.tags;
+    instance.info.icon = exported.info.icon;
+
+    let result = manager.save(&instance).and_then(|_| {
+        on_progress("Extracting files...");
+        let game_dir = manager.get_game_dir(&instance.info.id);
+        extract_prefix(&mut archive, GAME_DIR_PREFIX, &game_dir)
+    });
+    match result {
+        Ok(_) => {
+            apply_pack_icon(manager, &instance.info.id, &mut archive);
+            // Reload to pick up the icon, or drop a `custom` icon that had no file
+            manager.load_id(&instance.info.id)
+        }
+        Err(e) => {
+            if let Err(cleanup) = manager.delete(&instance.info.id) {
+                tracing::warn!("Failed to remove partial instance {}: {}", name, cleanup);
+            }
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::ModLoader;
+    use crate::util::temp::TempDir;
+
+    #[test]
+    fn test_archive_round_trip() {
+        let dir = TempDir::new("archive-roundtrip");
+        let manager = InstanceManager::with_dir(&dir.join("instances"));
+        let mut instance = manager
+            .create("Team", "1.20.1", ModLoader::Fabric, Some("0.15.3".into()))
+            .unwrap();
+        instance.java.max_memory = "6G".to_string();
+        instance.java.override_global = true;
+        instance.java.path = "/opt/java/bin/java".to_string();
+        instance.info.favorite = true;
+        instance.info.launch_count = 3;
+        manager.save(&instance).unwrap();
+        let mut icon = std::io::Cursor::new(Vec::new());
+        image::RgbaImage::new(16, 16)
+            .write_to(&mut icon, image::ImageFormat::Png)
+            .unwrap();
+        manager
+            .set_icon_image(&instance.info.id, icon.get_ref())
+            .unwrap();
+
+        let game_dir = manager.get_game_dir(&instance.info.id);
+        for dir in ["mods", "saves/World/region", "logs", "config/.cache"] {
+            std::fs::create_dir_all(game_dir.join(dir)).unwrap();
+        }
+        std::fs::write(game_dir.join("mods/a.jar"), b"a").unwrap();
+        std::fs::write(game_dir.join("saves/World/level.dat"), b"l").unwrap();
+        std::fs::write(game_dir.join("logs/latest.log"), b"x").unwrap();
+        std::fs::write(game_dir.join("config/.cache/c"), b"x").unwrap();
+        std::fs::write(game_dir.join("options.txt"), b"fov:70").unwrap();
+
+        let output = dir.join("team.zip");
+        let report = export_archive(
+            &manager,
+            "Team",
+            &output,
+            &["mods", "config", "options.txt", "logs"],
+            &|_| {},
+        )
+        .unwrap();
+        // logs are excluded even when selected; saves were not selected
+        assert_eq!(report.overrides, 2);
+
+        let imported = import_archive(&manager, &output, None, &|_| {}).unwrap();
+        assert_eq!(imported.info.name, "Team (2)");
+        assert_eq!(imported.java.max_memory, "6G");
+        assert!(!imported.java.override_global && imported.java.path.is_empty());
+        assert!(!imported.info.favorite);
+        assert_eq!(imported.info.launch_count, 0);
+        assert_eq!(imported.info.loader, ModLoader::Fabric);
+        assert!(manager.icon_path(&imported).is_some());
+
+        let game_dir = manager.get_game_dir(&imported.info.id);
+        assert!(game_dir.join("mods/a.jar").exists());
+        assert!(game_dir.join("options.txt").exists());
+        assert!(!game_dir.join("saves").exists());
+        assert!(!game_dir.join("logs").exists());
+        assert_eq!(manager.load("Team (2)").unwrap().info.name, "Team (2)");
+    }
+
+    #[test]
+    fn test_import_rejects_zip_slip() {
+        let dir = TempDir::new("archive-zipslip");
+        let manager = InstanceManager::with_dir(&dir.join("instances"));
+        let path = dir.join("evil.zip");
+
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        let options = SimpleFileOptions::default();
+        zip.start_file(INSTANCE_FILE, options).unwrap();
+        zip.write_all(
+            br#"[info]
+name = "Evil"
+version = "1.20.1"
+loader = "vanilla"
+created_at = "2024-01-01T00:00:00Z"
+
+[java]
+
+[game]
+"#,
+        )
+        .unwrap();
+        zip.start_file(".minecraft/mods/ok.jar", options).unwrap();
+        zip.write_all(b"ok").unwrap();
+        zip.start_file(".minecraft/../../evil.txt", options)
+            .unwrap();
+        zip.write_all(b"evil").unwrap();
+        zip.finish().unwrap();
+
+        let err = import_archive(&manager, &path, None, &|_| {}).unwrap_err();
+        assert!(err.to_string().contains("Unsafe path"), "{}", err);
+        assert!(!manager.exists("Evil"));
+        assert!(!dir.join("evil.txt").exists());
+    }
+}
@@ -0,0 +1,411 @@
+//! CurseForge modpack format
+//!
+//! A zip with a `manifest.json` listing CurseForge project and file IDs, plus
+//! an overrides folder copied into the game directory.
+
+use super::{
+    ImportReport, ManualDownload, apply_pack_icon, download_verified, extract_prefix, safe_join,
+};
+use crate::core::instance::{InstanceManager, ModLoader};
+use crate::core::mods::{CfFile, CurseForgeClient, file_page_url};
+use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+/// Manifest file name inside the archive
+pub(super) const MANIFEST_FILE: &str = "manifest.json";
+
+/// Concurrent downloads during import
+const CONCURRENT_DOWNLOADS: usize = 8;
+
+/// Project class IDs for content that does not go in `mods/`
+const RESOURCE_PACKS_CLASS_ID: u32 = 12;
+const SHADERS_CLASS_ID: u32 = 6552;
+
+/// `manifest.json`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CfManifest {
+    pub minecraft: CfManifestMinecraft,
+    pub manifest_type: String,
+    pub manifest_version: u32,
+    pub name: String,
+    #[serde(default)]
+    pub files: Vec<CfManifestFile>,
+    /// Folder in the archive copied into the game directory
+    #[serde(default = "default_overrides")]
+    pub overrides: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CfManifestMinecraft {
+    pub version: String,
+    #[serde(default)]
+    pub mod_loaders: Vec<CfManifestLoader>,
+}
+
+/// A loader entry such as `forge-47.2.0`
+#[derive(Debug, Clone, Deserialize)]
+pub struct CfManifestLoader {
+    pub id: String,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+/// A file referenced by file ID
+#[derive(Debug, Clone, Deserialize)]
+pub struct CfManifestFile {
+    #[serde(rename = "fileID")]
+    pub file_id: u64,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_overrides() -> String {
+    "overrides".to_string()
+}
+
+fn default_required() -> bool {
+    true
+}
+
+impl CfManifest {
+    /// Minecraft version, loader and loader version required by the pack
+    pub fn game_requirements(&self) -> Result<(String, ModLoader, Option<String>)> {
+        let loader = self
+            .minecraft
+            .mod_loaders
+            .iter()
+            .find(|l| l.primary)
+            .or_else(|| self.minecraft.mod_loaders.first());
+
+        let Some(loader) = loader else {
+            return Ok((self.minecraft.version.clone(), ModLoader::Vanilla, None));
+        };
+        let (loader, version) = parse_loader_id(&loader.id)?;
+        Ok((self.minecraft.version.clone(), loader, Some(version)))
+    }
+}
+
+/// Split a manifest loader ID (`forge-47.2.0`, `neoforge-20.4.80`) into
+/// loader and version
+fn parse_loader_id(id: &str) -> Result<(ModLoader, String)> {
+    let (name, version) = id
+        .split_once('-')
+        .filter(|(_, v)| !v.is_empty())
+        .with_context(|| format!("Invalid mod loader in manifest: {}", id))?;
+
+    let loader = match name {
+        "forge" => ModLoader::Forge,
+        "neoforge" => ModLoader::NeoForge,
+        "fabric" => ModLoader::Fabric,
+        "quilt" => ModLoader::Quilt,
+        other => anyhow::bail!("Unsupported mod loader in manifest: {}", other),
+    };
+    Ok((loader, version.to_string()))
+}
+
+/// Read and validate the manifest of a CurseForge modpack
+pub fn read_manifest<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>) -> Result<CfManifest> {
+    let mut contents = String::new();
+    archive
+        .by_name(MANIFEST_FILE)
+        .context("Not a CurseForge modpack (manifest.json missing)")?
+        .read_to_string(&mut contents)?;
+
+    let manifest: CfManifest =
+        serde_json::from_str(&contents).context("Failed to parse manifest.json")?;
+    if manifest.manifest_type != "minecraftModpack" {
+        anyhow::bail!("Unsupported manifest type: {}", manifest.manifest_type);
+    }
+    if manifest.manifest_version != 1 {
+        anyhow::bail!(
+            "Unsupported manifest version: {}",
+            manifest.manifest_version
+        );
+    }
+    Ok(manifest)
+}
+
+/// Import a CurseForge modpack zip as a new instance
+///
+/// Files whose authors disallow third-party downloads are listed in the
+/// report instead of failing the import. Optional files are skipped.
+pub async fn import_curseforge(
+    manager: &InstanceManager,
+    pack: &Path,
+    name: Option<&str>,
+    client: &CurseForgeClient,
+    on_progress: &(dyn Fn(&str) + Sync),
+) -> Result<ImportReport> {
+    let file = std::fs::File::open(pack).context("Failed to open modpack")?;
+    let mut archive = ZipArchive::new(file).context("Modpack is not a valid zip")?;
+    let manifest = read_manifest(&mut archive)?;
+    let (mc_version, loader, loader_version) = manifest.game_requirements()?;
+    // Reject an overrides folder like "../x" before anything is created
+    safe_join(Path::new(""), &manifest.overrides)?;
+
+    on_progress("Resolving files on CurseForge...");
+    let file_ids: Vec<u64> = manifest
+        .files
+        .iter()
+        .filter(|f| f.required)
+        .map(|f| f.file_id)
+        .collect();
+    let files = client.files(&file_ids).await?;
+    if let Some(missing) = file_ids
+        .iter()
+        .find(|id| !files.iter().any(|f| f.id == **id))
+    {
+        anyhow::bail!("CurseForge file {} not found", missing);
+    }
+
+    let mut mod_ids: Vec<u64> = files.iter().map(|f| f.mod_id).collect();
+    mod_ids.sort_unstable();
+    mod_ids.dedup();
+    let projects: HashMap<u64, _> = client
+        .mods(&mod_ids)
+        .await?
+        .into_iter()
+        .map(|m| (m.id, m))
+        .collect();
+
+    let name = manager.unique_name(name.unwrap_or(&manifest.name));
+
+    on_progress(&format!("Creating instance {}...", name));
+    let instance = manager.create(&name, &mc_version, loader, loader_version)?;
+    let game_dir = manager.get_game_dir(&instance.info.id);
+
+    let mut manual = Vec::new();
+    let result = async {
+        let mut downloads: Vec<(&CfFile, PathBuf)> = Vec::new();
+        for file in &files {
+            let project = projects.get(&file.mod_id);
+            let folder = content_folder(project.and_then(|p| p.class_id));
+            let dest = safe_join(&game_dir.join(folder), &file.file_name)?;
+
+            if file.download_url().is_some() {
+                downloads.push((file, dest));
+            } else {
+                let website = project.and_then(|p| p.links.website_url.as_deref());
+                manual.push(ManualDownload {
+                    name: project.map_or_else(|| file.display_name.clone(), |p| p.name.clone()),
+                    filename: file.file_name.clone(),
+                    page_url: file_page_url(website, file),
+                    dest_dir: game_dir.join(folder),
+                });
+            }
+        }
+
+        install_files(
+            &mut archive,
+            &downloads,
+            &manifest.overrides,
+            &game_dir,
+            on_progress,
+        )
+        .await
+    }
+    .await;
+    if result.is_ok() {
+        apply_pack_icon(manager, &instance.info.id, &mut archive);
+    }
+    match result {
+        Ok((downloaded, overrides)) => Ok(ImportReport {
+            instance_name: name,
+            downloaded,
+            overrides,
+            manual,
+        }),
+        Err(e) => {
+            if let Err(cleanup) = manager.delete(&instance.info.id) {
+                tracing::warn!("Failed to remove partial instance {}: {}", name, cleanup);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Download resolved files and extract overrides into the game directory
+async fn install_files<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    files: &[(&CfFile, PathBuf)],
+    overrides: &str,
+    game_dir: &Path,
+    on_progress: &(dyn Fn(&str) + Sync),
+) -> Result<(usize, usize)> {
+    let client = reqwest::Client::new();
+    let total = files.len();
+
+    let results: Vec<Result<()>> = stream::iter(files.iter().enumerate())
+        .map(|(i, (file, dest))| {
+            let client = &client;
+            async move {
+                on_progress(&format!(
+                    "Downloading {}/{}: {}",
+                    i + 1,
+                    total,
+                    file.file_name
+                ));
+                let urls: Vec<String> = file
+                    .download_url()
+                    .map(str::to_string)
+                    .into_iter()
+                    .collect();
+                download_verified(client, &urls, &file.file_hashes(), dest).await
+            }
+        })
+        .buffer_unordered(CONCURRENT_DOWNLOADS)
+        .collect()
+        .await;
+    results.into_iter().collect::<Result<Vec<_>>>()?;
+
+    on_progress("Applying overrides...");
+    let overrides = extract_prefix(archive, overrides, game_dir)?;
+
+    Ok((total, overrides))
+}
+
+/// Game directory folder for a project class
+fn content_folder(class_id: Option<u32>) -> &'static str {
+    match class_id {
+        Some(RESOURCE_PACKS_CLASS_ID) => "resourcepacks",
+        Some(SHADERS_CLASS_ID) => "shaderpacks",
+        _ => "mods",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mods::mock::MockServer;
+    use crate::util::hash::sha1_bytes;
+    use crate::util::temp::TempDir;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    fn write_pack(path: &Path, manifest: &str, extra: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        let options = SimpleFileOptions::default();
+        zip.start_file(MANIFEST_FILE, options).unwrap();
+        zip.write_all(manifest.as_bytes()).unwrap();
+        for (name, data) in extra {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    const MANIFEST: &str = r#"{
+        "minecraft": {
+            "version": "1.20.1",
+            "modLoaders": [{ "id": "forge-47.2.0", "primary": true }]
+        },
+        "manifestType": "minecraftModpack",
+        "manifestVersion": 1,
+        "name": "CF Pack",
+        "version": "1.0",
+        "files": [
+            { "projectID": 100, "fileID": 1000, "required": true },
+            { "projectID": 200, "fileID": 2000, "required": true },
+            { "projectID": 300, "fileID": 3000, "required": true },
+            { "projectID": 400, "fileID": 4000, "required": false }
+        ],
+        "overrides": "overrides"
+    }"#;
+
+    #[test]
+    fn test_parse_loader_id() {
+        let (loader, version) = parse_loader_id("forge-47.2.0").unwrap();
+        assert_eq!(loader, ModLoader::Forge);
+        assert_eq!(version, "47.2.0");
+        let (loader, version) = parse_loader_id("neoforge-20.4.80-beta").unwrap();
+        assert_eq!(loader, ModLoader::NeoForge);
+        assert_eq!(version, "20.4.80-beta");
+        assert_eq!(
+            parse_loader_id("fabric-0.15.3").unwrap().0,
+            ModLoader::Fabric
+        );
+        assert!(parse_loader_id("forge").is_err());
+        assert!(parse_loader_id("liteloader-1.0").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_curseforge() {
+        let dir = TempDir::new("cfpack-import");
+        let manager = InstanceManager::with_dir(&dir.join("instances"));
+        let jar = b"jei jar".to_vec();
+        let pack_zip = b"faithful".to_vec();
+
+        let server = MockServer::start_with(|base| {
+            let files = format!(
+                r#"{{ "data": [
+                    {{ "id": 1000, "modId": 100, "displayName": "JEI", "fileName": "jei.jar",
+                       "downloadUrl": "{base}/files/jei.jar",
+                       "hashes": [{{ "value": "{jar_sha1}", "algo": 1 }}] }},
+                    {{ "id": 2000, "modId": 200, "displayName": "Faithful", "fileName": "faithful.zip",
+                       "downloadUrl": "{base}/files/faithful.zip" }},
+                    {{ "id": 3000, "modId": 300, "displayName": "OptiFine", "fileName": "optifine.jar",
+                       "downloadUrl": null }}
+                ] }}"#,
+                jar_sha1 = sha1_bytes(&jar)
+            );
+            let mods = r#"{ "data": [
+                { "id": 100, "name": "JEI", "classId": 6 },
+                { "id": 200, "name": "Faithful", "classId": 12 },
+                { "id": 300, "name": "OptiFine", "classId": 6,
+                  "links": { "websiteUrl": "https://www.curseforge.com/minecraft/mc-mods/optifine" } }
+            ] }"#;
+            vec![
+                ("POST", "/v1/mods/files".to_string(), 200, files.into_bytes()),
+                ("POST", "/v1/mods".to_string(), 200, mods.as_bytes().to_vec()),
+                ("GET", "/files/jei.jar".to_string(), 200, jar.clone()),
+                ("GET", "/files/faithful.zip".to_string(), 200, pack_zip.clone()),
+            ]
+        })
+        .await;
+        let client = CurseForgeClient::with_base_url(&server.base_url, "key");
+
+        let pack = dir.join("pack.zip");
+        write_pack(
+            &pack,
+            MANIFEST,
+            &[
+                ("overrides/config/jei.toml", b"x"),
+                ("other/ignored.txt", b"y"),
+            ],
+        );
+
+        let report = import_curseforge(&manager, &pack, None, &client, &|_| {})
+            .await
+            .unwrap();
+        assert_eq!(report.instance_name, "CF Pack");
+        assert_eq!(report.downloaded, 2);
+        assert_eq!(report.overrides, 1);
+        assert_eq!(report.manual.len(), 1);
+        assert_eq!(report.manual[0].filename, "optifine.jar");
+        assert_eq!(
+            report.manual[0].page_url,
+            "https://www.curseforge.com/minecraft/mc-mods/optifine/files/3000"
+        );
+
+        let instance = manager.load("CF Pack").unwrap();
+        assert_eq!(instance.info.loader, ModLoader::Forge);
+        assert_eq!(instance.info.loader_version.as_deref(), Some("47.2.0"));
+
+        let game_dir = manager.get_game_dir(&instance.info.id);
+        assert_eq!(std::fs::read(game_dir.join("mods/jei.jar")).unwrap(), jar);
+        assert!(game_dir.join("resourcepacks/faithful.zip").exists());
+        assert!(game_dir.join("config/jei.toml").exists());
+        assert!(!game_dir.join("ignored.txt").exists());
+
+        // The optional file was never requested
+        let body = server.requests.lock().unwrap()[0].1.clone();
+        assert!(!body.contains("4000"), "{}", body);
+    }
+}
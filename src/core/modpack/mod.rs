@@ -0,0 +1,311 @@
+//! Modpack import and export
+//!
+//! Converts modpack archives (Modrinth `.mrpack`, CurseForge zips) and
+//! portable instance archives into instances and back.
+
+mod archive;
+mod curseforge;
+mod mrpack;
+
+pub use archive::{DEFAULT_ARCHIVE_CONTENT, export_archive, import_archive};
+pub use curseforge::import_curseforge;
+pub use mrpack::{export_mrpack, import_mrpack};
+
+use crate::core::instance::{ICON_FILE, InstanceManager};
+use crate::core::mods::{CurseForgeClient, FileHashes, verify_hashes};
+use anyhow::{Context, Result};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+use zip::ZipArchive;
+
+/// Result of importing a modpack
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    /// Name of the created instance
+    pub instance_name: String,
+    /// Files downloaded from the pack index
+    pub downloaded: usize,
+    /// Files copied from overrides
+    pub overrides: usize,
+    /// Files that must be downloaded by hand
+    pub manual: Vec<ManualDownload>,
+}
+
+/// A pack file whose author disallows third-party downloads
+#[derive(Debug, Clone)]
+pub struct ManualDownload {
+    /// Project name
+    pub name: String,
+    pub filename: String,
+    /// Page to download the file from
+    pub page_url: String,
+    /// Folder the file belongs in
+    pub dest_dir: PathBuf,
+}
+
+/// Export format, chosen explicitly or from the output extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Modrinth modpack
+    Mrpack,
+    /// Portable instance archive
+    Zip,
+}
+
+impl ExportFormat {
+    /// File extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Mrpack => "mrpack",
+            ExportFormat::Zip => "zip",
+        }
+    }
+
+    /// Format implied by an output path's extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.parse().ok()
+    }
+}
+
+impl std::str::FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "mrpack" => Ok(ExportFormat::Mrpack),
+            "zip" => Ok(ExportFormat::Zip),
+            _ => anyhow::bail!("Unknown export format: {}. Use mrpack or zip.", s),
+        }
+    }
+}
+
+/// Result of exporting an instance as a modpack
+#[derive(Debug, Default)]
+pub struct ExportReport {
+    /// Files referenced by download URL
+    pub indexed: usize,
+    /// Files stored in the archive
+    pub overrides: usize,
+}
+
+/// Game directory folders and files never included in exports
+pub const EXPORT_EXCLUDES: &[&str] = &[
+    "logs",
+    "crash-reports",
+    "screenshots",
+    "natives",
+    "saves",
+    "backups",
+    ".cache",
+    ".fabric",
+    ".mixin.out",
+    "usercache.json",
+    "usernamecache.json",
+];
+
+/// Import a modpack archive, detecting its format from the contents
+pub async fn import_pack(
+    manager: &InstanceManager,
+    pack: &Path,
+    name: Option<&str>,
+    on_progress: &(dyn Fn(&str) + Sync),
+) -> Result<ImportReport> {
+    let file = std::fs::File::open(pack).context("Failed to open modpack")?;
+    let archive = ZipArchive::new(file).context("Modpack is not a valid zip")?;
+    let has = |entry: &str| archive.file_names().any(|n| n == entry);
+
+    if has(archive::INSTANCE_FILE) {
+        let instance = import_archive(manager, pack, name, on_progress)?;
+        Ok(ImportReport {
+            instance_name: instance.info.name,
+            ..Default::default()
+        })
+    } else if has(mrpack::INDEX_FILE) {
+        import_mrpack(manager, pack, name, on_progress).await
+    } else if has(curseforge::MANIFEST_FILE) {
+        let client = CurseForgeClient::from_config()?;
+        import_curseforge(manager, pack, name, &client, on_progress).await
+    } else {
+        anyhow::bail!("Unrecognised modpack format: {}", pack.display())
+    }
+}
+
+/// Join a relative path from a pack onto a directory, rejecting escapes
+pub fn safe_join(root: &Path, relative: &str) -> Result<PathBuf> {
+    let path = Path::new(relative);
+    let is_safe = !relative.is_empty()
+        && !relative.contains('\\')
+        && path
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !is_safe {
+        anyhow::bail!("Unsafe path in pack: {}", relative);
+    }
+    Ok(root.join(path))
+}
+
+/// Extract entries under `prefix/` into `dest`, returning the number of files
+///
+/// Entries that would escape `dest` abort the extraction.
+pub fn extract_prefix<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    prefix: &str,
+    dest: &Path,
+) -> Result<usize> {
+    let prefix = format!("{}/", prefix.trim_end_matches('/'));
+    let mut count = 0;
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)?;
+        let Some(relative) = entry.name().strip_prefix(&prefix).map(str::to_string) else {
+            continue;
+        };
+        if relative.is_empty() {
+            continue;
+        }
+        if entry.enclosed_name().is_none() {
+            anyhow::bail!("Unsafe path in pack: {}", entry.name());
+        }
+
+        let target = safe_join(dest, relative.trim_end_matches('/'))?;
+        if entry.is_dir() {
+            std::fs::create_dir_all(&target)?;
+            continue;
+        }
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut out = std::fs::File::create(&target)
+            .with_context(|| format!("Failed to create {}", target.display()))?;
+        std::io::copy(&mut entry, &mut out)?;
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+/// Use the pack's root `icon.png` as the instance icon, if it has one
+///
+/// A broken icon is logged and otherwise ignored.
+pub fn apply_pack_icon<R: Read + std::io::Seek>(
+    manager: &InstanceManager,
+    id: &str,
+    archive: &mut ZipArchive<R>,
+) {
+    let Ok(mut entry) = archive.by_name(ICON_FILE) else {
+        return;
+    };
+    let mut bytes = Vec::new();
+    let result = entry
+        .read_to_end(&mut bytes)
+        .map_err(anyhow::Error::from)
+        .and_then(|_| manager.set_icon_image(id, &bytes));
+    if let Err(e) = result {
+        tracing::warn!("Failed to import pack icon: {}", e);
+    }
+}
+
+/// Download a file from the first working URL and verify its hashes
+pub async fn download_verified(
+    client: &reqwest::Client,
+    urls: &[String],
+    hashes: &FileHashes,
+    dest: &Path,
+) -> Result<()> {
+    let name = dest
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut last_error = None;
+
+    for url in urls {
+        let bytes = match fetch(client, url).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                tracing::warn!("Download of {} from {} failed: {}", name, url, e);
+                last_error = Some(e);
+                continue;
+            }
+        };
+        verify_hashes(&name, hashes, &bytes)?;
+
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(dest, &bytes).with_context(|| format!("Failed to write {}", name))?;
+        return Ok(());
+    }
+
+    match last_error {
+        Some(e) => Err(e.context(format!("Failed to download {}", name))),
+        None => anyhow::bail!("No download URL for {}", name),
+    }
+}
+
+async fn fetch(client: &reqwest::Client, url: &str) -> Result<Vec<u8>> {
+    let response = client.get(url).send().await?;
+    if !response.status().is_success() {
+        anyhow::bail!("HTTP {}", response.status());
+    }
+    Ok(response.bytes().await?.to_vec())
+}
+
+/// Files under the game directory that belong in an export, relative and sorted
+pub fn export_files(game_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    collect_files(game_dir, game_dir, &mut files)?;
+    files.sort();
+    Ok(files)
+}
+
+fn collect_files(root: &Path, dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let relative = path.strip_prefix(root)?.to_path_buf();
+
+        if dir == root
+            && EXPORT_EXCLUDES
+                .iter()
+                .any(|e| relative.as_os_str() == std::ffi::OsStr::new(e))
+        {
+            continue;
+        }
+
+        let file_type = entry.file_type()?;
+        if file_type.is_dir() {
+            collect_files(root, &path, files)?;
+        } else if file_type.is_file() {
+            files.push(relative);
+        }
+    }
+    Ok(())
+}
+
+/// Path inside an archive for a relative file path (always `/`-separated)
+pub fn archive_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_join() {
+        let root = Path::new("/game");
+        assert_eq!(
+            safe_join(root, "mods/sodium.jar").unwrap(),
+            Path::new("/game/mods/sodium.jar")
+        );
+        assert!(safe_join(root, "../escape.jar").is_err());
+        assert!(safe_join(root, "mods/../../escape.jar").is_err());
+        assert!(safe_join(root, "/etc/passwd").is_err());
+        assert!(safe_join(root, "mods\\..\\x").is_err());
+        assert!(safe_join(root, "").is_err());
+    }
+}
@@ -0,0 +1,533 @@
+//! Modrinth modpack format (.mrpack)
+//!
+//! A zip with a `modrinth.index.json` listing downloadable files, plus
+//! `overrides/` and `client-overrides/` folders copied into the game directory.
+
+use super::{
+    ExportReport, ImportReport, apply_pack_icon, archive_path, download_verified, export_files,
+    extract_prefix, safe_join,
+};
+use crate::core::instance::{InstanceManager, ModLoader};
+use crate::core::mods::{FileHashes, ModrinthClient};
+use crate::util::hash::{sha1_bytes, sha512_bytes};
+use anyhow::{Context, Result};
+use futures::stream::{self, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+use zip::write::SimpleFileOptions;
+
+/// Index file name inside the archive
+pub(super) const INDEX_FILE: &str = "modrinth.index.json";
+
+/// Concurrent downloads during import
+const CONCURRENT_DOWNLOADS: usize = 8;
+
+/// Folders whose files may be available on Modrinth
+const INDEXABLE_DIRS: &[&str] = &["mods", "resourcepacks", "shaderpacks"];
+
+/// `modrinth.index.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackIndex {
+    pub format_version: u32,
+    pub game: String,
+    pub version_id: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(default)]
+    pub files: Vec<MrpackFile>,
+    /// `minecraft` plus one loader (`fabric-loader`, `forge`, ...)
+    pub dependencies: BTreeMap<String, String>,
+}
+
+/// A downloadable file in the index
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MrpackFile {
+    /// Destination relative to the game directory
+    pub path: String,
+    pub hashes: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<MrpackEnv>,
+    pub downloads: Vec<String>,
+    #[serde(default)]
+    pub file_size: u64,
+}
+
+/// Client/server support for a file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MrpackEnv {
+    pub client: String,
+    pub server: String,
+}
+
+impl MrpackIndex {
+    /// Minecraft version, loader and loader version required by the pack
+    pub fn game_requirements(&self) -> Result<(String, ModLoader, Option<String>)> {
+        let mc_version = self
+            .dependencies
+            .get("minecraft")
+            .context("Pack does not specify a Minecraft version")?
+            .clone();
+
+        let loaders = [
+            ("fabric-loader", ModLoader::Fabric),
+            ("quilt-loader", ModLoader::Quilt),
+            ("neoforge", ModLoader::NeoForge),
+            ("forge", ModLoader::Forge),
+        ];
+        for (key, loader) in loaders {
+            if let Some(version) = self.dependencies.get(key) {
+                return Ok((mc_version, loader, Some(version.clone())));
+            }
+        }
+        Ok((mc_version, ModLoader::Vanilla, None))
+    }
+}
+
+impl MrpackFile {
+    /// Whether the client needs this file
+    fn is_for_client(&self) -> bool {
+        self.env
+            .as_ref()
+            .is_none_or(|env| env.client != "unsupported")
+    }
+
+    fn file_hashes(&self) -> FileHashes {
+        FileHashes {
+            sha1: self.hashes.get("sha1").cloned(),
+            sha512: self.hashes.get("sha512").cloned(),
+        }
+    }
+}
+
+/// Read and validate the index of a `.mrpack`
+pub fn read_index<R: Read + std::io::Seek>(archive: &mut ZipArchive<R>) -> Result<MrpackIndex> {
+    let mut contents = String::new();
+    archive
+        .by_name(INDEX_FILE)
+        .context("Not a Modrinth modpack (modrinth.index.json missing)")?
+        .read_to_string(&mut contents)?;
+
+    let index: MrpackIndex =
+        serde_json::from_str(&contents).context("Failed to parse modrinth.index.json")?;
+    if index.game != "minecraft" {
+        anyhow::bail!("Unsupported game in modpack: {}", index.game);
+    }
+    if index.format_version != 1 {
+        anyhow::bail!(
+            "Unsupported modpack format version: {}",
+            index.format_version
+        );
+    }
+    Ok(index)
+}
+
+/// Import a `.mrpack` as a new instance
+///
+/// The instance is named after the pack unless `name` is given; a numbered
+/// suffix is added on collision. The instance is removed again if any file
+/// fails to download or verify.
+pub async fn import_mrpack(
+    manager: &InstanceManager,
+    pack: &Path,
+    name: Option<&str>,
+    on_progress: &(dyn Fn(&str) + Sync),
+) -> Result<ImportReport> {
+    let file = std::fs::File::open(pack).context("Failed to open modpack")?;
+    let mut archive = ZipArchive::new(file).context("Modpack is not a valid zip")?;
+    let index = read_index(&mut archive)?;
+    let (mc_version, loader, loader_version) = index.game_requirements()?;
+
+    let name = manager.unique_name(name.unwrap_or(&index.name));
+
+    on_progress(&format!("Creating instance {}...", name));
+    let instance = manager.create(&name, &mc_version, loader, loader_version)?;
+    let game_dir = manager.get_game_dir(&instance.info.id);
+
+    let result = async {
+        // Validate every destination and hash before downloading anything
+        let files: Vec<(&MrpackFile, PathBuf)> = index
+            .files
+            .iter()
+            .filter(|f| f.is_for_client())
+            .map(|f| {
+                if !f.hashes.contains_key("sha1") && !f.hashes.contains_key("sha512") {
+                    anyhow::bail!("Modpack file {} has no sha1 or sha512 hash", f.path);
+                }
+                Ok((f, safe_join(&game_dir, &f.path)?))
+            })
+            .collect::<Result<_>>()?;
+        install_files(&mut archive, &files, &game_dir, on_progress).await
+    }
+    .await;
+    if result.is_ok() {
+        apply_pack_icon(manager, &instance.info.id, &mut archive);
+    }
+    match result {
+        Ok((downloaded, overrides)) => Ok(ImportReport {
+            instance_name: name,
+            downloaded,
+            overrides,
+            manual: Vec::new(),
+        }),
+        Err(e) => {
+            if let Err(cleanup) = manager.delete(&instance.info.id) {
+                tracing::warn!("Failed to remove partial instance {}: {}", name, cleanup);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Download indexed files and extract overrides into the game directory
+async fn install_files<R: Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    files: &[(&MrpackFile, PathBuf)],
+    game_dir: &Path,
+    on_progress: &(dyn Fn(&str) + Sync),
+) -> Result<(usize, usize)> {
+    let client = reqwest::Client::new();
+    let total = files.len();
+
+    let results: Vec<Result<()>> = stream::iter(files.iter().enumerate())
+        .map(|(i, (file, dest))| {
+            let client = &client;
+            async move {
+                on_progress(&format!("Downloading {}/{}: {}", i + 1, total, file.path));
+                download_verified(client, &file.downloads, &file.file_hashes(), dest).await
+            }
+        })
+        .buffer_unordered(CONCURRENT_DOWNLOADS)
+        .collect()
+        .await;
+    results.into_iter().collect::<Result<Vec<_>>>()?;
+
+    on_progress("Applying overrides...");
+    let mut overrides = extract_prefix(archive, "overrides", game_dir)?;
+    overrides += extract_prefix(archive, "client-overrides", game_dir)?;
+
+    Ok((total, overrides))
+}
+
+/// Export an instance as a `.mrpack`
+///
+/// Files Modrinth recognises by hash are referenced by URL; everything else
+/// is stored under `overrides/`.
+pub async fn export_mrpack(
+    manager: &InstanceManager,
+    name: &str,
+    output: &Path,
+    client: &ModrinthClient,
+    on_progress: &(dyn Fn(&str) + Sync),
+) -> Result<ExportReport> {
+    let instance = manager.load(name)?;
+    let game_dir = manager.get_game_dir(&instance.info.id);
+
+    let mut dependencies = BTreeMap::new();
+    dependencies.insert("minecraft".to_string(), instance.info.version.clone());
+    if let Some(key) = loader_key(&instance.info.loader) {
+        let version = instance.info.loader_version.clone().with_context(|| {
+            format!(
+                "Instance '{}' has no {} version set",
+                name, instance.info.loader
+            )
+        })?;
+        dependencies.insert(key.to_string(), version);
+    }
+
+    on_progress("Scanning files...");
+    let files = if game_dir.exists() {
+        export_files(&game_dir)?
+    } else {
+        Vec::new()
+    };
+
+    // Look up candidate files on Modrinth by SHA1
+    let mut by_sha1: HashMap<String, PathBuf> = HashMap::new();
+    for relative in files.iter().filter(|p| is_indexable(p)) {
+        let bytes = std::fs::read(game_dir.join(relative))?;
+        by_sha1.insert(sha1_bytes(&bytes), relative.clone());
+    }
+    on_progress("Looking up files on Modrinth...");
+    let hashes: Vec<String> = by_sha1.keys().cloned().collect();
+    let versions = client.versions_by_hashes(&hashes, "sha1").await?;
+
+    let mut indexed: BTreeMap<PathBuf, MrpackFile> = BTreeMap::new();
+    for (sha1, version) in versions {
+        let Some(relative) = by_sha1.get(&sha1) else {
+            continue;
+        };
+        let Some(remote) = version
+            .files
+            .iter()
+            .find(|f| f.hashes.get("sha1") == Some(&sha1))
+        else {
+            continue;
+        };
+        let bytes = std::fs::read(game_dir.join(relative))?;
+        indexed.insert(
+            relative.clone(),
+            MrpackFile {
+                path: archive_path(relative),
+                hashes: BTreeMap::from([
+                    ("sha1".to_string(), sha1.clone()),
+                    ("sha512".to_string(), sha512_bytes(&bytes)),
+                ]),
+                env: Some(MrpackEnv {
+                    client: "required".to_string(),
+                    server: "required".to_string(),
+                }),
+                downloads: vec![remote.url.clone()],
+                file_size: bytes.len() as u64,
+            },
+        );
+    }
+
+    let index = MrpackIndex {
+        format_version: 1,
+        game: "minecraft".to_string(),
+        version_id: "1.0.0".to_string(),
+        name: instance.info.name.clone(),
+        summary: None,
+        files: indexed.values().cloned().collect(),
+        dependencies,
+    };
+
+    on_progress("Writing modpack...");
+    let tmp = output.with_extension("mrpack.part");
+    let mut zip = zip::ZipWriter::new(std::fs::File::create(&tmp)?);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    zip.start_file(INDEX_FILE, options)?;
+    zip.write_all(serde_json::to_string_pretty(&index)?.as_bytes())?;
+
+    let mut overrides = 0;
+    for relative in files.iter().filter(|p| !indexed.contains_key(*p)) {
+        zip.start_file(format!("overrides/{}", archive_path(relative)), options)?;
+        zip.write_all(&std::fs::read(game_dir.join(relative))?)?;
+        overrides += 1;
+    }
+    zip.finish()?;
+    std::fs::rename(&tmp, output).context("Failed to write modpack")?;
+
+    Ok(ExportReport {
+        indexed: index.files.len(),
+        overrides,
+    })
+}
+
+/// Dependency key for a loader in the index
+fn loader_key(loader: &ModLoader) -> Option<&'static str> {
+    match loader {
+        ModLoader::Vanilla => None,
+        ModLoader::Fabric => Some("fabric-loader"),
+        ModLoader::Quilt => Some("quilt-loader"),
+        ModLoader::Forge => Some("forge"),
+        ModLoader::NeoForge => Some("neoforge"),
+    }
+}
+
+/// Whether a file could be a Modrinth download (enabled jars and packs)
+fn is_indexable(relative: &Path) -> bool {
+    let in_dir = relative
+        .components()
+        .next()
+        .is_some_and(|c| INDEXABLE_DIRS.iter().any(|d| c.as_os_str() == *d));
+    let ext = relative.extension().and_then(|e| e.to_str());
+    in_dir && matches!(ext, Some("jar") | Some("zip"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mods::mock::MockServer;
+    use crate::util::temp::TempDir;
+
+    fn write_pack(path: &Path, index: &str, extra: &[(&str, &[u8])]) {
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(path).unwrap());
+        let options = SimpleFileOptions::default();
+        zip.start_file(INDEX_FILE, options).unwrap();
+        zip.write_all(index.as_bytes()).unwrap();
+        for (name, data) in extra {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    fn index_json(base: &str, path: &str, jar: &[u8]) -> String {
+        format!(
+            r#"{{
+                "formatVersion": 1,
+                "game": "minecraft",
+                "versionId": "2.0",
+                "name": "Test Pack",
+                "files": [
+                    {{
+                        "path": "{path}",
+                        "hashes": {{ "sha1": "{sha1}", "sha512": "{sha512}" }},
+                        "env": {{ "client": "required", "server": "required" }},
+                        "downloads": ["{base}/files/sodium.jar"],
+                        "fileSize": {size}
+                    }},
+                    {{
+                        "path": "mods/server-only.jar",
+                        "hashes": {{ "sha1": "0" }},
+                        "env": {{ "client": "unsupported", "server": "required" }},
+                        "downloads": ["{base}/missing"],
+                        "fileSize": 1
+                    }}
+                ],
+                "dependencies": {{ "minecraft": "1.20.1", "fabric-loader": "0.15.3" }}
+            }}"#,
+            sha1 = sha1_bytes(jar),
+            sha512 = sha512_bytes(jar),
+            size = jar.len()
+        )
+    }
+
+    #[tokio::test]
+    async fn test_import_mrpack() {
+        let dir = TempDir::new("mrpack-import");
+        let manager = InstanceManager::with_dir(&dir.join("instances"));
+        let jar = b"sodium jar".to_vec();
+
+        let server = MockServer::start(vec![(
+            "GET",
+            "/files/sodium.jar".to_string(),
+            200,
+            jar.clone(),
+        )])
+        .await;
+        let pack = dir.join("pack.mrpack");
+        write_pack(
+            &pack,
+            &index_json(&server.base_url, "mods/sodium.jar", &jar),
+            &[
+                ("overrides/config/sodium.json", b"{}"),
+                ("overrides/options.txt", b"fov:70"),
+                ("client-overrides/options.txt", b"fov:90"),
+            ],
+        );
+
+        let report = import_mrpack(&manager, &pack, None, &|_| {}).await.unwrap();
+        assert_eq!(report.instance_name, "Test Pack");
+        assert_eq!(report.downloaded, 1);
+
+        let instance = manager.load("Test Pack").unwrap();
+        assert_eq!(instance.info.version, "1.20.1");
+        assert_eq!(instance.info.loader, ModLoader::Fabric);
+        assert_eq!(instance.info.loader_version.as_deref(), Some("0.15.3"));
+
+        let game_dir = manager.get_game_dir(&instance.info.id);
+        assert_eq!(
+            std::fs::read(game_dir.join("mods/sodium.jar")).unwrap(),
+            jar
+        );
+        assert!(!game_dir.join("mods/server-only.jar").exists());
+        assert!(game_dir.join("config/sodium.json").exists());
+        assert_eq!(
+            std::fs::read_to_string(game_dir.join("options.txt")).unwrap(),
+            "fov:90"
+        );
+
+        // A second import gets a distinct name
+        let report = import_mrpack(&manager, &pack, None, &|_| {}).await.unwrap();
+        assert_eq!(report.instance_name, "Test Pack (2)");
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_path_traversal() {
+        let dir = TempDir::new("mrpack-traversal");
+        let manager = InstanceManager::with_dir(&dir.join("instances"));
+        let pack = dir.join("evil.mrpack");
+        write_pack(
+            &pack,
+            &index_json("http://127.0.0.1:9", "../../evil.jar", b"x"),
+            &[],
+        );
+
+        let err = import_mrpack(&manager, &pack, None, &|_| {})
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Unsafe path"), "{}", err);
+        assert!(!manager.exists("Test Pack"));
+
+        // Files without a usable hash are rejected the same way
+        let mut index: serde_json::Value =
+            serde_json::from_str(&index_json("http://127.0.0.1:9", "mods/a.jar", b"x")).unwrap();
+        index["files"][0]["hashes"] = serde_json::json!({ "md5": "0" });
+        write_pack(&pack, &index.to_string(), &[]);
+
+        let err = import_mrpack(&manager, &pack, None, &|_| {})
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("no sha1 or sha512"), "{}", err);
+        assert!(!manager.exists("Test Pack"));
+    }
+
+    #[tokio::test]
+    async fn test_export_mrpack() {
+        let dir = TempDir::new("mrpack-export");
+        let manager = InstanceManager::with_dir(&dir.join("instances"));
+        let instance = manager
+            .create("Pack", "1.20.1", ModLoader::Fabric, Some("0.15.3".into()))
+            .unwrap();
+        let game_dir = manager.get_game_dir(&instance.info.id);
+        std::fs::create_dir_all(game_dir.join("mods")).unwrap();
+        std::fs::create_dir_all(game_dir.join("config")).unwrap();
+        std::fs::create_dir_all(game_dir.join("logs")).unwrap();
+        std::fs::write(game_dir.join("mods/sodium.jar"), b"sodium").unwrap();
+        std::fs::write(game_dir.join("mods/custom.jar"), b"custom").unwrap();
+        std::fs::write(game_dir.join("config/sodium.json"), b"{}").unwrap();
+        std::fs::write(game_dir.join("logs/latest.log"), b"log").unwrap();
+
+        let sha1 = sha1_bytes(b"sodium");
+        let body = format!(
+            r#"{{ "{sha1}": {{
+                "id": "v1",
+                "project_id": "sodium",
+                "version_number": "0.5.8",
+                "files": [{{
+                    "hashes": {{ "sha1": "{sha1}" }},
+                    "url": "https://cdn.modrinth.com/data/AANobbMI/versions/v1/sodium.jar",
+                    "filename": "sodium.jar",
+                    "primary": true
+                }}]
+            }} }}"#
+        );
+        let server = MockServer::start(vec![(
+            "POST",
+            "/version_files".to_string(),
+            200,
+            body.into_bytes(),
+        )])
+        .await;
+
+        let output = dir.join("Pack.mrpack");
+        let report = export_mrpack(
+            &manager,
+            "Pack",
+            &output,
+            &ModrinthClient::with_base_url(&server.base_url),
+            &|_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(report.indexed, 1);
+        assert_eq!(report.overrides, 2);
+
+        let mut archive = ZipArchive::new(std::fs::File::open(&output).unwrap()).unwrap();
+        let index = read_index(&mut archive).unwrap();
+        assert_eq!(index.files[0].path, "mods/sodium.jar");
+        assert_eq!(index.dependencies["fabric-loader"], "0.15.3");
+        assert!(archive.by_name("overrides/mods/custom.jar").is_ok());
+        assert!(archive.by_name("overrides/config/sodium.json").is_ok());
+        assert!(archive.by_name("overrides/logs/latest.log").is_err());
+    }
+}
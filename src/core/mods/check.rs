@@ -0,0 +1,664 @@
+//! Mod dependency checking
+//!
+//! Builds the mod graph of an instance from declared metadata and reports
+//! missing dependencies, version mismatches, incompatibilities, duplicate
+//! mod IDs and jars built for another loader.
+
+use super::{DependencyKind, ModInfo, ModManager};
+use crate::core::instance::{Instance, InstanceManager, ModLoader};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// What the game provides besides the mods themselves
+#[derive(Debug, Clone)]
+pub struct ModEnvironment {
+    pub mc_version: String,
+    pub loader: ModLoader,
+    /// Loader version, if known
+    pub loader_version: Option<String>,
+}
+
+/// A problem found in an instance's mods
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ModIssue {
+    /// A required mod is not installed
+    MissingDependency {
+        mod_name: String,
+        dependency: String,
+        version_range: String,
+    },
+    /// A required mod is installed in a version outside the accepted range
+    VersionMismatch {
+        mod_name: String,
+        dependency: String,
+        version_range: String,
+        found: String,
+    },
+    /// Two installed mods declare they don't work together
+    Incompatible {
+        mod_name: String,
+        other: String,
+        /// `breaks` (fatal) rather than `conflicts` (known issues)
+        breaks: bool,
+    },
+    /// Several jars provide the same mod ID
+    DuplicateId { mod_id: String, files: Vec<String> },
+    /// A jar is built for a different loader
+    WrongLoader {
+        filename: String,
+        mod_loader: ModLoader,
+        instance_loader: ModLoader,
+    },
+}
+
+impl ModIssue {
+    /// Whether the game is expected to fail to start
+    pub fn is_fatal(&self) -> bool {
+        !matches!(self, ModIssue::Incompatible { breaks: false, .. })
+    }
+}
+
+impl std::fmt::Display for ModIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModIssue::MissingDependency {
+                mod_name,
+                dependency,
+                version_range,
+            } => {
+                if version_range == "*" {
+                    write!(
+                        f,
+                        "{} requires {}, which is not installed",
+                        mod_name, dependency
+                    )
+                } else {
+                    write!(
+                        f,
+                        "{} requires {} {}, which is not installed",
+                        mod_name, dependency, version_range
+                    )
+                }
+            }
+            ModIssue::VersionMismatch {
+                mod_name,
+                dependency,
+                version_range,
+                found,
+            } => write!(
+                f,
+                "{} requires {} {}, but {} is installed",
+                mod_name, dependency, version_range, found
+            ),
+            ModIssue::Incompatible {
+                mod_name,
+                other,
+                breaks,
+            } => {
+                if *breaks {
+                    write!(f, "{} is incompatible with {}", mod_name, other)
+                } else {
+                    write!(f, "{} has known issues with {}", mod_name, other)
+                }
+            }
+            ModIssue::DuplicateId { mod_id, files } => write!(
+                f,
+                "Mod '{}' is installed more than once: {}",
+                mod_id,
+                files.join(", ")
+            ),
+            ModIssue::WrongLoader {
+                filename,
+                mod_loader,
+                instance_loader,
+            } => write!(
+                f,
+                "{} is a {} mod, but this instance uses {}",
+                filename, mod_loader, instance_loader
+            ),
+        }
+    }
+}
+
+impl ModEnvironment {
+    /// Environment of an instance
+    pub fn for_instance(instance: &Instance) -> Self {
+        Self {
+            mc_version: instance.info.version.clone(),
+            loader: instance.info.loader.clone(),
+            loader_version: instance.info.loader_version.clone(),
+        }
+    }
+}
+
+/// Check the mods folder of an instance
+pub fn check_instance(instance: &Instance) -> Vec<ModIssue> {
+    let mods_dir = InstanceManager::new()
+        .get_game_dir(&instance.info.id)
+        .join("mods");
+    match ModManager::new(&mods_dir).list_mods() {
+        Ok(mods) => check_mods(&mods, &ModEnvironment::for_instance(instance)),
+        Err(e) => {
+            tracing::warn!("Failed to list mods for checking: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Check the enabled mods of an instance
+pub fn check_mods(mods: &[ModInfo], env: &ModEnvironment) -> Vec<ModIssue> {
+    let mut issues = Vec::new();
+    let enabled: Vec<&ModInfo> = mods
+        .iter()
+        .filter(|m| m.enabled && !m.mod_id.is_empty())
+        .collect();
+
+    // Wrong loader; such jars won't be loaded, so leave them out of the graph
+    let mut loadable = Vec::new();
+    for m in enabled {
+        if let Some(ref mod_loader) = m.loader
+            && !loader_accepts(&env.loader, mod_loader)
+        {
+            issues.push(ModIssue::WrongLoader {
+                filename: m.filename.clone(),
+                mod_loader: mod_loader.clone(),
+                instance_loader: env.loader.clone(),
+            });
+        } else {
+            loadable.push(m);
+        }
+    }
+
+    // Duplicate IDs
+    let mut by_id: HashMap<String, Vec<&ModInfo>> = HashMap::new();
+    for m in &loadable {
+        by_id.entry(m.mod_id.to_lowercase()).or_default().push(m);
+    }
+    let mut duplicates: Vec<_> = by_id.iter().filter(|(_, jars)| jars.len() > 1).collect();
+    duplicates.sort_by(|a, b| a.0.cmp(b.0));
+    for (mod_id, jars) in duplicates {
+        let mut files: Vec<String> = jars.iter().map(|m| m.filename.clone()).collect();
+        files.sort();
+        issues.push(ModIssue::DuplicateId {
+            mod_id: mod_id.clone(),
+            files,
+        });
+    }
+
+    // Everything available to satisfy dependencies: mod ID -> version
+    let mut provided: HashMap<String, Option<String>> = builtin_provides(env);
+    for m in &loadable {
+        for (mod_id, version) in &m.provides {
+            provided
+                .entry(mod_id.to_lowercase())
+                .or_insert_with(|| Some(version.clone()));
+        }
+    }
+    for m in &loadable {
+        provided.insert(m.mod_id.to_lowercase(), Some(m.version.clone()));
+    }
+
+    // Declared relationships
+    for m in &loadable {
+        for dep in &m.dependencies {
+            let dep_id = dep.mod_id.to_lowercase();
+            if dep_id == "java" {
+                continue;
+            }
+
+            let installed = provided.get(&dep_id);
+            match dep.kind {
+                DependencyKind::Required => match installed {
+                    None => issues.push(ModIssue::MissingDependency {
+                        mod_name: m.name.clone(),
+                        dependency: dep.mod_id.clone(),
+                        version_range: dep.version_range.clone(),
+                    }),
+                    Some(Some(version)) if !version_matches(version, &dep.version_range) => issues
+                        .push(ModIssue::VersionMismatch {
+                            mod_name: m.name.clone(),
+                            dependency: dep.mod_id.clone(),
+                            version_range: dep.version_range.clone(),
+                            found: version.clone(),
+                        }),
+                    _ => {}
+                },
+                DependencyKind::Optional => {}
+                DependencyKind::Breaks | DependencyKind::Conflicts => {
+                    let conflicting = match installed {
+                        Some(Some(version)) => version_matches(version, &dep.version_range),
+                        Some(None) => true,
+                        None => false,
+                    };
+                    if conflicting {
+                        issues.push(ModIssue::Incompatible {
+                            mod_name: m.name.clone(),
+                            other: dep.mod_id.clone(),
+                            breaks: dep.kind == DependencyKind::Breaks,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    issues
+}
+
+/// Mod IDs provided by the game and loader
+fn builtin_provides(env: &ModEnvironment) -> HashMap<String, Option<String>> {
+    let mut provided = HashMap::new();
+    provided.insert("minecraft".to_string(), Some(env.mc_version.clone()));
+
+    let loader_ids: &[&str] = match env.loader {
+        ModLoader::Vanilla => &[],
+        ModLoader::Fabric => &["fabricloader"],
+        // Quilt also loads Fabric mods
+        ModLoader::Quilt => &["quilt_loader", "fabricloader"],
+        ModLoader::Forge => &["forge", "javafml"],
+        ModLoader::NeoForge => &["neoforge", "javafml"],
+    };
+    for id in loader_ids {
+        provided.insert(id.to_string(), env.loader_version.clone());
+    }
+    provided
+}
+
+/// Whether an instance loader can load a mod built for `mod_loader`
+fn loader_accepts(instance_loader: &ModLoader, mod_loader: &ModLoader) -> bool {
+    match instance_loader {
+        ModLoader::Vanilla => false,
+        ModLoader::Quilt => matches!(mod_loader, ModLoader::Quilt | ModLoader::Fabric),
+        ModLoader::NeoForge => matches!(mod_loader, ModLoader::NeoForge | ModLoader::Forge),
+        loader => loader == mod_loader,
+    }
+}
+
+/// Check a version against a range in Fabric or Maven syntax
+///
+/// Versions that can't be compared (snapshots, odd formats) are accepted
+/// rather than reported.
+pub fn version_matches(version: &str, range: &str) -> bool {
+    let range = range.trim();
+    if range.is_empty() || range == "*" {
+        return true;
+    }
+    if !is_comparable(version) {
+        return true;
+    }
+    if range.starts_with('[') || range.starts_with('(') {
+        maven_range_matches(version, range)
+    } else {
+        range
+            .split("||")
+            .any(|alternative| fabric_predicate_matches(version, alternative))
+    }
+}
+
+/// Fabric/semver predicate: space-separated comparisons that must all hold
+fn fabric_predicate_matches(version: &str, predicate: &str) -> bool {
+    predicate.split_whitespace().all(|term| {
+        let (op, target) = split_operator(term);
+        if target == "*" || target.is_empty() {
+            return true;
+        }
+        if !is_comparable(target) {
+            return true;
+        }
+
+        // x-ranges like 1.20.x match the prefix
+        if let Some(prefix) = target
+            .strip_suffix(".x")
+            .or_else(|| target.strip_suffix(".X"))
+            .or_else(|| target.strip_suffix(".*"))
+        {
+            return same_prefix(version, prefix);
+        }
+
+        let ord = compare_versions(version, target);
+        match op {
+            ">=" => ord != Ordering::Less,
+            "<=" => ord != Ordering::Greater,
+            ">" => ord == Ordering::Greater,
+            "<" => ord == Ordering::Less,
+            "~" => {
+                // Same major.minor, at least target
+                ord != Ordering::Less && same_prefix(version, &leading(target, 2))
+            }
+            "^" => {
+                // Same major (same minor for 0.x), at least target
+                let depth = if target.starts_with("0.") { 2 } else { 1 };
+                ord != Ordering::Less && same_prefix(version, &leading(target, depth))
+            }
+            _ => ord == Ordering::Equal,
+        }
+    })
+}
+
+/// Maven version range, e.g. `[1.20,1.21)` or `[47,)`; comma-separated sets are OR'ed
+fn maven_range_matches(version: &str, range: &str) -> bool {
+    let mut rest = range;
+    while let Some(start) = rest.find(['[', '(']) {
+        let Some(end) = rest[start..].find([']', ')']).map(|e| start + e) else {
+            return true;
+        };
+        let inclusive_low = &rest[start..=start] == "[";
+        let inclusive_high = &rest[end..=end] == "]";
+        let body = &rest[start + 1..end];
+
+        let matched = match body.split_once(',') {
+            Some((low, high)) => {
+                let low = low.trim();
+                let high = high.trim();
+                let above = low.is_empty()
+                    || match compare_versions(version, low) {
+                        Ordering::Greater => true,
+                        Ordering::Equal => inclusive_low,
+                        Ordering::Less => false,
+                    };
+                let below = high.is_empty()
+                    || match compare_versions(version, high) {
+                        Ordering::Less => true,
+                        Ordering::Equal => inclusive_high,
+                        Ordering::Greater => false,
+                    };
+                above && below
+            }
+            // [1.0] means exactly 1.0
+            None => compare_versions(version, body.trim()) == Ordering::Equal,
+        };
+        if matched {
+            return true;
+        }
+        rest = &rest[end + 1..];
+    }
+    false
+}
+
+fn split_operator(term: &str) -> (&str, &str) {
+    for op in [">=", "<=", ">", "<", "=", "~", "^"] {
+        if let Some(target) = term.strip_prefix(op) {
+            return (op, target.trim());
+        }
+    }
+    ("=", term)
+}
+
+/// Whether a version starts with a number we can compare
+fn is_comparable(version: &str) -> bool {
+    version.trim().starts_with(|c: char| c.is_ascii_digit())
+}
+
+/// First `depth` numeric components of a version, joined with dots
+fn leading(version: &str, depth: usize) -> String {
+    core_parts(version)
+        .into_iter()
+        .take(depth)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Whether `version` starts with the components of `prefix`
+fn same_prefix(version: &str, prefix: &str) -> bool {
+    let version = core_parts(version);
+    let prefix = core_parts(prefix);
+    prefix.len() <= version.len()
+        && prefix
+            .iter()
+            .zip(&version)
+            .all(|(a, b)| compare_part(a, b) == Ordering::Equal)
+}
+
+/// Dot-separated components before any pre-release or build suffix
+fn core_parts(version: &str) -> Vec<&str> {
+    let version = version.split('+').next().unwrap_or(version);
+    let core = version.split('-').next().unwrap_or(version);
+    core.split('.').filter(|p| !p.is_empty()).collect()
+}
+
+fn compare_part(a: &str, b: &str) -> Ordering {
+    match (a.parse::<u64>(), b.parse::<u64>()) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+/// Compare two versions, semver-style
+///
+/// Missing components count as zero, build metadata (`+...`) is ignored and
+/// a pre-release (`-beta.1`) sorts before its release.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a_parts = core_parts(a);
+    let b_parts = core_parts(b);
+    for i in 0..a_parts.len().max(b_parts.len()) {
+        let ord = compare_part(
+            a_parts.get(i).copied().unwrap_or("0"),
+            b_parts.get(i).copied().unwrap_or("0"),
+        );
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+
+    let pre = |v: &str| -> Option<String> {
+        let v = v.split('+').next().unwrap_or(v);
+        v.split_once('-').map(|(_, pre)| pre.to_string())
+    };
+    match (pre(a), pre(b)) {
+        (None, None) => Ordering::Equal,
+        (None, Some(_)) => Ordering::Greater,
+        (Some(_), None) => Ordering::Less,
+        (Some(a), Some(b)) => a.cmp(&b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::temp::TempDir;
+    use std::io::Write;
+    use std::path::Path;
+
+    /// Write a fixture jar containing a single metadata file
+    fn write_jar(dir: &Path, filename: &str, entry: &str, contents: &str) {
+        let file = std::fs::File::create(dir.join(filename)).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        zip.start_file(entry, zip::write::SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(contents.as_bytes()).unwrap();
+        zip.finish().unwrap();
+    }
+
+    fn fabric_jar(dir: &Path, filename: &str, id: &str, version: &str, extra: &str) {
+        let json = format!(
+            r#"{{ "schemaVersion": 1, "id": "{}", "version": "{}", "name": "{}" {} }}"#,
+            id, version, id, extra
+        );
+        write_jar(dir, filename, "fabric.mod.json", &json);
+    }
+
+    fn fixture_dir(name: &str) -> TempDir {
+        TempDir::new(&format!("modcheck-{}", name))
+    }
+
+    fn fabric_env() -> ModEnvironment {
+        ModEnvironment {
+            mc_version: "1.20.4".to_string(),
+            loader: ModLoader::Fabric,
+            loader_version: Some("0.15.7".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_check_fixture_jars() {
+        let dir = fixture_dir("fabric");
+        fabric_jar(
+            &dir,
+            "sodium.jar",
+            "sodium",
+            "0.5.8+mc1.20.4",
+            r#", "depends": { "minecraft": "1.20.x", "fabricloader": ">=0.12.0", "fabric-api": "*" }"#,
+        );
+        fabric_jar(
+            &dir,
+            "iris.jar",
+            "iris",
+            "1.6.17",
+            r#", "depends": { "sodium": ">=0.6.0" }, "breaks": { "optifabric": "*" }"#,
+        );
+        fabric_jar(&dir, "optifabric.jar", "optifabric", "1.14.3", "");
+        fabric_jar(&dir, "lithium-old.jar", "lithium", "0.11.1", "");
+        fabric_jar(&dir, "lithium.jar", "lithium", "0.11.2", "");
+        write_jar(
+            &dir,
+            "jei.jar",
+            "META-INF/mods.toml",
+            "modLoader = \"javafml\"\n[[mods]]\nmodId = \"jei\"\nversion = \"15.2.0\"\n",
+        );
+        // Disabled jars are not part of the graph
+        fabric_jar(&dir, "modmenu.jar.disabled", "modmenu", "9.0.0", "");
+
+        let mods = ModManager::new(&dir).list_mods().unwrap();
+        let issues = check_mods(&mods, &fabric_env());
+
+        assert!(issues.contains(&ModIssue::MissingDependency {
+            mod_name: "sodium".to_string(),
+            dependency: "fabric-api".to_string(),
+            version_range: "*".to_string(),
+        }));
+        assert!(issues.contains(&ModIssue::VersionMismatch {
+            mod_name: "iris".to_string(),
+            dependency: "sodium".to_string(),
+            version_range: ">=0.6.0".to_string(),
+            found: "0.5.8+mc1.20.4".to_string(),
+        }));
+        assert!(issues.contains(&ModIssue::Incompatible {
+            mod_name: "iris".to_string(),
+            other: "optifabric".to_string(),
+            breaks: true,
+        }));
+        assert!(issues.contains(&ModIssue::DuplicateId {
+            mod_id: "lithium".to_string(),
+            files: vec!["lithium-old.jar".to_string(), "lithium.jar".to_string()],
+        }));
+        assert!(issues.contains(&ModIssue::WrongLoader {
+            filename: "jei.jar".to_string(),
+            mod_loader: ModLoader::Forge,
+            instance_loader: ModLoader::Fabric,
+        }));
+        assert_eq!(issues.len(), 5, "{:#?}", issues);
+    }
+
+    #[test]
+    fn test_check_clean_forge_instance() {
+        let dir = fixture_dir("forge");
+        write_jar(
+            &dir,
+            "jei.jar",
+            "META-INF/mods.toml",
+            r#"
+modLoader = "javafml"
+[[mods]]
+modId = "jei"
+version = "15.2.0"
+[[dependencies.jei]]
+modId = "forge"
+mandatory = true
+versionRange = "[47,)"
+[[dependencies.jei]]
+modId = "minecraft"
+mandatory = true
+versionRange = "[1.20.1,1.20.2)"
+"#,
+        );
+
+        let mods = ModManager::new(&dir).list_mods().unwrap();
+        let env = ModEnvironment {
+            mc_version: "1.20.1".to_string(),
+            loader: ModLoader::Forge,
+            loader_version: Some("47.2.0".to_string()),
+        };
+        let issues = check_mods(&mods, &env);
+
+        assert!(issues.is_empty(), "{:#?}", issues);
+    }
+
+    #[test]
+    fn test_check_nested_and_provided_mods() {
+        let dir = fixture_dir("nested");
+
+        // fabric-api bundles its modules as nested jars
+        let mut module = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        module
+            .start_file("fabric.mod.json", zip::write::SimpleFileOptions::default())
+            .unwrap();
+        module
+            .write_all(
+                br#"{ "schemaVersion": 1, "id": "fabric-resource-loader-v0", "version": "0.11.10" }"#,
+            )
+            .unwrap();
+        let module = module.finish().unwrap().into_inner();
+
+        let file = std::fs::File::create(dir.join("fabric-api.jar")).unwrap();
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("fabric.mod.json", options).unwrap();
+        zip.write_all(
+            br#"{
+                "schemaVersion": 1, "id": "fabric-api", "version": "0.92.0",
+                "provides": ["fabric"],
+                "jars": [{ "file": "META-INF/jars/fabric-resource-loader-v0.jar" }]
+            }"#,
+        )
+        .unwrap();
+        zip.start_file("META-INF/jars/fabric-resource-loader-v0.jar", options)
+            .unwrap();
+        zip.write_all(&module).unwrap();
+        zip.finish().unwrap();
+
+        fabric_jar(
+            &dir,
+            "mod.jar",
+            "some-mod",
+            "1.0.0",
+            r#", "depends": { "fabric-resource-loader-v0": ">=0.11", "fabric": "*" }"#,
+        );
+
+        let mods = ModManager::new(&dir).list_mods().unwrap();
+        let issues = check_mods(&mods, &fabric_env());
+
+        assert!(issues.is_empty(), "{:#?}", issues);
+    }
+
+    #[test]
+    fn test_version_matches_fabric() {
+        assert!(version_matches("1.20.4", "*"));
+        assert!(version_matches("1.20.4", ">=1.20"));
+        assert!(!version_matches("1.19.2", ">=1.20"));
+        assert!(version_matches("1.20.4", ">=1.20 <1.21"));
+        assert!(version_matches("1.20.4", "1.20.x"));
+        assert!(!version_matches("1.21", "1.20.x"));
+        assert!(version_matches("1.20.4", "~1.20.2"));
+        assert!(!version_matches("1.21.0", "~1.20.2"));
+        assert!(version_matches("2.5.0", "^2.1.0"));
+        assert!(!version_matches("3.0.0", "^2.1.0"));
+        assert!(version_matches("1.20.3", "1.20.3 || 1.20.4"));
+        assert!(!version_matches("1.20.2", "1.20.3 || 1.20.4"));
+        assert!(version_matches("0.5.8+mc1.20.4", ">=0.5.0"));
+        assert!(!version_matches("1.0.0-beta.1", ">=1.0.0"));
+        // Snapshots can't be compared; don't report them
+        assert!(version_matches("24w14a", ">=1.20.5"));
+    }
+
+    #[test]
+    fn test_version_matches_maven() {
+        assert!(version_matches("47.2.0", "[47,)"));
+        assert!(!version_matches("46.0.1", "[47,)"));
+        assert!(version_matches("1.20.1", "[1.20.1,1.20.2)"));
+        assert!(!version_matches("1.20.2", "[1.20.1,1.20.2)"));
+        assert!(version_matches("1.20.2", "[1.20.1,1.20.2]"));
+        assert!(version_matches("1.0", "(,1.0]"));
+        assert!(!version_matches("1.5", "[1.0]"));
+        assert!(version_matches("3.5", "[1.0,2.0),[3.0,4.0)"));
+    }
+}
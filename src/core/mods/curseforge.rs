@@ -0,0 +1,578 @@
+//! CurseForge API client
+//!
+//! Searches and installs mods through the CurseForge Core API and identifies
+//! jars by their murmur2 fingerprint. Requires an API key.
+
+use super::provider::{FileDependency, FileHashes, ModFile, ModProvider, ProjectHit, SearchQuery};
+use crate::core::instance::ModLoader;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// CurseForge API base URL
+const CURSEFORGE_API: &str = "https://api.curseforge.com";
+
+/// Minecraft game ID
+const MINECRAFT_GAME_ID: u32 = 432;
+
+/// "Mods" class ID within Minecraft
+const MODS_CLASS_ID: u32 = 6;
+
+/// Dependency relation type for required dependencies
+const RELATION_REQUIRED: u32 = 3;
+
+/// Hash algorithm ID for SHA1
+const HASH_ALGO_SHA1: u32 = 1;
+
+/// CurseForge API client
+pub struct CurseForgeClient {
+    client: reqwest::Client,
+    base_url: String,
+    api_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct Response<T> {
+    data: T,
+}
+
+/// A CurseForge project
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CfMod {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub slug: String,
+    #[serde(default)]
+    pub summary: String,
+    /// Project class (mods, resource packs, shaders, ...)
+    #[serde(default)]
+    pub class_id: Option<u32>,
+    #[serde(default)]
+    pub download_count: f64,
+    #[serde(default)]
+    pub authors: Vec<CfAuthor>,
+    pub logo: Option<CfLogo>,
+    #[serde(default)]
+    pub links: CfLinks,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CfAuthor {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CfLogo {
+    pub thumbnail_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CfLinks {
+    pub website_url: Option<String>,
+}
+
+/// A CurseForge file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CfFile {
+    pub id: u64,
+    pub mod_id: u64,
+    pub display_name: String,
+    pub file_name: String,
+    /// `None` when the author disallows third-party downloads
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub file_length: u64,
+    #[serde(default)]
+    pub file_date: String,
+    /// murmur2 fingerprint of the file
+    #[serde(default)]
+    pub file_fingerprint: u64,
+    #[serde(default)]
+    pub hashes: Vec<CfHash>,
+    #[serde(default)]
+    pub dependencies: Vec<CfDependency>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct CfHash {
+    pub value: String,
+    pub algo: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CfDependency {
+    pub mod_id: u64,
+    pub relation_type: u32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FingerprintMatches {
+    #[serde(default)]
+    exact_matches: Vec<FingerprintMatch>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FingerprintMatch {
+    file: CfFile,
+}
+
+impl CfFile {
+    /// Published hashes (CurseForge only offers SHA1 and MD5)
+    pub fn file_hashes(&self) -> FileHashes {
+        let sha1 = self
+            .hashes
+            .iter()
+            .find(|h| h.algo == HASH_ALGO_SHA1)
+            .map(|h| h.value.clone());
+        FileHashes { sha1, sha512: None }
+    }
+
+    /// Direct download URL, if third-party downloads are allowed
+    pub fn download_url(&self) -> Option<&str> {
+        self.download_url.as_deref().filter(|u| !u.is_empty())
+    }
+
+    /// Convert to the provider-neutral file
+    fn into_mod_file(self, page_url: Option<String>) -> ModFile {
+        let hashes = self.file_hashes();
+
+        ModFile {
+            project_id: self.mod_id.to_string(),
+            version_id: self.id.to_string(),
+            version_number: self.display_name,
+            filename: self.file_name,
+            url: self.download_url.filter(|u| !u.is_empty()),
+            page_url,
+            size: self.file_length,
+            hashes,
+            dependencies: self
+                .dependencies
+                .into_iter()
+                .filter(|d| d.relation_type == RELATION_REQUIRED)
+                .map(|d| FileDependency {
+                    project_id: d.mod_id.to_string(),
+                    version_id: None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl CurseForgeClient {
+    /// Create a client for the public CurseForge API
+    pub fn new(api_key: &str) -> Self {
+        Self::with_base_url(CURSEFORGE_API, api_key)
+    }
+
+    /// Create a client for another API endpoint (e.g. a local mock)
+    pub fn with_base_url(base_url: &str, api_key: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            api_key: api_key.to_string(),
+        }
+    }
+
+    /// Create a client using the API key from the launcher config
+    pub fn from_config() -> Result<Self> {
+        let config = crate::config::load().unwrap_or_default();
+        if config.mods.curseforge_api_key.is_empty() {
+            anyhow::bail!(
+                "CurseForge API key is not set. Add `curseforge_api_key` under [mods] in config.toml."
+            );
+        }
+        Ok(Self::new(&config.mods.curseforge_api_key))
+    }
+
+    /// Send a request and parse the `data` field of the JSON response
+    async fn send<T: DeserializeOwned>(&self, request: reqwest::RequestBuilder) -> Result<T> {
+        let response = request
+            .header("x-api-key", &self.api_key)
+            .header("Accept", "application/json")
+            .send()
+            .await
+            .context("Failed to reach CurseForge")?;
+
+        match response.status().as_u16() {
+            200..=299 => {}
+            401 | 403 => anyhow::bail!("CurseForge rejected the API key"),
+            status => anyhow::bail!("CurseForge request failed: HTTP {}", status),
+        }
+
+        let body: Response<T> = response
+            .json()
+            .await
+            .context("Failed to parse CurseForge response")?;
+        Ok(body.data)
+    }
+
+    fn get(&self, path: &str) -> reqwest::RequestBuilder {
+        self.client.get(format!("{}{}", self.base_url, path))
+    }
+
+    fn post(&self, path: &str) -> reqwest::RequestBuilder {
+        self.client.post(format!("{}{}", self.base_url, path))
+    }
+
+    /// Look up a mod by numeric ID or slug
+    async fn resolve_mod(&self, project: &str) -> Result<CfMod> {
+        if project.parse::<u64>().is_ok() {
+            return self.send(self.get(&format!("/v1/mods/{}", project))).await;
+        }
+
+        let mods: Vec<CfMod> = self
+            .send(self.get("/v1/mods/search").query(&[
+                ("gameId", MINECRAFT_GAME_ID.to_string()),
+                ("classId", MODS_CLASS_ID.to_string()),
+                ("slug", project.to_string()),
+            ]))
+            .await?;
+        mods.into_iter()
+            .next()
+            .with_context(|| format!("CurseForge project '{}' not found", project))
+    }
+
+    /// Fetch several files by ID in one request
+    pub async fn files(&self, file_ids: &[u64]) -> Result<Vec<CfFile>> {
+        if file_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.send(
+            self.post("/v1/mods/files")
+                .json(&serde_json::json!({ "fileIds": file_ids })),
+        )
+        .await
+    }
+
+    /// Fetch several projects by ID in one request
+    pub async fn mods(&self, mod_ids: &[u64]) -> Result<Vec<CfMod>> {
+        if mod_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        self.send(
+            self.post("/v1/mods")
+                .json(&serde_json::json!({ "modIds": mod_ids })),
+        )
+        .await
+    }
+
+    /// Page to download a file by hand, when direct downloads are disallowed
+    async fn manual_page_url(&self, file: &CfFile) -> Option<String> {
+        if file.download_url().is_some() {
+            return None;
+        }
+        let website = match self.resolve_mod(&file.mod_id.to_string()).await {
+            Ok(m) => m.links.website_url,
+            Err(e) => {
+                tracing::warn!("Failed to look up CurseForge mod {}: {}", file.mod_id, e);
+                None
+            }
+        };
+        Some(file_page_url(website.as_deref(), file))
+    }
+
+    async fn to_mod_file(&self, file: CfFile) -> ModFile {
+        let page_url = self.manual_page_url(&file).await;
+        file.into_mod_file(page_url)
+    }
+}
+
+impl ModProvider for CurseForgeClient {
+    fn name(&self) -> &'static str {
+        "CurseForge"
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<Vec<ProjectHit>> {
+        let mut params = vec![
+            ("gameId", MINECRAFT_GAME_ID.to_string()),
+            ("classId", MODS_CLASS_ID.to_string()),
+            ("searchFilter", query.query.clone()),
+            ("gameVersion", query.mc_version.clone()),
+            ("pageSize", query.limit.to_string()),
+            // Sort by popularity
+            ("sortField", "2".to_string()),
+            ("sortOrder", "desc".to_string()),
+        ];
+        if let Some(loader) = mod_loader_type(&query.loader) {
+            params.push(("modLoaderType", loader.to_string()));
+        }
+
+        let mods: Vec<CfMod> = self
+            .send(self.get("/v1/mods/search").query(&params))
+            .await?;
+
+        Ok(mods
+            .into_iter()
+            .map(|m| ProjectHit {
+                id: m.id.to_string(),
+                slug: m.slug,
+                title: m.name,
+                description: m.summary,
+                author: m
+                    .authors
+                    .into_iter()
+                    .map(|a| a.name)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                downloads: m.download_count as u64,
+                icon_url: m.logo.and_then(|l| l.thumbnail_url),
+            })
+            .collect())
+    }
+
+    async fn latest_file(
+        &self,
+        project: &str,
+        mc_version: &str,
+        loader: &ModLoader,
+    ) -> Result<Option<ModFile>> {
+        let mod_id = self.resolve_mod(project).await?.id;
+
+        let mut params = vec![("gameVersion", mc_version.to_string())];
+        if let Some(loader) = mod_loader_type(loader) {
+            params.push(("modLoaderType", loader.to_string()));
+        }
+        let files: Vec<CfFile> = self
+            .send(
+                self.get(&format!("/v1/mods/{}/files", mod_id))
+                    .query(&params),
+            )
+            .await?;
+
+        // ISO 8601 dates sort lexicographically
+        match files
+            .into_iter()
+            .max_by(|a, b| a.file_date.cmp(&b.file_date))
+        {
+            Some(file) => Ok(Some(self.to_mod_file(file).await)),
+            None => Ok(None),
+        }
+    }
+
+    async fn file(&self, version_id: &str) -> Result<ModFile> {
+        let file_id: u64 = version_id
+            .parse()
+            .with_context(|| format!("Invalid CurseForge file ID: {}", version_id))?;
+
+        let file = self
+            .files(&[file_id])
+            .await?
+            .into_iter()
+            .next()
+            .with_context(|| format!("CurseForge file {} not found", version_id))?;
+        Ok(self.to_mod_file(file).await)
+    }
+
+    async fn identify(&self, jars: &[PathBuf]) -> Result<HashMap<PathBuf, ModFile>> {
+        let mut by_fingerprint: HashMap<u32, PathBuf> = HashMap::new();
+        for jar in jars {
+            match std::fs::read(jar) {
+                Ok(bytes) => {
+                    by_fingerprint.insert(fingerprint(&bytes), jar.clone());
+                }
+                Err(e) => tracing::warn!("Failed to read {}: {}", jar.display(), e),
+            }
+        }
+        if by_fingerprint.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let fingerprints: Vec<u32> = by_fingerprint.keys().copied().collect();
+        let matches: FingerprintMatches = self
+            .send(
+                self.post(&format!("/v1/fingerprints/{}", MINECRAFT_GAME_ID))
+                    .json(&serde_json::json!({ "fingerprints": fingerprints })),
+            )
+            .await?;
+
+        let mut identified = HashMap::new();
+        for m in matches.exact_matches {
+            if let Some(jar) = by_fingerprint.get(&(m.file.file_fingerprint as u32)) {
+                identified.insert(jar.clone(), m.file.into_mod_file(None));
+            }
+        }
+        Ok(identified)
+    }
+}
+
+/// Page for a file on the CurseForge website
+pub fn file_page_url(website_url: Option<&str>, file: &CfFile) -> String {
+    match website_url {
+        Some(url) => format!("{}/files/{}", url.trim_end_matches('/'), file.id),
+        None => format!("https://www.curseforge.com/projects/{}", file.mod_id),
+    }
+}
+
+/// CurseForge mod loader type ID
+fn mod_loader_type(loader: &ModLoader) -> Option<u32> {
+    match loader {
+        ModLoader::Vanilla => None,
+        ModLoader::Forge => Some(1),
+        ModLoader::Fabric => Some(4),
+        ModLoader::Quilt => Some(5),
+        ModLoader::NeoForge => Some(6),
+    }
+}
+
+/// CurseForge file fingerprint: murmur2 (seed 1) over the file with
+/// whitespace bytes removed
+pub fn fingerprint(bytes: &[u8]) -> u32 {
+    let filtered: Vec<u8> = bytes
+        .iter()
+        .copied()
+        .filter(|b| !matches!(b, 9 | 10 | 13 | 32))
+        .collect();
+    murmur2(&filtered, 1)
+}
+
+/// MurmurHash2, 32-bit
+fn murmur2(data: &[u8], seed: u32) -> u32 {
+    const M: u32 = 0x5bd1_e995;
+    const R: u32 = 24;
+
+    let mut h = seed ^ data.len() as u32;
+
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let mut k = u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        k = k.wrapping_mul(M);
+        k ^= k >> R;
+        k = k.wrapping_mul(M);
+        h = h.wrapping_mul(M);
+        h ^= k;
+    }
+
+    let rest = chunks.remainder();
+    if rest.len() >= 3 {
+        h ^= (rest[2] as u32) << 16;
+    }
+    if rest.len() >= 2 {
+        h ^= (rest[1] as u32) << 8;
+    }
+    if !rest.is_empty() {
+        h ^= rest[0] as u32;
+        h = h.wrapping_mul(M);
+    }
+
+    h ^= h >> 13;
+    h = h.wrapping_mul(M);
+    h ^= h >> 15;
+    h
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mods::mock::MockServer;
+    use crate::util::temp::TempDir;
+
+    fn file_json(download_url: &str, fingerprint: u32) -> String {
+        format!(
+            r#"{{
+                "id": 456,
+                "modId": 123,
+                "displayName": "JEI 15.3.0",
+                "fileName": "jei-1.20.1-15.3.0.jar",
+                "downloadUrl": {download_url},
+                "fileLength": 1024,
+                "fileDate": "2024-01-10T00:00:00Z",
+                "fileFingerprint": {fingerprint},
+                "hashes": [{{ "value": "abc", "algo": 1 }}, {{ "value": "def", "algo": 2 }}],
+                "dependencies": [
+                    {{ "modId": 7, "relationType": 3 }},
+                    {{ "modId": 8, "relationType": 2 }}
+                ]
+            }}"#
+        )
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_whitespace() {
+        assert_eq!(murmur2(b"", 1), 1540447798);
+        assert_eq!(murmur2(b"abc", 1), 1621425345);
+        assert_eq!(fingerprint(b"a b\r\n\tc"), fingerprint(b"abc"));
+    }
+
+    #[tokio::test]
+    async fn test_disallowed_download_gives_page_url() {
+        let files = format!(r#"{{ "data": [{}] }}"#, file_json("null", 0));
+        let project = r#"{ "data": {
+            "id": 123,
+            "name": "Just Enough Items",
+            "slug": "jei",
+            "links": { "websiteUrl": "https://www.curseforge.com/minecraft/mc-mods/jei" }
+        } }"#;
+        let server = MockServer::start(vec![
+            (
+                "GET",
+                "/v1/mods/123/files".to_string(),
+                200,
+                files.into_bytes(),
+            ),
+            (
+                "GET",
+                "/v1/mods/123".to_string(),
+                200,
+                project.as_bytes().to_vec(),
+            ),
+        ])
+        .await;
+
+        let client = CurseForgeClient::with_base_url(&server.base_url, "key");
+        let file = client
+            .latest_file("123", "1.20.1", &ModLoader::Forge)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(file.url, None);
+        assert_eq!(
+            file.page_url.as_deref(),
+            Some("https://www.curseforge.com/minecraft/mc-mods/jei/files/456")
+        );
+        assert_eq!(file.hashes.sha1.as_deref(), Some("abc"));
+        assert_eq!(file.dependencies.len(), 1);
+        assert_eq!(file.dependencies[0].project_id, "7");
+
+        let request = &server.request_lines()[1];
+        assert!(request.contains("modLoaderType=1"), "{}", request);
+        assert!(request.contains("gameVersion=1.20.1"), "{}", request);
+    }
+
+    #[tokio::test]
+    async fn test_identify_by_fingerprint() {
+        let dir = TempDir::new("cf");
+        let jar = dir.join("jei.jar");
+        std::fs::write(&jar, b"jei contents").unwrap();
+        let fp = fingerprint(b"jei contents");
+
+        let body = format!(
+            r#"{{ "data": {{ "exactMatches": [{{ "id": 123, "file": {} }}] }} }}"#,
+            file_json(r#""https://edge.forgecdn.net/jei.jar""#, fp)
+        );
+        let server = MockServer::start(vec![(
+            "POST",
+            "/v1/fingerprints/432".to_string(),
+            200,
+            body.into_bytes(),
+        )])
+        .await;
+
+        let client = CurseForgeClient::with_base_url(&server.base_url, "key");
+        let identified = client.identify(std::slice::from_ref(&jar)).await.unwrap();
+
+        assert_eq!(identified[&jar].project_id, "123");
+        assert_eq!(identified[&jar].version_id, "456");
+        let sent = server.requests.lock().unwrap()[0].1.clone();
+        assert!(sent.contains(&fp.to_string()), "{}", sent);
+    }
+}
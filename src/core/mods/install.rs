@@ -0,0 +1,393 @@
+//! Mod installation
+//!
+//! Resolves a project and its required dependencies through a mod provider
+//! and downloads the files into a mods folder with hash verification.
+
+use super::provider::{FileHashes, ModFile, ModProvider, mod_jars};
+use crate::core::instance::ModLoader;
+use crate::util::hash::{sha1_bytes, sha512_bytes};
+use anyhow::{Context, Result};
+use std::collections::{HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+
+/// Outcome of an install
+#[derive(Debug, Default)]
+pub struct InstallReport {
+    /// Files downloaded into the mods folder
+    pub installed: Vec<ModFile>,
+    /// Projects skipped because they are already installed
+    pub already_installed: Vec<String>,
+    /// Files that must be downloaded by hand
+    pub manual: Vec<ModFile>,
+}
+
+/// Resolve a project and its required dependencies into files to install
+///
+/// Projects in `installed` are skipped, along with their dependencies.
+pub async fn resolve<P: ModProvider + Sync>(
+    provider: &P,
+    project: &str,
+    mc_version: &str,
+    loader: &ModLoader,
+    installed: &HashSet<String>,
+) -> Result<Vec<ModFile>> {
+    let mut files = Vec::new();
+    let mut seen: HashSet<String> = installed.clone();
+    let mut queue = VecDeque::from([(project.to_string(), None::<String>)]);
+
+    while let Some((project, version_id)) = queue.pop_front() {
+        // Version-only dependencies are tracked by version until fetched
+        let key = match (&version_id, project.is_empty()) {
+            (Some(id), true) => id.clone(),
+            _ => project.clone(),
+        };
+        if !seen.insert(key) {
+            continue;
+        }
+
+        let file = match version_id {
+            Some(id) => provider.file(&id).await?,
+            None => provider
+                .latest_file(&project, mc_version, loader)
+                .await?
+                .with_context(|| {
+                    format!(
+                        "No {} version of {} for Minecraft {} ({})",
+                        provider.name(),
+                        project,
+                        mc_version,
+                        loader
+                    )
+                })?,
+        };
+
+        // The API may have been queried by slug or version; skip projects
+        // already installed or resolved under their canonical ID
+        if file.project_id != project && !seen.insert(file.project_id.clone()) {
+            continue;
+        }
+
+        for dep in &file.dependencies {
+            if dep.project_id.is_empty() || !seen.contains(&dep.project_id) {
+                queue.push_back((dep.project_id.clone(), dep.version_id.clone()));
+            }
+        }
+        files.push(file);
+    }
+
+    Ok(files)
+}
+
+/// Install a project and its required dependencies into a mods folder
+pub async fn install_project<P: ModProvider + Sync>(
+    provider: &P,
+    project: &str,
+    mods_dir: &Path,
+    mc_version: &str,
+    loader: &ModLoader,
+    on_progress: &(dyn Fn(&str) + Sync),
+) -> Result<InstallReport> {
+    std::fs::create_dir_all(mods_dir)?;
+
+    on_progress("Checking installed mods...");
+    let existing = provider.identify(&mod_jars(mods_dir)).await?;
+    let installed: HashSet<String> = existing.values().map(|f| f.project_id.clone()).collect();
+
+    on_progress("Resolving dependencies...");
+    let files = resolve(provider, project, mc_version, loader, &installed).await?;
+
+    let mut report = InstallReport::default();
+    if files.is_empty() {
+        report.already_installed.push(project.to_string());
+        return Ok(report);
+    }
+
+    let client = reqwest::Client::new();
+    for file in files {
+        if file.url.is_none() {
+            report.manual.push(file);
+            continue;
+        }
+        on_progress(&format!("Downloading {}...", file.filename));
+        download_mod_file(&client, &file, mods_dir).await?;
+        report.installed.push(file);
+    }
+
+    Ok(report)
+}
+
+/// Download a file into the mods folder, verifying its published hash
+pub async fn download_mod_file(
+    client: &reqwest::Client,
+    file: &ModFile,
+    mods_dir: &Path,
+) -> Result<PathBuf> {
+    let url = file
+        .url
+        .as_deref()
+        .with_context(|| format!("{} must be downloaded manually", file.filename))?;
+    let dest = mod_file_path(mods_dir, &file.filename)?;
+
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .with_context(|| format!("Failed to download {}", file.filename))?;
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "Failed to download {}: HTTP {}",
+            file.filename,
+            response.status()
+        );
+    }
+    let bytes = response.bytes().await?;
+
+    verify_hashes(&file.filename, &file.hashes, &bytes)?;
+
+    // Write beside the target first so a failed write never leaves a broken jar
+    let tmp = mods_dir.join(format!(".{}.part", file.filename));
+    std::fs::write(&tmp, &bytes).context("Failed to write mod file")?;
+    std::fs::rename(&tmp, &dest).context("Failed to move mod file into place")?;
+
+    Ok(dest)
+}
+
+/// Check downloaded bytes against the strongest published hash
+pub fn verify_hashes(name: &str, hashes: &FileHashes, bytes: &[u8]) -> Result<()> {
+    let expected = |hash: &Option<String>| {
+        hash.as_deref()
+            .filter(|h| !h.is_empty())
+            .map(str::to_lowercase)
+    };
+
+    let (algorithm, expected, actual) = if let Some(sha512) = expected(&hashes.sha512) {
+        ("SHA512", sha512, sha512_bytes(bytes))
+    } else if let Some(sha1) = expected(&hashes.sha1) {
+        ("SHA1", sha1, sha1_bytes(bytes))
+    } else {
+        return Ok(());
+    };
+
+    if actual != expected {
+        anyhow::bail!(
+            "{} hash mismatch for {}: expected {}, got {}",
+            algorithm,
+            name,
+            expected,
+            actual
+        );
+    }
+    Ok(())
+}
+
+/// Destination for a published filename, rejecting path components
+fn mod_file_path(mods_dir: &Path, filename: &str) -> Result<PathBuf> {
+    let is_plain = !filename.is_empty()
+        && !filename.starts_with('.')
+        && !filename.contains(['/', '\\'])
+        && filename != "..";
+    if !is_plain {
+        anyhow::bail!("Refusing to install mod with unsafe filename: {}", filename);
+    }
+    Ok(mods_dir.join(filename))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mods::ModrinthClient;
+    use crate::core::mods::mock::MockServer;
+    use crate::util::temp::TempDir;
+
+    fn version(id: &str, project: &str, base: &str, sha1: &str, deps: &[&str]) -> String {
+        let deps: Vec<String> = deps
+            .iter()
+            .map(|d| {
+                format!(
+                    r#"{{ "project_id": "{d}", "version_id": null, "dependency_type": "required" }}"#
+                )
+            })
+            .collect();
+        format!(
+            r#"[{{
+                "id": "{id}",
+                "project_id": "{project}",
+                "version_number": "1.0.0",
+                "files": [{{
+                    "hashes": {{ "sha1": "{sha1}" }},
+                    "url": "{base}/files/{project}.jar",
+                    "filename": "{project}.jar",
+                    "primary": true,
+                    "size": 4
+                }}],
+                "dependencies": [{deps}]
+            }}]"#,
+            deps = deps.join(",")
+        )
+    }
+
+    /// Serve a mock Modrinth API whose download URLs point back at itself
+    async fn mock_modrinth(sha1_override: Option<&str>) -> MockServer {
+        let jar = b"jar!".to_vec();
+        let sha1 = sha1_override
+            .map(str::to_string)
+            .unwrap_or_else(|| sha1_bytes(&jar));
+
+        MockServer::start_with(|base| {
+            vec![
+                (
+                    "GET",
+                    "/project/iris/version".to_string(),
+                    200,
+                    version("v-iris", "iris", base, &sha1, &["sodium"]).into_bytes(),
+                ),
+                (
+                    "GET",
+                    "/project/sodium/version".to_string(),
+                    200,
+                    version("v-sodium", "sodium", base, &sha1, &[]).into_bytes(),
+                ),
+                ("POST", "/version_files".to_string(), 200, b"{}".to_vec()),
+                ("GET", "/files/".to_string(), 200, jar),
+            ]
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_install_resolves_dependencies() {
+        let server = mock_modrinth(None).await;
+        let client = ModrinthClient::with_base_url(&server.base_url);
+        let dir = TempDir::new("install");
+
+        let report = install_project(&client, "iris", &dir, "1.20.4", &ModLoader::Fabric, &|_| {})
+            .await
+            .unwrap();
+
+        let names: Vec<_> = report
+            .installed
+            .iter()
+            .map(|f| f.filename.as_str())
+            .collect();
+        assert_eq!(names, vec!["iris.jar", "sodium.jar"]);
+        assert!(dir.join("iris.jar").exists());
+        assert!(dir.join("sodium.jar").exists());
+        assert!(
+            server
+                .request_lines()
+                .iter()
+                .any(|l| l.starts_with("GET /project/sodium/version"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_install_rejects_hash_mismatch() {
+        let server = mock_modrinth(Some("0000")).await;
+        let client = ModrinthClient::with_base_url(&server.base_url);
+        let dir = TempDir::new("install-bad");
+
+        let err = install_project(
+            &client,
+            "sodium",
+            &dir,
+            "1.20.4",
+            &ModLoader::Fabric,
+            &|_| {},
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("hash mismatch"), "{}", err);
+        assert!(!dir.join("sodium.jar").exists());
+    }
+
+    #[tokio::test]
+    async fn test_install_by_slug_skips_installed_project() {
+        let jar = b"jar!".to_vec();
+        let sha1 = sha1_bytes(&jar);
+        let server = MockServer::start_with(|base| {
+            let sodium = version("v-sodium", "AANobbMI", base, &sha1, &[]);
+            let installed = format!(r#"{{ "{}": {} }}"#, sha1, &sodium[1..sodium.len() - 1]);
+            vec![
+                (
+                    "GET",
+                    "/project/sodium/version".to_string(),
+                    200,
+                    sodium.into_bytes(),
+                ),
+                (
+                    "POST",
+                    "/version_files".to_string(),
+                    200,
+                    installed.into_bytes(),
+                ),
+                ("GET", "/files/".to_string(), 200, jar.clone()),
+            ]
+        })
+        .await;
+        let client = ModrinthClient::with_base_url(&server.base_url);
+        let dir = TempDir::new("install-slug");
+        std::fs::write(dir.join("sodium-old.jar"), &jar).unwrap();
+
+        let report = install_project(
+            &client,
+            "sodium",
+            &dir,
+            "1.20.4",
+            &ModLoader::Fabric,
+            &|_| {},
+        )
+        .await
+        .unwrap();
+        assert!(report.installed.is_empty());
+        assert_eq!(report.already_installed, vec!["sodium".to_string()]);
+        assert!(!dir.join("AANobbMI.jar").exists());
+    }
+
+    #[tokio::test]
+    async fn test_resolve_version_only_dependency() {
+        let server = MockServer::start_with(|base| {
+            let iris = version("v-iris", "iris", base, "00", &[]).replace(
+                r#""dependencies": []"#,
+                r#""dependencies": [{ "project_id": null, "version_id": "v-sodium", "dependency_type": "required" }]"#,
+            );
+            let sodium = version("v-sodium", "sodium", base, "00", &[]);
+            vec![
+                (
+                    "GET",
+                    "/project/iris/version".to_string(),
+                    200,
+                    iris.into_bytes(),
+                ),
+                (
+                    "GET",
+                    "/version/v-sodium".to_string(),
+                    200,
+                    sodium[1..sodium.len() - 1].as_bytes().to_vec(),
+                ),
+            ]
+        })
+        .await;
+        let client = ModrinthClient::with_base_url(&server.base_url);
+
+        let files = resolve(
+            &client,
+            "iris",
+            "1.20.4",
+            &ModLoader::Fabric,
+            &HashSet::new(),
+        )
+        .await
+        .unwrap();
+        let projects: Vec<_> = files.iter().map(|f| f.project_id.as_str()).collect();
+        assert_eq!(projects, vec!["iris", "sodium"]);
+    }
+
+    #[test]
+    fn test_mod_file_path_rejects_traversal() {
+        let dir = Path::new("/mods");
+        assert!(mod_file_path(dir, "sodium.jar").is_ok());
+        assert!(mod_file_path(dir, "../evil.jar").is_err());
+        assert!(mod_file_path(dir, "a/b.jar").is_err());
+        assert!(mod_file_path(dir, "..").is_err());
+    }
+}
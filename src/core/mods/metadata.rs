@@ -0,0 +1,776 @@
+//! Mod metadata parsing
+//!
+//! Reads mod descriptors from Fabric (`fabric.mod.json`), Quilt
+//! (`quilt.mod.json`), Forge/NeoForge (`META-INF/mods.toml`,
+//! `META-INF/neoforge.mods.toml`) and legacy Forge (`mcmod.info`) jars,
+//! including mods nested inside them (Fabric/Quilt `jars`,
+//! `META-INF/jarjar`).
+
+use crate::core::instance::ModLoader;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::{Cursor, Read, Seek};
+use zip::ZipArchive;
+
+/// Kind of relationship a mod declares with another mod
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyKind {
+    /// Must be present
+    Required,
+    /// Used if present
+    Optional,
+    /// Must not be present (game will not work)
+    Breaks,
+    /// Should not be present (known issues)
+    Conflicts,
+}
+
+/// A dependency declared by a mod
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModDependency {
+    /// Mod ID of the dependency
+    pub mod_id: String,
+    /// Accepted versions in the mod's own syntax (`*` for any)
+    pub version_range: String,
+    /// Relationship kind
+    pub kind: DependencyKind,
+}
+
+/// Metadata read from a mod jar
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModMetadata {
+    pub mod_id: String,
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub authors: Vec<String>,
+    pub loader: Option<ModLoader>,
+    /// Icon path inside the jar
+    pub icon: Option<String>,
+    pub dependencies: Vec<ModDependency>,
+    /// Other mod IDs the jar supplies, with versions: declared aliases and
+    /// nested jar-in-jar mods
+    pub provides: Vec<(String, String)>,
+}
+
+/// Read metadata from a mod jar, trying each supported format
+pub fn read_metadata<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Option<ModMetadata> {
+    let mut meta = read_own_metadata(archive)?;
+    for path in nested_jars(archive) {
+        let Some(bytes) = read_entry_bytes(archive, &path) else {
+            continue;
+        };
+        let Ok(mut nested) = ZipArchive::new(Cursor::new(bytes)) else {
+            continue;
+        };
+        if let Some(nested) = read_metadata(&mut nested) {
+            meta.provides.push((nested.mod_id, nested.version));
+            meta.provides.extend(nested.provides);
+        }
+    }
+    Some(meta)
+}
+
+/// Read the jar's own descriptor, ignoring nested jars
+fn read_own_metadata<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Option<ModMetadata> {
+    if let Some(contents) = read_entry(archive, "fabric.mod.json") {
+        return parse_fabric(&contents);
+    }
+    if let Some(contents) = read_entry(archive, "quilt.mod.json") {
+        return parse_quilt(&contents);
+    }
+
+    for (entry, loader) in [
+        ("META-INF/neoforge.mods.toml", ModLoader::NeoForge),
+        ("META-INF/mods.toml", ModLoader::Forge),
+    ] {
+        if let Some(contents) = read_entry(archive, entry) {
+            let jar_version = read_entry(archive, "META-INF/MANIFEST.MF")
+                .and_then(|manifest| manifest_value(&manifest, "Implementation-Version"));
+            return parse_mods_toml(&contents, loader, jar_version.as_deref());
+        }
+    }
+
+    if let Some(contents) = read_entry(archive, "mcmod.info") {
+        return parse_mcmod_info(&contents);
+    }
+
+    None
+}
+
+/// Read a text entry from a jar
+fn read_entry<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Option<String> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut contents = String::new();
+    entry.read_to_string(&mut contents).ok()?;
+    Some(contents)
+}
+
+/// Read a binary entry from a jar
+fn read_entry_bytes<R: Read + Seek>(archive: &mut ZipArchive<R>, name: &str) -> Option<Vec<u8>> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut contents = Vec::new();
+    entry.read_to_end(&mut contents).ok()?;
+    Some(contents)
+}
+
+/// Nested jar listings in each supported format
+#[derive(Debug, Default, Deserialize)]
+struct NestedJars {
+    #[serde(default)]
+    jars: Vec<NestedJar>,
+    quilt_loader: Option<Box<NestedJars>>,
+}
+
+/// A nested jar: a Fabric `{ "file": ... }`, a jarjar `{ "path": ... }` or a
+/// bare Quilt path
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum NestedJar {
+    Path(String),
+    Object {
+        #[serde(alias = "path")]
+        file: String,
+    },
+}
+
+/// Paths of the jars nested inside a jar
+fn nested_jars<R: Read + Seek>(archive: &mut ZipArchive<R>) -> Vec<String> {
+    let mut paths = Vec::new();
+    for entry in [
+        "fabric.mod.json",
+        "quilt.mod.json",
+        "META-INF/jarjar/metadata.json",
+    ] {
+        let Some(contents) = read_entry(archive, entry) else {
+            continue;
+        };
+        let Ok(listing) = serde_json::from_str::<NestedJars>(&contents) else {
+            continue;
+        };
+        let quilt = listing.quilt_loader.map(|q| q.jars).unwrap_or_default();
+        for jar in listing.jars.into_iter().chain(quilt) {
+            let (NestedJar::Path(path) | NestedJar::Object { file: path }) = jar;
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Look up a main attribute in a `MANIFEST.MF`
+pub fn manifest_value(manifest: &str, key: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        (name.trim() == key).then(|| value.trim().to_string())
+    })
+}
+
+/// String or list of strings
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OneOrMany {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl OneOrMany {
+    /// Join alternatives the way Fabric reads them (any may match)
+    fn into_range(self) -> String {
+        match self {
+            OneOrMany::One(s) => s,
+            OneOrMany::Many(v) if v.is_empty() => "*".to_string(),
+            OneOrMany::Many(v) => v.join(" || "),
+        }
+    }
+}
+
+/// Author as a plain name or a person object
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Person {
+    Name(String),
+    Object { name: String },
+}
+
+impl Person {
+    fn into_name(self) -> String {
+        match self {
+            Person::Name(name) | Person::Object { name } => name,
+        }
+    }
+}
+
+/// Icon as a single path or a size -> path map
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Icon {
+    Path(String),
+    Sizes(HashMap<String, String>),
+}
+
+impl Icon {
+    /// Pick the single path, or the largest size
+    fn into_path(self) -> Option<String> {
+        match self {
+            Icon::Path(path) => Some(path),
+            Icon::Sizes(sizes) => sizes
+                .into_iter()
+                .max_by_key(|(size, _)| size.parse::<u32>().unwrap_or(0))
+                .map(|(_, path)| path),
+        }
+    }
+}
+
+/// Fabric mod metadata from fabric.mod.json
+#[derive(Debug, Deserialize)]
+struct FabricModJson {
+    #[serde(default)]
+    id: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    authors: Vec<Person>,
+    icon: Option<Icon>,
+    #[serde(default)]
+    depends: HashMap<String, OneOrMany>,
+    #[serde(default)]
+    recommends: HashMap<String, OneOrMany>,
+    #[serde(default)]
+    suggests: HashMap<String, OneOrMany>,
+    #[serde(default)]
+    breaks: HashMap<String, OneOrMany>,
+    #[serde(default)]
+    conflicts: HashMap<String, OneOrMany>,
+    #[serde(default)]
+    provides: Vec<String>,
+}
+
+/// Parse a `fabric.mod.json`
+pub fn parse_fabric(contents: &str) -> Option<ModMetadata> {
+    let meta: FabricModJson = serde_json::from_str(contents).ok()?;
+
+    let mut dependencies = Vec::new();
+    for (map, kind) in [
+        (meta.depends, DependencyKind::Required),
+        (meta.recommends, DependencyKind::Optional),
+        (meta.suggests, DependencyKind::Optional),
+        (meta.breaks, DependencyKind::Breaks),
+        (meta.conflicts, DependencyKind::Conflicts),
+    ] {
+        for (mod_id, range) in map {
+            dependencies.push(ModDependency {
+                mod_id,
+                version_range: range.into_range(),
+                kind,
+            });
+        }
+    }
+    dependencies.sort_by(|a, b| a.mod_id.cmp(&b.mod_id));
+
+    let name = if meta.name.is_empty() {
+        meta.id.clone()
+    } else {
+        meta.name
+    };
+
+    let provides = meta
+        .provides
+        .into_iter()
+        .map(|id| (id, meta.version.clone()))
+        .collect();
+
+    Some(ModMetadata {
+        mod_id: meta.id,
+        name,
+        version: meta.version,
+        description: meta.description,
+        authors: meta.authors.into_iter().map(Person::into_name).collect(),
+        loader: Some(ModLoader::Fabric),
+        icon: meta.icon.and_then(Icon::into_path),
+        dependencies,
+        provides,
+    })
+}
+
+/// Quilt mod metadata from quilt.mod.json
+#[derive(Debug, Deserialize)]
+struct QuiltModJson {
+    quilt_loader: QuiltLoader,
+}
+
+#[derive(Debug, Deserialize)]
+struct QuiltLoader {
+    id: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    metadata: QuiltMetadata,
+    #[serde(default)]
+    depends: Vec<QuiltDependency>,
+    #[serde(default)]
+    breaks: Vec<QuiltDependency>,
+    #[serde(default)]
+    provides: Vec<QuiltProvide>,
+}
+
+/// Quilt alias: a bare mod ID or an object with its own version
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum QuiltProvide {
+    Id(String),
+    Object { id: String, version: Option<String> },
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct QuiltMetadata {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    contributors: HashMap<String, serde_json::Value>,
+    icon: Option<Icon>,
+}
+
+/// Quilt dependency: a bare mod ID or an object
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum QuiltDependency {
+    Id(String),
+    Object {
+        id: String,
+        versions: Option<OneOrMany>,
+        #[serde(default)]
+        optional: bool,
+    },
+}
+
+/// Parse a `quilt.mod.json`
+pub fn parse_quilt(contents: &str) -> Option<ModMetadata> {
+    let meta: QuiltModJson = serde_json::from_str(contents).ok()?;
+    let loader = meta.quilt_loader;
+
+    let mut dependencies = Vec::new();
+    for (list, base_kind) in [
+        (loader.depends, DependencyKind::Required),
+        (loader.breaks, DependencyKind::Breaks),
+    ] {
+        for dep in list {
+            let (mod_id, versions, optional) = match dep {
+                QuiltDependency::Id(id) => (id, None, false),
+                QuiltDependency::Object {
+                    id,
+                    versions,
+                    optional,
+                } => (id, versions, optional),
+            };
+            let kind = if optional && base_kind == DependencyKind::Required {
+                DependencyKind::Optional
+            } else {
+                base_kind
+            };
+            dependencies.push(ModDependency {
+                mod_id,
+                version_range: versions.map_or_else(|| "*".to_string(), OneOrMany::into_range),
+                kind,
+            });
+        }
+    }
+
+    let mut authors: Vec<String> = loader.metadata.contributors.into_keys().collect();
+    authors.sort();
+
+    let name = if loader.metadata.name.is_empty() {
+        loader.id.clone()
+    } else {
+        loader.metadata.name
+    };
+
+    let provides = loader
+        .provides
+        .into_iter()
+        .map(|provide| match provide {
+            QuiltProvide::Id(id) => (id, loader.version.clone()),
+            QuiltProvide::Object { id, version } => {
+                (id, version.unwrap_or_else(|| loader.version.clone()))
+            }
+        })
+        .collect();
+
+    Some(ModMetadata {
+        mod_id: loader.id,
+        name,
+        version: loader.version,
+        description: loader.metadata.description,
+        authors,
+        loader: Some(ModLoader::Quilt),
+        icon: loader.metadata.icon.and_then(Icon::into_path),
+        dependencies,
+        provides,
+    })
+}
+
+/// Forge/NeoForge mods.toml
+#[derive(Debug, Deserialize)]
+struct ModsToml {
+    #[serde(default)]
+    mods: Vec<ModsTomlMod>,
+    #[serde(default)]
+    dependencies: HashMap<String, Vec<ModsTomlDependency>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModsTomlMod {
+    mod_id: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    display_name: String,
+    #[serde(default)]
+    description: String,
+    authors: Option<toml::Value>,
+    logo_file: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ModsTomlDependency {
+    mod_id: String,
+    /// Forge: required or not
+    mandatory: Option<bool>,
+    /// NeoForge: required, optional, incompatible or discouraged
+    #[serde(rename = "type")]
+    dependency_type: Option<String>,
+    #[serde(default)]
+    version_range: String,
+}
+
+/// Parse a `mods.toml` or `neoforge.mods.toml`
+///
+/// `${file.jarVersion}` is replaced with `jar_version` from the manifest.
+pub fn parse_mods_toml(
+    contents: &str,
+    loader: ModLoader,
+    jar_version: Option<&str>,
+) -> Option<ModMetadata> {
+    let mut meta: ModsToml = toml::from_str(contents).ok()?;
+    if meta.mods.is_empty() {
+        return None;
+    }
+    let first = meta.mods.remove(0);
+
+    let version = if first.version.contains("${file.jarVersion}") {
+        match jar_version {
+            Some(jar_version) => first.version.replace("${file.jarVersion}", jar_version),
+            None => "Unknown".to_string(),
+        }
+    } else {
+        first.version
+    };
+
+    let authors = match first.authors {
+        Some(toml::Value::String(s)) => s
+            .split(',')
+            .map(|a| a.trim().to_string())
+            .filter(|a| !a.is_empty())
+            .collect(),
+        Some(toml::Value::Array(values)) => values
+            .into_iter()
+            .filter_map(|v| v.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let dependencies = meta
+        .dependencies
+        .remove(&first.mod_id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|dep| {
+            let kind = match dep.dependency_type.as_deref() {
+                Some("required") => DependencyKind::Required,
+                Some("optional") => DependencyKind::Optional,
+                Some("incompatible") => DependencyKind::Breaks,
+                Some("discouraged") => DependencyKind::Conflicts,
+                _ if dep.mandatory.unwrap_or(true) => DependencyKind::Required,
+                _ => DependencyKind::Optional,
+            };
+            ModDependency {
+                mod_id: dep.mod_id,
+                version_range: if dep.version_range.is_empty() {
+                    "*".to_string()
+                } else {
+                    dep.version_range
+                },
+                kind,
+            }
+        })
+        .collect();
+
+    let name = if first.display_name.is_empty() {
+        first.mod_id.clone()
+    } else {
+        first.display_name
+    };
+
+    Some(ModMetadata {
+        mod_id: first.mod_id,
+        name,
+        version,
+        description: first.description.trim().to_string(),
+        authors,
+        loader: Some(loader),
+        icon: first.logo_file,
+        dependencies,
+        provides: Vec::new(),
+    })
+}
+
+/// Legacy Forge mcmod.info entry
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct McModInfo {
+    modid: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    author_list: Vec<String>,
+    logo_file: Option<String>,
+    #[serde(default)]
+    required_mods: Vec<String>,
+}
+
+/// mcmod.info is either a bare list or a versioned wrapper
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum McModInfoFile {
+    List(Vec<McModInfo>),
+    Wrapped {
+        #[serde(rename = "modList")]
+        mod_list: Vec<McModInfo>,
+    },
+}
+
+/// Parse a legacy `mcmod.info`
+pub fn parse_mcmod_info(contents: &str) -> Option<ModMetadata> {
+    let file: McModInfoFile = serde_json::from_str(contents).ok()?;
+    let info = match file {
+        McModInfoFile::List(list) | McModInfoFile::Wrapped { mod_list: list } => {
+            list.into_iter().next()?
+        }
+    };
+
+    // requiredMods entries look like "modid" or "modid@[1.0,)"
+    let dependencies = info
+        .required_mods
+        .into_iter()
+        .map(|spec| {
+            let (mod_id, range) = spec.split_once('@').unwrap_or((&spec, "*"));
+            ModDependency {
+                mod_id: mod_id.to_string(),
+                version_range: range.to_string(),
+                kind: DependencyKind::Required,
+            }
+        })
+        .collect();
+
+    let name = if info.name.is_empty() {
+        info.modid.clone()
+    } else {
+        info.name
+    };
+
+    Some(ModMetadata {
+        mod_id: info.modid,
+        name,
+        version: info.version,
+        description: info.description,
+        authors: info.author_list,
+        loader: Some(ModLoader::Forge),
+        icon: info.logo_file.filter(|logo| !logo.is_empty()),
+        dependencies,
+        provides: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fabric() {
+        let json = r#"{
+            "schemaVersion": 1,
+            "id": "sodium",
+            "version": "0.5.8+mc1.20.4",
+            "name": "Sodium",
+            "description": "Rendering engine",
+            "authors": ["JellySquid", { "name": "IMS" }],
+            "icon": "assets/sodium/icon.png",
+            "depends": { "fabricloader": ">=0.12.0", "minecraft": ["1.20.3", "1.20.4"] },
+            "breaks": { "optifabric": "*" }
+        }"#;
+        let meta = parse_fabric(json).unwrap();
+        assert_eq!(meta.mod_id, "sodium");
+        assert_eq!(meta.authors, vec!["JellySquid", "IMS"]);
+        assert_eq!(meta.icon.as_deref(), Some("assets/sodium/icon.png"));
+        assert_eq!(meta.loader, Some(ModLoader::Fabric));
+        assert_eq!(meta.dependencies.len(), 3);
+        let minecraft = meta
+            .dependencies
+            .iter()
+            .find(|d| d.mod_id == "minecraft")
+            .unwrap();
+        assert_eq!(minecraft.version_range, "1.20.3 || 1.20.4");
+        let optifabric = meta
+            .dependencies
+            .iter()
+            .find(|d| d.mod_id == "optifabric")
+            .unwrap();
+        assert_eq!(optifabric.kind, DependencyKind::Breaks);
+    }
+
+    #[test]
+    fn test_parse_quilt() {
+        let json = r#"{
+            "schema_version": 1,
+            "quilt_loader": {
+                "group": "org.quiltmc",
+                "id": "qsl",
+                "version": "7.0.0",
+                "metadata": {
+                    "name": "Quilt Standard Libraries",
+                    "contributors": { "Alice": "Owner" },
+                    "icon": { "32": "icon32.png", "128": "icon128.png" }
+                },
+                "depends": [
+                    "quilt_loader",
+                    { "id": "minecraft", "versions": ">=1.20" },
+                    { "id": "modmenu", "optional": true }
+                ]
+            }
+        }"#;
+        let meta = parse_quilt(json).unwrap();
+        assert_eq!(meta.mod_id, "qsl");
+        assert_eq!(meta.name, "Quilt Standard Libraries");
+        assert_eq!(meta.authors, vec!["Alice"]);
+        assert_eq!(meta.icon.as_deref(), Some("icon128.png"));
+        assert_eq!(meta.loader, Some(ModLoader::Quilt));
+        assert_eq!(meta.dependencies[0].version_range, "*");
+        assert_eq!(meta.dependencies[1].version_range, ">=1.20");
+        assert_eq!(meta.dependencies[2].kind, DependencyKind::Optional);
+    }
+
+    #[test]
+    fn test_parse_mods_toml() {
+        let toml = r#"
+modLoader = "javafml"
+loaderVersion = "[47,)"
+license = "MIT"
+
+[[mods]]
+modId = "jei"
+version = "${file.jarVersion}"
+displayName = "Just Enough Items"
+authors = "mezz, Alice"
+logoFile = "logo.png"
+description = '''
+Item and recipe viewing mod.
+'''
+
+[[dependencies.jei]]
+modId = "forge"
+mandatory = true
+versionRange = "[47.1.3,)"
+ordering = "NONE"
+side = "BOTH"
+
+[[dependencies.jei]]
+modId = "minecraft"
+mandatory = true
+versionRange = "[1.20.1,1.20.2)"
+"#;
+        let meta = parse_mods_toml(toml, ModLoader::Forge, Some("15.2.0.27")).unwrap();
+        assert_eq!(meta.mod_id, "jei");
+        assert_eq!(meta.version, "15.2.0.27");
+        assert_eq!(meta.authors, vec!["mezz", "Alice"]);
+        assert_eq!(meta.description, "Item and recipe viewing mod.");
+        assert_eq!(meta.icon.as_deref(), Some("logo.png"));
+        assert_eq!(meta.dependencies.len(), 2);
+        assert_eq!(meta.dependencies[1].version_range, "[1.20.1,1.20.2)");
+        assert_eq!(meta.dependencies[1].kind, DependencyKind::Required);
+
+        let unresolved = parse_mods_toml(toml, ModLoader::Forge, None).unwrap();
+        assert_eq!(unresolved.version, "Unknown");
+    }
+
+    #[test]
+    fn test_parse_neoforge_mods_toml() {
+        let toml = r#"
+modLoader = "javafml"
+loaderVersion = "[2,)"
+
+[[mods]]
+modId = "examplemod"
+version = "1.0.0"
+
+[[dependencies.examplemod]]
+modId = "neoforge"
+type = "required"
+versionRange = "[20.4,)"
+
+[[dependencies.examplemod]]
+modId = "optifine"
+type = "incompatible"
+"#;
+        let meta = parse_mods_toml(toml, ModLoader::NeoForge, None).unwrap();
+        assert_eq!(meta.name, "examplemod");
+        assert_eq!(meta.loader, Some(ModLoader::NeoForge));
+        assert_eq!(meta.dependencies[0].kind, DependencyKind::Required);
+        assert_eq!(meta.dependencies[1].kind, DependencyKind::Breaks);
+        assert_eq!(meta.dependencies[1].version_range, "*");
+    }
+
+    #[test]
+    fn test_parse_mcmod_info() {
+        let list = r#"[{
+            "modid": "journeymap",
+            "name": "JourneyMap",
+            "version": "5.7.1",
+            "authorList": ["techbrew", "mysticdrew"],
+            "logoFile": "",
+            "requiredMods": ["Forge@[14.23,)"]
+        }]"#;
+        let meta = parse_mcmod_info(list).unwrap();
+        assert_eq!(meta.mod_id, "journeymap");
+        assert_eq!(meta.authors.len(), 2);
+        assert_eq!(meta.icon, None);
+        assert_eq!(meta.dependencies[0].mod_id, "Forge");
+        assert_eq!(meta.dependencies[0].version_range, "[14.23,)");
+
+        let wrapped =
+            r#"{ "modListVersion": 2, "modList": [{ "modid": "ic2", "version": "2.8" }] }"#;
+        let meta = parse_mcmod_info(wrapped).unwrap();
+        assert_eq!(meta.name, "ic2");
+    }
+
+    #[test]
+    fn test_manifest_value() {
+        let manifest = "Manifest-Version: 1.0\r\nImplementation-Version: 1.2.3\r\n";
+        assert_eq!(
+            manifest_value(manifest, "Implementation-Version").as_deref(),
+            Some("1.2.3")
+        );
+        assert_eq!(manifest_value(manifest, "Missing"), None);
+    }
+}
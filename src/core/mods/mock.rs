@@ -0,0 +1,121 @@
+//! Local mock HTTP server for mod provider tests
+//!
+//! Serves canned responses by method and path prefix on a random port.
+
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A canned response: (method, path prefix, status, body)
+pub type Route = (&'static str, String, u16, Vec<u8>);
+
+/// Running mock server
+pub struct MockServer {
+    pub base_url: String,
+    /// Request lines and bodies received so far
+    pub requests: Arc<Mutex<Vec<(String, String)>>>,
+}
+
+impl MockServer {
+    /// Start serving routes; the first matching prefix wins
+    pub async fn start(routes: Vec<Route>) -> Self {
+        Self::start_with(|_| routes).await
+    }
+
+    /// Start serving routes built from the server's own base URL
+    pub async fn start_with(routes: impl FnOnce(&str) -> Vec<Route>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = requests.clone();
+        let routes = Arc::new(routes(&base_url));
+
+        tokio::spawn(async move {
+            loop {
+                let Ok((mut socket, _)) = listener.accept().await else {
+                    return;
+                };
+                let routes = routes.clone();
+                let log = log.clone();
+                tokio::spawn(async move {
+                    let Some((request_line, body)) = read_request(&mut socket).await else {
+                        return;
+                    };
+                    log.lock()
+                        .unwrap()
+                        .push((request_line.clone(), body.clone()));
+
+                    let mut parts = request_line.split_whitespace();
+                    let method = parts.next().unwrap_or_default();
+                    let target = parts.next().unwrap_or_default();
+
+                    let (status, body) = routes
+                        .iter()
+                        .find(|(m, prefix, _, _)| *m == method && target.starts_with(prefix))
+                        .map(|(_, _, status, body)| (*status, body.clone()))
+                        .unwrap_or((404, b"{}".to_vec()));
+
+                    let head = format!(
+                        "HTTP/1.1 {} OK\r\nContent-Length: {}\r\nContent-Type: application/json\r\nConnection: close\r\n\r\n",
+                        status,
+                        body.len()
+                    );
+                    let _ = socket.write_all(head.as_bytes()).await;
+                    let _ = socket.write_all(&body).await;
+                    let _ = socket.shutdown().await;
+                });
+            }
+        });
+
+        Self { base_url, requests }
+    }
+
+    /// Request lines received so far
+    pub fn request_lines(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(line, _)| line.clone())
+            .collect()
+    }
+}
+
+/// Read one HTTP request, returning its request line and body
+async fn read_request(socket: &mut tokio::net::TcpStream) -> Option<(String, String)> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+
+    let header_end = loop {
+        let n = socket.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).to_string();
+    let content_length = head
+        .lines()
+        .find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.eq_ignore_ascii_case("content-length")
+                .then(|| value.trim().parse::<usize>().ok())?
+        })
+        .unwrap_or(0);
+
+    while buf.len() < header_end + content_length {
+        let n = socket.read(&mut chunk).await.ok()?;
+        if n == 0 {
+            break;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let request_line = head.lines().next()?.to_string();
+    let body = String::from_utf8_lossy(&buf[header_end..]).to_string();
+    Some((request_line, body))
+}
@@ -2,11 +2,31 @@
 //!
 //! Scans and manages mods in the instance mods folder.
 
+mod check;
+mod curseforge;
+mod install;
+mod metadata;
+#[cfg(test)]
+pub(crate) mod mock;
+mod modrinth;
+mod provider;
+mod update;
+
+pub use check::{ModEnvironment, ModIssue, check_instance, check_mods, compare_versions};
+pub use curseforge::{CfFile, CurseForgeClient, file_page_url};
+pub use install::{install_project, verify_hashes};
+pub use metadata::{DependencyKind, ModDependency, ModMetadata};
+pub use modrinth::ModrinthClient;
+pub use provider::{
+    FileHashes, ModFile, ModProvider, ModSource, ProjectHit, SearchQuery, mod_jars,
+};
+pub use update::{ModUpdate, apply_update, check_updates};
+
+use crate::core::instance::ModLoader;
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::fs;
-use std::io::Read;
 use std::path::{Path, PathBuf};
 use zip::ZipArchive;
 
@@ -17,7 +37,10 @@ pub struct ModInfo {
     pub filename: String,
     /// Full path to the mod
     pub path: PathBuf,
-    /// Mod name (from fabric.mod.json or filename)
+    /// Mod ID (empty if the jar has no metadata)
+    #[serde(default)]
+    pub mod_id: String,
+    /// Mod name (from mod metadata or filename)
     pub name: String,
     /// Mod version
     pub version: String,
@@ -29,17 +52,21 @@ pub struct ModInfo {
     pub modified_at: DateTime<Utc>,
     /// File size in bytes
     pub size: u64,
-}
-
-/// Fabric mod metadata from fabric.mod.json
-#[derive(Debug, Deserialize)]
-struct FabricModJson {
+    /// Mod authors
     #[serde(default)]
-    name: String,
+    pub authors: Vec<String>,
+    /// Loader the mod is built for
     #[serde(default)]
-    version: String,
+    pub loader: Option<ModLoader>,
+    /// Icon path inside the jar
     #[serde(default)]
-    description: String,
+    pub icon: Option<String>,
+    /// Declared dependencies, breaks and conflicts
+    #[serde(default)]
+    pub dependencies: Vec<ModDependency>,
+    /// Other mod IDs the jar supplies, with versions
+    #[serde(default)]
+    pub provides: Vec<(String, String)>,
 }
 
 /// Mod manager for scanning and managing mods
@@ -104,24 +131,34 @@ impl ModManager {
                 .unwrap_or_else(Utc::now);
 
             // Try to read mod metadata from the JAR
-            let (name, version, description) = self.read_mod_metadata(&path).unwrap_or_else(|| {
+            let meta = self.read_mod_metadata(&path).unwrap_or_else(|| {
                 // Fallback to filename
                 let clean_name = filename
                     .trim_end_matches(".disabled")
                     .trim_end_matches(".jar")
                     .to_string();
-                (clean_name, "Unknown".to_string(), String::new())
+                ModMetadata {
+                    name: clean_name,
+                    version: "Unknown".to_string(),
+                    ..Default::default()
+                }
             });
 
             mods.push(ModInfo {
                 filename: filename.clone(),
                 path: path.clone(),
-                name,
-                version,
-                description,
+                mod_id: meta.mod_id,
+                name: meta.name,
+                version: meta.version,
+                description: meta.description,
                 enabled: is_jar,
                 modified_at: modified,
                 size: metadata.len(),
+                authors: meta.authors,
+                loader: meta.loader,
+                icon: meta.icon,
+                dependencies: meta.dependencies,
+                provides: meta.provides,
             });
         }
 
@@ -132,23 +169,10 @@ impl ModManager {
     }
 
     /// Read mod metadata from a JAR file
-    fn read_mod_metadata(&self, path: &Path) -> Option<(String, String, String)> {
+    fn read_mod_metadata(&self, path: &Path) -> Option<ModMetadata> {
         let file = fs::File::open(path).ok()?;
         let mut archive = ZipArchive::new(file).ok()?;
-
-        // Try fabric.mod.json first
-        if let Ok(mut entry) = archive.by_name("fabric.mod.json") {
-            let mut contents = String::new();
-            entry.read_to_string(&mut contents).ok()?;
-
-            if let Ok(meta) = serde_json::from_str::<FabricModJson>(&contents) {
-                if !meta.name.is_empty() {
-                    return Some((meta.name, meta.version, meta.description));
-                }
-            }
-        }
-
-        None
+        metadata::read_metadata(&mut archive)
     }
 
     /// Toggle a mod's enabled state
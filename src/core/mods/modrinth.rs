@@ -0,0 +1,443 @@
+//! Modrinth API client
+//!
+//! Searches projects, fetches versions and identifies jars by hash using
+//! the Modrinth v2 API.
+
+use super::provider::{
+    FileDependency, FileHashes, ModFile, ModProvider, ProjectHit, SearchQuery, loader_slug,
+};
+use crate::core::instance::ModLoader;
+use crate::util::hash::sha1_file;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Modrinth API base URL
+const MODRINTH_API: &str = "https://api.modrinth.com/v2";
+
+/// Modrinth asks clients to identify themselves
+const USER_AGENT: &str = concat!("gLauncher/", env!("CARGO_PKG_VERSION"));
+
+/// Modrinth API client
+pub struct ModrinthClient {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    hits: Vec<SearchHit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchHit {
+    project_id: String,
+    #[serde(default)]
+    slug: String,
+    title: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default)]
+    author: String,
+    #[serde(default)]
+    downloads: u64,
+    icon_url: Option<String>,
+}
+
+/// A project version
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModrinthVersion {
+    pub id: String,
+    pub project_id: String,
+    pub version_number: String,
+    #[serde(default)]
+    pub files: Vec<ModrinthFile>,
+    #[serde(default)]
+    pub dependencies: Vec<ModrinthDependency>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModrinthFile {
+    pub hashes: HashMap<String, String>,
+    pub url: String,
+    pub filename: String,
+    #[serde(default)]
+    pub primary: bool,
+    #[serde(default)]
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ModrinthDependency {
+    pub version_id: Option<String>,
+    pub project_id: Option<String>,
+    pub dependency_type: String,
+}
+
+#[derive(Debug, Serialize)]
+struct HashesRequest<'a> {
+    hashes: &'a [String],
+    algorithm: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct UpdatesRequest<'a> {
+    hashes: &'a [String],
+    algorithm: &'a str,
+    loaders: Vec<&'a str>,
+    game_versions: [&'a str; 1],
+}
+
+impl ModrinthVersion {
+    /// Convert to the provider-neutral file, using the primary file
+    pub fn into_mod_file(self) -> Option<ModFile> {
+        let file = self
+            .files
+            .iter()
+            .find(|f| f.primary)
+            .or_else(|| self.files.first())?
+            .clone();
+
+        let dependencies = self
+            .dependencies
+            .into_iter()
+            .filter(|d| d.dependency_type == "required")
+            .filter(|d| d.project_id.is_some() || d.version_id.is_some())
+            .map(|d| FileDependency {
+                project_id: d.project_id.unwrap_or_default(),
+                version_id: d.version_id,
+            })
+            .collect();
+
+        Some(ModFile {
+            page_url: Some(format!(
+                "https://modrinth.com/mod/{}/version/{}",
+                self.project_id, self.id
+            )),
+            project_id: self.project_id,
+            version_id: self.id,
+            version_number: self.version_number,
+            filename: file.filename,
+            url: Some(file.url),
+            size: file.size,
+            hashes: FileHashes {
+                sha1: file.hashes.get("sha1").cloned(),
+                sha512: file.hashes.get("sha512").cloned(),
+            },
+            dependencies,
+        })
+    }
+}
+
+impl ModrinthClient {
+    /// Create a client for the public Modrinth API
+    pub fn new() -> Self {
+        Self::with_base_url(MODRINTH_API)
+    }
+
+    /// Create a client for another API endpoint (e.g. a local mock)
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            client: reqwest::Client::builder()
+                .user_agent(USER_AGENT)
+                .build()
+                .unwrap_or_default(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Send a GET request and parse the JSON response
+    async fn get_json<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<T> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.base_url, path))
+            .query(query)
+            .send()
+            .await
+            .context("Failed to reach Modrinth")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Modrinth request failed: HTTP {}", response.status());
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse Modrinth response")
+    }
+
+    /// Versions of a project for a game version and loader, newest first
+    pub async fn project_versions(
+        &self,
+        project: &str,
+        mc_version: &str,
+        loader: &ModLoader,
+    ) -> Result<Vec<ModrinthVersion>> {
+        let mut query = vec![("game_versions", format!("[\"{}\"]", mc_version))];
+        if let Some(loader) = loader_slug(loader) {
+            query.push(("loaders", format!("[\"{}\"]", loader)));
+        }
+        self.get_json(&format!("/project/{}/version", project), &query)
+            .await
+    }
+
+    /// Send a POST request with a JSON body and parse the JSON response
+    async fn post_json<T: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &impl Serialize,
+    ) -> Result<T> {
+        let response = self
+            .client
+            .post(format!("{}{}", self.base_url, path))
+            .json(body)
+            .send()
+            .await
+            .context("Failed to reach Modrinth")?;
+
+        if !response.status().is_success() {
+            anyhow::bail!("Modrinth request failed: HTTP {}", response.status());
+        }
+
+        response
+            .json()
+            .await
+            .context("Failed to parse Modrinth response")
+    }
+
+    /// Look up versions by file hash (`sha1` or `sha512`)
+    pub async fn versions_by_hashes(
+        &self,
+        hashes: &[String],
+        algorithm: &str,
+    ) -> Result<HashMap<String, ModrinthVersion>> {
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+        self.post_json("/version_files", &HashesRequest { hashes, algorithm })
+            .await
+    }
+
+    /// Newest compatible version for each file hash (`sha1` or `sha512`)
+    pub async fn latest_versions_by_hashes(
+        &self,
+        hashes: &[String],
+        algorithm: &str,
+        mc_version: &str,
+        loader: &ModLoader,
+    ) -> Result<HashMap<String, ModrinthVersion>> {
+        if hashes.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let request = UpdatesRequest {
+            hashes,
+            algorithm,
+            loaders: loader_slug(loader).into_iter().collect(),
+            game_versions: [mc_version],
+        };
+        self.post_json("/version_files/update", &request).await
+    }
+}
+
+impl Default for ModrinthClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModProvider for ModrinthClient {
+    fn name(&self) -> &'static str {
+        "Modrinth"
+    }
+
+    async fn search(&self, query: &SearchQuery) -> Result<Vec<ProjectHit>> {
+        let mut facets = vec![
+            vec!["project_type:mod".to_string()],
+            vec![format!("versions:{}", query.mc_version)],
+        ];
+        if let Some(loader) = loader_slug(&query.loader) {
+            facets.push(vec![format!("categories:{}", loader)]);
+        }
+
+        let response: SearchResponse = self
+            .get_json(
+                "/search",
+                &[
+                    ("query", query.query.clone()),
+                    ("facets", serde_json::to_string(&facets)?),
+                    ("limit", query.limit.to_string()),
+                ],
+            )
+            .await?;
+
+        Ok(response
+            .hits
+            .into_iter()
+            .map(|hit| ProjectHit {
+                id: hit.project_id,
+                slug: hit.slug,
+                title: hit.title,
+                description: hit.description,
+                author: hit.author,
+                downloads: hit.downloads,
+                icon_url: hit.icon_url,
+            })
+            .collect())
+    }
+
+    async fn latest_file(
+        &self,
+        project: &str,
+        mc_version: &str,
+        loader: &ModLoader,
+    ) -> Result<Option<ModFile>> {
+        let versions = self.project_versions(project, mc_version, loader).await?;
+        Ok(versions
+            .into_iter()
+            .next()
+            .and_then(ModrinthVersion::into_mod_file))
+    }
+
+    async fn file(&self, version_id: &str) -> Result<ModFile> {
+        let version: ModrinthVersion = self
+            .get_json(&format!("/version/{}", version_id), &[])
+            .await?;
+        version
+            .into_mod_file()
+            .context(format!("Modrinth version {} has no files", version_id))
+    }
+
+    async fn identify(&self, jars: &[PathBuf]) -> Result<HashMap<PathBuf, ModFile>> {
+        let mut by_hash: HashMap<String, PathBuf> = HashMap::new();
+        for jar in jars {
+            if let Ok(hash) = sha1_file(jar) {
+                by_hash.insert(hash, jar.clone());
+            }
+        }
+
+        let hashes: Vec<String> = by_hash.keys().cloned().collect();
+        let versions = self.versions_by_hashes(&hashes, "sha1").await?;
+
+        Ok(versions
+            .into_iter()
+            .filter_map(|(hash, version)| {
+                let jar = by_hash.get(&hash)?.clone();
+                Some((jar, version.into_mod_file()?))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mods::mock::MockServer;
+
+    fn version_json(id: &str, project: &str, filename: &str, sha1: &str, deps: &str) -> String {
+        format!(
+            r#"{{
+                "id": "{id}",
+                "project_id": "{project}",
+                "version_number": "1.0.0",
+                "files": [{{
+                    "hashes": {{ "sha1": "{sha1}", "sha512": "" }},
+                    "url": "URL/files/{filename}",
+                    "filename": "{filename}",
+                    "primary": true,
+                    "size": 4
+                }}],
+                "dependencies": [{deps}]
+            }}"#
+        )
+    }
+
+    #[tokio::test]
+    async fn test_search_sends_facets() {
+        let body = r#"{ "hits": [{
+            "project_id": "AANobbMI",
+            "slug": "sodium",
+            "title": "Sodium",
+            "description": "Rendering engine",
+            "author": "jellysquid3",
+            "downloads": 1000,
+            "icon_url": null
+        }] }"#;
+        let server = MockServer::start(vec![(
+            "GET",
+            "/search".to_string(),
+            200,
+            body.as_bytes().to_vec(),
+        )])
+        .await;
+
+        let client = ModrinthClient::with_base_url(&server.base_url);
+        let hits = client
+            .search(&SearchQuery {
+                query: "sodium".to_string(),
+                mc_version: "1.20.4".to_string(),
+                loader: ModLoader::Fabric,
+                limit: 10,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].id, "AANobbMI");
+        assert_eq!(hits[0].title, "Sodium");
+
+        let request = &server.request_lines()[0];
+        assert!(request.contains("categories%3Afabric"), "{}", request);
+        assert!(request.contains("versions%3A1.20.4"), "{}", request);
+    }
+
+    #[tokio::test]
+    async fn test_latest_file_keeps_required_dependencies() {
+        let body = format!(
+            "[{}]",
+            version_json(
+                "v1",
+                "iris",
+                "iris.jar",
+                "abc",
+                r#"{ "project_id": "sodium", "version_id": null, "dependency_type": "required" },
+                   { "project_id": "modmenu", "version_id": null, "dependency_type": "optional" }"#,
+            )
+        );
+        let server = MockServer::start(vec![(
+            "GET",
+            "/project/iris/version".to_string(),
+            200,
+            body.into_bytes(),
+        )])
+        .await;
+
+        let client = ModrinthClient::with_base_url(&server.base_url);
+        let file = client
+            .latest_file("iris", "1.20.4", &ModLoader::Fabric)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(file.filename, "iris.jar");
+        assert_eq!(file.hashes.sha1.as_deref(), Some("abc"));
+        assert_eq!(
+            file.dependencies,
+            vec![FileDependency {
+                project_id: "sodium".to_string(),
+                version_id: None,
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_http_error() {
+        let server = MockServer::start(vec![]).await;
+        let client = ModrinthClient::with_base_url(&server.base_url);
+        assert!(client.file("missing").await.is_err());
+    }
+}
@@ -0,0 +1,152 @@
+//! Mod source abstraction
+//!
+//! Common types for online mod sources (Modrinth, CurseForge) so search,
+//! install and identification work the same way for each.
+
+use crate::core::instance::ModLoader;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+/// Supported mod sources
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ModSource {
+    #[default]
+    Modrinth,
+    CurseForge,
+}
+
+impl std::fmt::Display for ModSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ModSource::Modrinth => write!(f, "Modrinth"),
+            ModSource::CurseForge => write!(f, "CurseForge"),
+        }
+    }
+}
+
+impl std::str::FromStr for ModSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "modrinth" | "mr" => Ok(ModSource::Modrinth),
+            "curseforge" | "cf" => Ok(ModSource::CurseForge),
+            _ => anyhow::bail!("Unknown mod source: {}. Use modrinth or curseforge.", s),
+        }
+    }
+}
+
+/// Search parameters
+#[derive(Debug, Clone)]
+pub struct SearchQuery {
+    pub query: String,
+    /// Only projects with files for this Minecraft version
+    pub mc_version: String,
+    /// Only projects with files for this loader
+    pub loader: ModLoader,
+    pub limit: u32,
+}
+
+/// A project found by a search
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectHit {
+    /// Provider-specific project ID
+    pub id: String,
+    pub slug: String,
+    pub title: String,
+    pub description: String,
+    pub author: String,
+    pub downloads: u64,
+    pub icon_url: Option<String>,
+}
+
+/// Hashes published for a file
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileHashes {
+    pub sha1: Option<String>,
+    pub sha512: Option<String>,
+}
+
+/// A dependency of a published file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileDependency {
+    /// Empty when the source names only the version
+    pub project_id: String,
+    /// Exact file/version wanted, if pinned
+    pub version_id: Option<String>,
+}
+
+/// A downloadable mod file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModFile {
+    pub project_id: String,
+    /// Provider-specific version/file ID
+    pub version_id: String,
+    pub version_number: String,
+    pub filename: String,
+    /// Download URL; `None` when the author disallows third-party downloads
+    pub url: Option<String>,
+    /// Page where the file can be downloaded by hand
+    pub page_url: Option<String>,
+    pub size: u64,
+    pub hashes: FileHashes,
+    /// Required dependencies
+    pub dependencies: Vec<FileDependency>,
+}
+
+/// An online source of mods
+pub trait ModProvider {
+    /// Provider name for messages
+    fn name(&self) -> &'static str;
+
+    /// Search projects compatible with a game version and loader
+    fn search(&self, query: &SearchQuery) -> impl Future<Output = Result<Vec<ProjectHit>>> + Send;
+
+    /// Newest file of a project for a game version and loader
+    fn latest_file(
+        &self,
+        project: &str,
+        mc_version: &str,
+        loader: &ModLoader,
+    ) -> impl Future<Output = Result<Option<ModFile>>> + Send;
+
+    /// A specific file by its version/file ID
+    fn file(&self, version_id: &str) -> impl Future<Output = Result<ModFile>> + Send;
+
+    /// Identify local jars, returning the published file for each recognised one
+    fn identify(
+        &self,
+        jars: &[PathBuf],
+    ) -> impl Future<Output = Result<HashMap<PathBuf, ModFile>>> + Send;
+}
+
+/// Loader name as used by mod sites
+pub fn loader_slug(loader: &ModLoader) -> Option<&'static str> {
+    match loader {
+        ModLoader::Vanilla => None,
+        ModLoader::Fabric => Some("fabric"),
+        ModLoader::Forge => Some("forge"),
+        ModLoader::Quilt => Some("quilt"),
+        ModLoader::NeoForge => Some("neoforge"),
+    }
+}
+
+/// Enabled and disabled jars in a mods directory
+pub fn mod_jars(mods_dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(mods_dir) else {
+        return Vec::new();
+    };
+    let mut jars: Vec<PathBuf> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| {
+            let name = p.to_string_lossy();
+            name.ends_with(".jar") || name.ends_with(".jar.disabled")
+        })
+        .collect();
+    jars.sort();
+    jars
+}
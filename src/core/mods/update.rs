@@ -0,0 +1,205 @@
+//! Mod update checking
+//!
+//! Finds newer versions of installed mods by looking up their file hashes
+//! on Modrinth, and applies updates while keeping the old jar for rollback.
+
+use super::ModInfo;
+use super::install::download_mod_file;
+use super::modrinth::ModrinthClient;
+use super::provider::ModFile;
+use crate::core::instance::ModLoader;
+use crate::util::hash::sha512_file;
+use anyhow::{Context, Result};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// An available update for an installed mod
+#[derive(Debug, Clone)]
+pub struct ModUpdate {
+    /// Installed jar
+    pub path: PathBuf,
+    pub name: String,
+    pub current_version: String,
+    /// Newer file to install
+    pub file: ModFile,
+}
+
+/// Check enabled mods for newer versions matching the game version and loader
+///
+/// Disabled jars are skipped; they are usually rollback copies of older versions.
+pub async fn check_updates(
+    client: &ModrinthClient,
+    mods: &[ModInfo],
+    mc_version: &str,
+    loader: &ModLoader,
+) -> Result<Vec<ModUpdate>> {
+    let mut by_hash: HashMap<String, &ModInfo> = HashMap::new();
+    for mod_info in mods.iter().filter(|m| m.enabled) {
+        match sha512_file(&mod_info.path) {
+            Ok(hash) => {
+                by_hash.insert(hash, mod_info);
+            }
+            Err(e) => tracing::warn!("Failed to hash {}: {}", mod_info.filename, e),
+        }
+    }
+
+    let hashes: Vec<String> = by_hash.keys().cloned().collect();
+    let latest = client
+        .latest_versions_by_hashes(&hashes, "sha512", mc_version, loader)
+        .await?;
+
+    let mut updates: Vec<ModUpdate> = latest
+        .into_iter()
+        .filter_map(|(hash, version)| {
+            let mod_info = by_hash.get(&hash)?;
+            let file = version.into_mod_file()?;
+            // The newest version may be the one already installed
+            if file.hashes.sha512.as_deref() == Some(hash.as_str()) {
+                return None;
+            }
+            Some(ModUpdate {
+                path: mod_info.path.clone(),
+                name: mod_info.name.clone(),
+                current_version: mod_info.version.clone(),
+                file,
+            })
+        })
+        .collect();
+
+    updates.sort_by_key(|u| u.name.to_lowercase());
+    Ok(updates)
+}
+
+/// Install an update, keeping the old jar as `.jar.disabled`
+pub async fn apply_update(
+    client: &reqwest::Client,
+    update: &ModUpdate,
+    mods_dir: &Path,
+) -> Result<PathBuf> {
+    let backup = disabled_path(&update.path);
+    if backup.exists() {
+        fs::remove_file(&backup).context("Failed to remove previous rollback copy")?;
+    }
+    fs::rename(&update.path, &backup).context("Failed to disable old mod version")?;
+
+    match download_mod_file(client, &update.file, mods_dir).await {
+        Ok(path) => Ok(path),
+        Err(e) => {
+            // Put the old version back so the instance still works
+            if let Err(restore) = fs::rename(&backup, &update.path) {
+                tracing::error!("Failed to restore {}: {}", update.path.display(), restore);
+            }
+            Err(e)
+        }
+    }
+}
+
+/// Path of the disabled copy of a jar
+fn disabled_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".disabled");
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::mods::ModManager;
+    use crate::core::mods::mock::MockServer;
+    use crate::util::hash::{sha1_bytes, sha512_bytes};
+    use crate::util::temp::TempDir;
+
+    fn update_json(base: &str, old_hash: &str, new_jar: &[u8]) -> String {
+        format!(
+            r#"{{ "{old_hash}": {{
+                "id": "v2",
+                "project_id": "sodium",
+                "version_number": "0.5.8",
+                "files": [{{
+                    "hashes": {{ "sha1": "{sha1}", "sha512": "{sha512}" }},
+                    "url": "{base}/files/sodium-0.5.8.jar",
+                    "filename": "sodium-0.5.8.jar",
+                    "primary": true,
+                    "size": {size}
+                }}],
+                "dependencies": []
+            }} }}"#,
+            sha1 = sha1_bytes(new_jar),
+            sha512 = sha512_bytes(new_jar),
+            size = new_jar.len()
+        )
+    }
+
+    #[tokio::test]
+    async fn test_check_and_apply_update() {
+        let dir = TempDir::new("update");
+
+        let old_jar = b"old sodium".to_vec();
+        let new_jar = b"new sodium".to_vec();
+        fs::write(dir.join("sodium-0.5.3.jar"), &old_jar).unwrap();
+        let old_hash = sha512_bytes(&old_jar);
+
+        let server = MockServer::start_with(|base| {
+            vec![
+                (
+                    "POST",
+                    "/version_files/update".to_string(),
+                    200,
+                    update_json(base, &old_hash, &new_jar).into_bytes(),
+                ),
+                ("GET", "/files/".to_string(), 200, new_jar.clone()),
+            ]
+        })
+        .await;
+        let client = ModrinthClient::with_base_url(&server.base_url);
+
+        let mods = ModManager::new(&dir).list_mods().unwrap();
+        let updates = check_updates(&client, &mods, "1.20.4", &ModLoader::Fabric)
+            .await
+            .unwrap();
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].file.version_number, "0.5.8");
+
+        let body = server.requests.lock().unwrap()[0].1.clone();
+        assert!(body.contains(r#""algorithm":"sha512""#), "{}", body);
+        assert!(body.contains(r#""loaders":["fabric"]"#), "{}", body);
+        assert!(body.contains(r#""game_versions":["1.20.4"]"#), "{}", body);
+
+        apply_update(&reqwest::Client::new(), &updates[0], &dir)
+            .await
+            .unwrap();
+        assert_eq!(fs::read(dir.join("sodium-0.5.8.jar")).unwrap(), new_jar);
+        assert_eq!(
+            fs::read(dir.join("sodium-0.5.3.jar.disabled")).unwrap(),
+            old_jar
+        );
+        assert!(!dir.join("sodium-0.5.3.jar").exists());
+    }
+
+    #[tokio::test]
+    async fn test_up_to_date_mod_is_not_listed() {
+        let dir = TempDir::new("update-none");
+
+        let jar = b"current sodium".to_vec();
+        fs::write(dir.join("sodium.jar"), &jar).unwrap();
+        let hash = sha512_bytes(&jar);
+
+        let server = MockServer::start_with(|base| {
+            vec![(
+                "POST",
+                "/version_files/update".to_string(),
+                200,
+                update_json(base, &hash, &jar).into_bytes(),
+            )]
+        })
+        .await;
+        let client = ModrinthClient::with_base_url(&server.base_url);
+
+        let mods = ModManager::new(&dir).list_mods().unwrap();
+        let updates = check_updates(&client, &mods, "1.20.4", &ModLoader::Fabric)
+            .await
+            .unwrap();
+        assert!(updates.is_empty());
+    }
+}
@@ -0,0 +1,527 @@
+//! Instance snapshots
+//!
+//! Named snapshots of `mods/`, `config/` and `instance.toml` for rolling back
+//! pack changes. File contents are stored once as SHA256-addressed blobs
+//! under `<instance>/snapshots/objects/`, so repeated snapshots are cheap.
+
+use crate::core::instance::{Instance, InstanceManager, running_game_pid};
+use crate::util::hash::sha256_bytes;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+/// Game directory folders captured in a snapshot
+const TRACKED_DIRS: &[&str] = &["mods", "config"];
+
+/// Instance config captured in a snapshot
+const INSTANCE_FILE: &str = "instance.toml";
+
+/// A stored file in a snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotFile {
+    /// SHA256 of the contents
+    pub hash: String,
+    pub size: u64,
+}
+
+/// A named snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub name: String,
+    pub created_at: DateTime<Utc>,
+    /// Files keyed by `/`-separated path (`instance.toml`, `mods/x.jar`, ...)
+    pub files: BTreeMap<String, SnapshotFile>,
+}
+
+impl Snapshot {
+    /// Total size of the files in the snapshot
+    pub fn size(&self) -> u64 {
+        self.files.values().map(|f| f.size).sum()
+    }
+}
+
+/// Differences between two file sets
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SnapshotDiff {
+    /// Only in the newer set
+    pub added: Vec<String>,
+    /// Only in the older set
+    pub removed: Vec<String>,
+    /// In both with different contents
+    pub changed: Vec<String>,
+}
+
+impl SnapshotDiff {
+    fn between(old: &BTreeMap<String, SnapshotFile>, new: &BTreeMap<String, SnapshotFile>) -> Self {
+        let mut diff = Self::default();
+        for (path, file) in new {
+            match old.get(path) {
+                None => diff.added.push(path.clone()),
+                Some(old_file) if old_file.hash != file.hash => diff.changed.push(path.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = old
+            .keys()
+            .filter(|p| !new.contains_key(*p))
+            .cloned()
+            .collect();
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Snapshot manager for one instance
+pub struct SnapshotManager {
+    instance_dir: PathBuf,
+}
+
+impl SnapshotManager {
+    /// Create a manager for an instance directory
+    pub fn new(instance_dir: &Path) -> Self {
+        Self {
+            instance_dir: instance_dir.to_path_buf(),
+        }
+    }
+
+    fn snapshots_dir(&self) -> PathBuf {
+        self.instance_dir.join("snapshots")
+    }
+
+    fn objects_dir(&self) -> PathBuf {
+        self.snapshots_dir().join("objects")
+    }
+
+    fn object_path(&self, hash: &str) -> PathBuf {
+        self.objects_dir().join(&hash[..2]).join(hash)
+    }
+
+    fn manifest_path(&self, name: &str) -> Result<PathBuf> {
+        Ok(self
+            .snapshots_dir()
+            .join(format!("{}.json", snapshot_name(name)?)))
+    }
+
+    /// Disk location of a snapshot path
+    fn file_path(&self, path: &str) -> PathBuf {
+        if path == INSTANCE_FILE {
+            self.instance_dir.join(INSTANCE_FILE)
+        } else {
+            self.instance_dir.join(".minecraft").join(path)
+        }
+    }
+
+    /// Hash the tracked files as they are now, storing blobs when `store` is set
+    fn scan(&self, store: bool) -> Result<BTreeMap<String, SnapshotFile>> {
+        let mut paths = vec![INSTANCE_FILE.to_string()];
+        let game_dir = self.instance_dir.join(".minecraft");
+        for dir in TRACKED_DIRS {
+            collect_files(&game_dir, &game_dir.join(dir), &mut paths)?;
+        }
+
+        let mut files = BTreeMap::new();
+        for path in paths {
+            let disk_path = self.file_path(&path);
+            if !disk_path.is_file() {
+                continue;
+            }
+            let bytes = std::fs::read(&disk_path)
+                .with_context(|| format!("Failed to read {}", disk_path.display()))?;
+            let hash = sha256_bytes(&bytes);
+            if store {
+                self.store_object(&hash, &bytes)?;
+            }
+            files.insert(
+                path,
+                SnapshotFile {
+                    hash,
+                    size: bytes.len() as u64,
+                },
+            );
+        }
+        Ok(files)
+    }
+
+    fn store_object(&self, hash: &str, bytes: &[u8]) -> Result<()> {
+        let path = self.object_path(hash);
+        if path.exists() {
+            return Ok(());
+        }
+        std::fs::create_dir_all(path.parent().unwrap())?;
+        let tmp = path.with_extension("part");
+        std::fs::write(&tmp, bytes)?;
+        std::fs::rename(&tmp, &path).context("Failed to store snapshot object")
+    }
+
+    /// Take a snapshot of the current mods, config and instance settings
+    pub fn create(&self, name: &str) -> Result<Snapshot> {
+        let manifest = self.manifest_path(name)?;
+        if manifest.exists() {
+            anyhow::bail!("Snapshot '{}' already exists", name);
+        }
+
+        let snapshot = Snapshot {
+            name: name.to_string(),
+            created_at: Utc::now(),
+            files: self.scan(true)?,
+        };
+        std::fs::write(&manifest, serde_json::to_string_pretty(&snapshot)?)
+            .context("Failed to save snapshot")?;
+
+        tracing::info!("Created snapshot {} ({} files)", name, snapshot.files.len());
+        Ok(snapshot)
+    }
+
+    /// Load a snapshot by name
+    pub fn load(&self, name: &str) -> Result<Snapshot> {
+        let content = std::fs::read_to_string(self.manifest_path(name)?)
+            .with_context(|| format!("Snapshot '{}' not found", name))?;
+        let snapshot: Snapshot =
+            serde_json::from_str(&content).context("Failed to parse snapshot")?;
+        if let Some((path, _)) = snapshot.files.iter().find(|(_, f)| !is_sha256(&f.hash)) {
+            anyhow::bail!("Snapshot '{}' has an invalid hash for {}", name, path);
+        }
+        Ok(snapshot)
+    }
+
+    /// All snapshots, newest first
+    pub fn list(&self) -> Result<Vec<Snapshot>> {
+        let dir = self.snapshots_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut snapshots = Vec::new();
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let parsed = std::fs::read_to_string(&path)
+                .map_err(anyhow::Error::from)
+                .and_then(|c| Ok(serde_json::from_str::<Snapshot>(&c)?));
+            match parsed {
+                Ok(snapshot) => snapshots.push(snapshot),
+                Err(e) => tracing::warn!("Failed to read snapshot {}: {}", path.display(), e),
+            }
+        }
+        snapshots.sort_by_key(|s| std::cmp::Reverse(s.created_at));
+        Ok(snapshots)
+    }
+
+    /// Changes from snapshot `from` to snapshot `to`, or to the current files
+    pub fn diff(&self, from: &str, to: Option<&str>) -> Result<SnapshotDiff> {
+        let old = self.load(from)?.files;
+        let new = match to {
+            Some(to) => self.load(to)?.files,
+            None => self.scan(false)?,
+        };
+        Ok(SnapshotDiff::between(&old, &new))
+    }
+
+    /// Put mods, config and instance settings back to a snapshot
+    ///
+    /// Every blob is checked before anything is written. Files in the
+    /// tracked folders that are not in the snapshot are removed. Only the
+    /// loader, Java and game settings come back from the saved
+    /// `instance.toml`; name, group and play stats stay as they are now.
+    pub fn restore(&self, name: &str) -> Result<SnapshotDiff> {
+        if let Some(pid) = running_game_pid(&self.instance_dir) {
+            anyhow::bail!(
+                "Minecraft is running for this instance (PID {}). Close the game first.",
+                pid
+            );
+        }
+
+        let snapshot = self.load(name)?;
+        for (path, file) in &snapshot.files {
+            if !self.object_path(&file.hash).exists() {
+                anyhow::bail!("Snapshot '{}' is missing data for {}", name, path);
+            }
+            safe_relative(path)?;
+        }
+
+        let current = self.scan(false)?;
+        let diff = SnapshotDiff::between(&current, &snapshot.files);
+
+        for path in diff.changed.iter().chain(&diff.added) {
+            let file = &snapshot.files[path];
+            let bytes = std::fs::read(self.object_path(&file.hash))?;
+            if sha256_bytes(&bytes) != file.hash {
+                anyhow::bail!("Snapshot data for {} is corrupted", path);
+            }
+            if path == INSTANCE_FILE {
+                self.restore_instance(&bytes)?;
+                continue;
+            }
+            let dest = self.file_path(path);
+            if let Some(parent) = dest.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            replace_file(&dest, &bytes)
+                .with_context(|| format!("Failed to restore {}", dest.display()))?;
+        }
+        for path in &diff.removed {
+            std::fs::remove_file(self.file_path(path))
+                .with_context(|| format!("Failed to remove {}", path))?;
+        }
+
+        tracing::info!("Restored snapshot {}", name);
+        Ok(diff)
+    }
+
+    /// Merge the saved instance settings into the current `instance.toml`
+    fn restore_instance(&self, bytes: &[u8]) -> Result<()> {
+        let saved: Instance = toml::from_str(std::str::from_utf8(bytes)?)
+            .context("Snapshot has an unreadable instance.toml")?;
+        let (Some(instances_dir), Some(id)) = (
+            self.instance_dir.parent(),
+            self.instance_dir.file_name().and_then(|n| n.to_str()),
+        ) else {
+            anyhow::bail!(
+                "Invalid instance directory: {}",
+                self.instance_dir.display()
+            );
+        };
+
+        let manager = InstanceManager::with_dir(instances_dir);
+        let mut instance = manager.load_id(id)?;
+        instance.info.loader = saved.info.loader;
+        instance.info.loader_version = saved.info.loader_version;
+        instance.java = saved.java;
+        instance.game = saved.game;
+        manager.save(&instance)
+    }
+
+    /// Delete a snapshot and any blobs no other snapshot uses
+    pub fn delete(&self, name: &str) -> Result<usize> {
+        let manifest = self.manifest_path(name)?;
+        if !manifest.exists() {
+            anyhow::bail!("Snapshot '{}' not found", name);
+        }
+        std::fs::remove_file(&manifest)?;
+        self.collect_garbage()
+    }
+
+    /// Remove blobs not referenced by any snapshot, returning how many
+    fn collect_garbage(&self) -> Result<usize> {
+        let referenced: HashSet<String> = self
+            .list()?
+            .into_iter()
+            .flat_map(|s| s.files.into_values().map(|f| f.hash))
+            .collect();
+
+        let objects = self.objects_dir();
+        if !objects.exists() {
+            return Ok(0);
+        }
+        let mut removed = 0;
+        for prefix in std::fs::read_dir(&objects)? {
+            let prefix = prefix?.path();
+            for object in std::fs::read_dir(&prefix)? {
+                let object = object?;
+                if !referenced.contains(&*object.file_name().to_string_lossy()) {
+                    std::fs::remove_file(object.path())?;
+                    removed += 1;
+                }
+            }
+            let _ = std::fs::remove_dir(&prefix);
+        }
+        Ok(removed)
+    }
+}
+
+/// Reject snapshot names that are not plain file names
+fn snapshot_name(name: &str) -> Result<&str> {
+    let is_plain = !name.trim().is_empty()
+        && name != "objects"
+        && !name.starts_with('.')
+        && !name.contains(['/', '\\', ':']);
+    if !is_plain {
+        anyhow::bail!("Invalid snapshot name: {}", name);
+    }
+    Ok(name)
+}
+
+/// Whether a string looks like a hex SHA256
+fn is_sha256(hash: &str) -> bool {
+    hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Write a file through a temporary sibling and rename it into place
+///
+/// Restored jars may be hard links shared with other instances, so the old
+/// inode is replaced rather than written through.
+fn replace_file(dest: &Path, bytes: &[u8]) -> Result<()> {
+    let mut tmp_name = dest.file_name().unwrap_or_default().to_os_string();
+    tmp_name.push(".restore");
+    let tmp = dest.with_file_name(tmp_name);
+    std::fs::write(&tmp, bytes)?;
+    std::fs::rename(&tmp, dest)?;
+    Ok(())
+}
+
+/// Reject snapshot paths that would escape the instance
+fn safe_relative(path: &str) -> Result<()> {
+    let safe = !path.contains('\\')
+        && Path::new(path)
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)));
+    if !safe {
+        anyhow::bail!("Unsafe path in snapshot: {}", path);
+    }
+    Ok(())
+}
+
+fn collect_files(root: &Path, dir: &Path, paths: &mut Vec<String>) -> Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if entry.file_type()?.is_dir() {
+            collect_files(root, &path, paths)?;
+        } else if entry.file_type()?.is_file() {
+            let relative = path.strip_prefix(root)?;
+            paths.push(crate::core::modpack::archive_path(relative));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::instance::ModLoader;
+    use crate::util::temp::TempDir;
+
+    fn instance_dir(tag: &str) -> (TempDir, PathBuf) {
+        let root = TempDir::new(tag);
+        let mut instance = InstanceManager::with_dir(&root)
+            .create("Pack", "1.20.1", ModLoader::Fabric, None)
+            .unwrap();
+        instance.java.max_memory = "4G".to_string();
+        InstanceManager::with_dir(&root).save(&instance).unwrap();
+
+        let dir = root.join(&instance.info.id);
+        std::fs::create_dir_all(dir.join(".minecraft/mods")).unwrap();
+        std::fs::create_dir_all(dir.join(".minecraft/config")).unwrap();
+        std::fs::write(dir.join(".minecraft/mods/sodium-0.5.3.jar"), b"sodium old").unwrap();
+        std::fs::write(dir.join(".minecraft/mods/lithium.jar"), b"lithium").unwrap();
+        std::fs::write(dir.join(".minecraft/config/sodium.json"), b"{}").unwrap();
+        (root, dir)
+    }
+
+    #[test]
+    fn test_snapshot_diff_and_restore() {
+        let (_root, dir) = instance_dir("snapshot-restore");
+        let manager = SnapshotManager::new(&dir);
+        let snapshot = manager.create("before update").unwrap();
+        assert_eq!(snapshot.files.len(), 4);
+        assert!(manager.create("before update").is_err());
+
+        // Simulate an update gone wrong
+        let mods = dir.join(".minecraft/mods");
+        std::fs::remove_file(mods.join("sodium-0.5.3.jar")).unwrap();
+        std::fs::write(mods.join("sodium-0.5.8.jar"), b"sodium new").unwrap();
+        std::fs::write(dir.join(".minecraft/config/sodium.json"), b"{broken}").unwrap();
+        set_max_memory(&dir, "8G");
+
+        let diff = manager.diff("before update", None).unwrap();
+        assert_eq!(diff.added, vec!["mods/sodium-0.5.8.jar"]);
+        assert_eq!(diff.removed, vec!["mods/sodium-0.5.3.jar"]);
+        assert_eq!(diff.changed, vec!["config/sodium.json", "instance.toml"]);
+
+        manager.restore("before update").unwrap();
+        assert!(manager.diff("before update", None).unwrap().is_empty());
+        assert_eq!(
+            std::fs::read(mods.join("sodium-0.5.3.jar")).unwrap(),
+            b"sodium old"
+        );
+        assert!(!mods.join("sodium-0.5.8.jar").exists());
+        assert_eq!(load(&dir).java.max_memory, "4G");
+    }
+
+    fn load(dir: &Path) -> Instance {
+        let manager = InstanceManager::with_dir(dir.parent().unwrap());
+        manager
+            .load_id(dir.file_name().unwrap().to_str().unwrap())
+            .unwrap()
+    }
+
+    fn set_max_memory(dir: &Path, memory: &str) {
+        let mut instance = load(dir);
+        instance.java.max_memory = memory.to_string();
+        InstanceManager::with_dir(dir.parent().unwrap())
+            .save(&instance)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_restore_keeps_current_instance_info() {
+        let (_root, dir) = instance_dir("snapshot-info");
+        let manager = SnapshotManager::new(&dir);
+        manager.create("base").unwrap();
+
+        let instances = InstanceManager::with_dir(dir.parent().unwrap());
+        let id = load(&dir).info.id;
+        instances
+            .record_session(&id, std::time::Duration::from_secs(600))
+            .unwrap();
+        instances.rename(&id, "Renamed").unwrap();
+        set_max_memory(&dir, "8G");
+
+        manager.restore("base").unwrap();
+        let restored = load(&dir);
+        assert_eq!(restored.java.max_memory, "4G");
+        assert_eq!(restored.info.name, "Renamed");
+        assert_eq!(restored.info.launch_count, 1);
+        assert_eq!(restored.info.playtime_seconds, 600);
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_hash() {
+        let (_root, dir) = instance_dir("snapshot-hash");
+        let manager = SnapshotManager::new(&dir);
+        manager.create("base").unwrap();
+
+        let path = manager.manifest_path("base").unwrap();
+        let mut snapshot = manager.load("base").unwrap();
+        snapshot.files.get_mut("mods/lithium.jar").unwrap().hash = "..".to_string();
+        std::fs::write(&path, serde_json::to_string(&snapshot).unwrap()).unwrap();
+        assert!(manager.load("base").is_err());
+        assert!(manager.restore("base").is_err());
+    }
+
+    #[test]
+    fn test_snapshots_share_objects() {
+        let (_root, dir) = instance_dir("snapshot-dedup");
+        let manager = SnapshotManager::new(&dir);
+        manager.create("one").unwrap();
+        std::fs::write(dir.join(".minecraft/config/new.json"), b"new").unwrap();
+        manager.create("two").unwrap();
+
+        let count_objects = || {
+            std::fs::read_dir(dir.join("snapshots/objects"))
+                .unwrap()
+                .map(|d| std::fs::read_dir(d.unwrap().path()).unwrap().count())
+                .sum::<usize>()
+        };
+        // 4 files + 1 new one, stored once each
+        assert_eq!(count_objects(), 5);
+
+        let diff = manager.diff("one", Some("two")).unwrap();
+        assert_eq!(diff.added, vec!["config/new.json"]);
+
+        assert_eq!(manager.delete("two").unwrap(), 1);
+        assert_eq!(count_objects(), 4);
+        assert_eq!(manager.list().unwrap().len(), 1);
+        assert!(manager.create("../escape").is_err());
+    }
+}
@@ -0,0 +1,322 @@
+//! Instance templates
+//!
+//! Named defaults for new instances: memory, JVM arguments, resolution, a
+//! starting mod list and an `options.txt` baseline. Templates are stored as
+//! TOML files under `~/.glauncher/templates/`.
+
+use crate::config;
+use crate::core::instance::{
+    GameConfig, Instance, InstanceManager, ModLoader, slugify, validate_name,
+};
+use crate::core::mods::{ModFile, ModProvider, install_project, mod_jars};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Game options file seeded from a template
+const OPTIONS_FILE: &str = "options.txt";
+
+/// Defaults applied to a new instance
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct InstanceTemplate {
+    pub name: String,
+    #[serde(default)]
+    pub min_memory: String,
+    #[serde(default)]
+    pub max_memory: String,
+    #[serde(default)]
+    pub jvm_args: Vec<String>,
+    #[serde(default)]
+    pub game: GameConfig,
+    /// Modrinth project IDs or slugs installed into new instances
+    #[serde(default)]
+    pub mods: Vec<String>,
+    /// Contents of `options.txt`; empty for none
+    #[serde(default)]
+    pub options: String,
+}
+
+impl InstanceTemplate {
+    /// Capture an instance's settings and `options.txt`
+    ///
+    /// The mod list is left empty; fill it with [`template_mods`].
+    pub fn from_instance(name: &str, instance: &Instance, game_dir: &Path) -> Result<Self> {
+        let options_path = game_dir.join(OPTIONS_FILE);
+        let options = if options_path.is_file() {
+            std::fs::read_to_string(&options_path)
+                .with_context(|| format!("Failed to read {}", options_path.display()))?
+        } else {
+            String::new()
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            min_memory: instance.java.min_memory.clone(),
+            max_memory: instance.java.max_memory.clone(),
+            jvm_args: instance.java.extra_args.clone(),
+            game: instance.game.clone(),
+            mods: Vec::new(),
+            options,
+        })
+    }
+}
+
+/// Outcome of installing a template's mods
+#[derive(Debug, Default)]
+pub struct TemplateReport {
+    /// Files downloaded into the mods folder
+    pub installed: Vec<ModFile>,
+    /// Files that must be downloaded by hand
+    pub manual: Vec<ModFile>,
+    /// Projects that could not be installed, with the reason
+    pub failed: Vec<(String, String)>,
+}
+
+/// Template storage
+pub struct TemplateManager {
+    templates_dir: PathBuf,
+}
+
+impl Default for TemplateManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TemplateManager {
+    pub fn new() -> Self {
+        Self::with_dir(&config::config_dir().join("templates"))
+    }
+
+    /// Create a manager for another templates directory
+    pub fn with_dir(templates_dir: &Path) -> Self {
+        Self {
+            templates_dir: templates_dir.to_path_buf(),
+        }
+    }
+
+    fn template_path(&self, name: &str) -> Result<PathBuf> {
+        validate_name(name).context("Invalid template name")?;
+        Ok(self.templates_dir.join(format!("{}.toml", slugify(name))))
+    }
+
+    /// All templates, sorted by name
+    pub fn list(&self) -> Vec<InstanceTemplate> {
+        let Ok(entries) = std::fs::read_dir(&self.templates_dir) else {
+            return Vec::new();
+        };
+        let mut templates: Vec<InstanceTemplate> = entries
+            .flatten()
+            .map(|e| e.path())
+            .filter(|p| p.extension().is_some_and(|e| e == "toml"))
+            .filter_map(|p| read_template(&p).ok())
+            .collect();
+        templates.sort_by_key(|t| t.name.to_lowercase());
+        templates
+    }
+
+    /// Load a template by name
+    pub fn load(&self, name: &str) -> Result<InstanceTemplate> {
+        let path = self.template_path(name)?;
+        if !path.is_file() {
+            anyhow::bail!("Template '{}' not found", name);
+        }
+        read_template(&path)
+    }
+
+    /// Save a template, replacing one with the same name
+    pub fn save(&self, template: &InstanceTemplate) -> Result<()> {
+        let path = self.template_path(&template.name)?;
+        if path.is_file()
+            && let Ok(existing) = read_template(&path)
+            && existing.name != template.name
+        {
+            anyhow::bail!(
+                "Template name '{}' clashes with '{}'",
+                template.name,
+                existing.name
+            );
+        }
+
+        std::fs::create_dir_all(&self.templates_dir)?;
+        let content = toml::to_string_pretty(template)?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        Ok(())
+    }
+
+    /// Delete a template
+    pub fn delete(&self, name: &str) -> Result<()> {
+        let path = self.template_path(name)?;
+        if !path.is_file() {
+            anyhow::bail!("Template '{}' not found", name);
+        }
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
+
+fn read_template(path: &Path) -> Result<InstanceTemplate> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+impl InstanceManager {
+    /// Apply a template's settings and `options.txt` to an instance
+    ///
+    /// Mods are installed separately with [`install_template_mods`].
+    pub fn apply_template(
+        &self,
+        instance: &mut Instance,
+        template: &InstanceTemplate,
+    ) -> Result<()> {
+        let has_java = !template.min_memory.is_empty()
+            || !template.max_memory.is_empty()
+            || !template.jvm_args.is_empty();
+        if has_java {
+            instance.java.min_memory = template.min_memory.clone();
+            instance.java.max_memory = template.max_memory.clone();
+            instance.java.extra_args = template.jvm_args.clone();
+        }
+        instance.game = template.game.clone();
+        self.save(instance)?;
+
+        if !template.options.is_empty() {
+            let game_dir = self.get_game_dir(&instance.info.id);
+            std::fs::create_dir_all(&game_dir)?;
+            std::fs::write(game_dir.join(OPTIONS_FILE), &template.options)
+                .context("Failed to write options.txt")?;
+        }
+        Ok(())
+    }
+}
+
+/// Install a template's mods into an instance
+///
+/// Projects without a file for the instance's version or loader are reported
+/// in [`TemplateReport::failed`] instead of aborting the rest.
+pub async fn install_template_mods<P: ModProvider + Sync>(
+    provider: &P,
+    template: &InstanceTemplate,
+    mods_dir: &Path,
+    instance: &Instance,
+    on_progress: &(dyn Fn(&str) + Sync),
+) -> TemplateReport {
+    let mut report = TemplateReport::default();
+    if instance.info.loader == ModLoader::Vanilla {
+        report.failed = template
+            .mods
+            .iter()
+            .map(|p| (p.clone(), "instance has no mod loader".to_string()))
+            .collect();
+        return report;
+    }
+
+    for project in &template.mods {
+        on_progress(&format!("Installing {}...", project));
+        match install_project(
+            provider,
+            project,
+            mods_dir,
+            &instance.info.version,
+            &instance.info.loader,
+            on_progress,
+        )
+        .await
+        {
+            Ok(installed) => {
+                report.installed.extend(installed.installed);
+                report.manual.extend(installed.manual);
+            }
+            Err(e) => report.failed.push((project.clone(), e.to_string())),
+        }
+    }
+    report
+}
+
+/// Project IDs of the recognised jars in a mods folder
+///
+/// Also returns the file names of jars the provider does not recognise.
+pub async fn template_mods<P: ModProvider + Sync>(
+    provider: &P,
+    mods_dir: &Path,
+) -> Result<(Vec<String>, Vec<String>)> {
+    let jars = mod_jars(mods_dir);
+    if jars.is_empty() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+
+    let identified = provider.identify(&jars).await?;
+    let mut projects = Vec::new();
+    let mut unknown = Vec::new();
+    for jar in &jars {
+        match identified.get(jar) {
+            Some(file) if !projects.contains(&file.project_id) => {
+                projects.push(file.project_id.clone());
+            }
+            Some(_) => {}
+            None => unknown.push(
+                jar.file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default(),
+            ),
+        }
+    }
+    Ok((projects, unknown))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::temp::TempDir;
+
+    #[test]
+    fn test_template_round_trip_and_apply() {
+        let dir = TempDir::new("template");
+        let templates = TemplateManager::with_dir(&dir.join("templates"));
+        let instances = InstanceManager::with_dir(&dir.join("instances"));
+
+        let mut source = instances
+            .create("Source", "1.20.1", ModLoader::Fabric, None)
+            .unwrap();
+        source.java.max_memory = "6G".to_string();
+        source.java.extra_args = vec!["-XX:+UseG1GC".to_string()];
+        source.game.resolution_width = 1920;
+        source.game.resolution_height = 1080;
+        instances.save(&source).unwrap();
+        let game_dir = instances.get_game_dir(&source.info.id);
+        std::fs::create_dir_all(&game_dir).unwrap();
+        std::fs::write(game_dir.join(OPTIONS_FILE), "renderDistance:16\n").unwrap();
+
+        let mut template =
+            InstanceTemplate::from_instance("Modded Base", &source, &game_dir).unwrap();
+        template.mods = vec!["sodium".to_string()];
+        templates.save(&template).unwrap();
+        assert_eq!(templates.load("Modded Base").unwrap(), template);
+        assert_eq!(templates.list().len(), 1);
+
+        let clash = InstanceTemplate {
+            name: "modded base".to_string(),
+            ..Default::default()
+        };
+        assert!(templates.save(&clash).is_err());
+
+        let mut target = instances
+            .create("Target", "1.20.1", ModLoader::Fabric, None)
+            .unwrap();
+        instances.apply_template(&mut target, &template).unwrap();
+        let target = instances.load(&target.info.id).unwrap();
+        assert_eq!(target.java.max_memory, "6G");
+        assert_eq!(target.java.extra_args, vec!["-XX:+UseG1GC".to_string()]);
+        assert_eq!(target.game.resolution_width, 1920);
+        assert_eq!(
+            std::fs::read_to_string(instances.get_game_dir(&target.info.id).join(OPTIONS_FILE))
+                .unwrap(),
+            "renderDistance:16\n"
+        );
+
+        templates.delete("Modded Base").unwrap();
+        assert!(templates.load("Modded Base").is_err());
+    }
+}
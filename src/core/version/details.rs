@@ -2,6 +2,7 @@
 //!
 //! Detailed version information including libraries, assets, and arguments.
 
+use super::{Arch, FeatureSet, Os, Platform, evaluate_rules};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -30,6 +31,9 @@ pub struct VersionDetails {
 
     pub java_version: Option<JavaVersion>,
 
+    /// Logging configuration (log4j)
+    pub logging: Option<Logging>,
+
     /// Inherited version (for modded versions)
     pub inherits_from: Option<String>,
 }
@@ -101,11 +105,18 @@ pub struct ExtractRule {
 /// Rule for conditional inclusion
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Rule {
-    pub action: String,
+    pub action: RuleAction,
     pub os: Option<OsRule>,
     pub features: Option<HashMap<String, bool>>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleAction {
+    Allow,
+    Disallow,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OsRule {
     pub name: Option<String>,
@@ -148,91 +159,102 @@ pub struct JavaVersion {
     pub major_version: u32,
 }
 
-// === Rule evaluation ===
-
-impl Rule {
-    /// Check if rule allows inclusion on current OS
-    pub fn is_allowed(&self) -> bool {
-        let os_matches = self.os.as_ref().map_or(true, |os| os.matches_current());
-
-        match self.action.as_str() {
-            "allow" => os_matches,
-            "disallow" => !os_matches,
-            _ => true,
-        }
-    }
+/// Logging configuration section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Logging {
+    pub client: Option<LoggingConfig>,
 }
 
-impl OsRule {
-    /// Check if current OS matches the rule
-    pub fn matches_current(&self) -> bool {
-        if let Some(ref name) = self.name {
-            let current_os = if cfg!(target_os = "windows") {
-                "windows"
-            } else if cfg!(target_os = "macos") {
-                "osx"
-            } else {
-                "linux"
-            };
-
-            if name != current_os {
-                return false;
-            }
-        }
-
-        if let Some(ref arch) = self.arch {
-            let current_arch = if cfg!(target_arch = "x86_64") {
-                "x64"
-            } else if cfg!(target_arch = "x86") {
-                "x86"
-            } else if cfg!(target_arch = "aarch64") {
-                "arm64"
-            } else {
-                "unknown"
-            };
-
-            if arch != current_arch {
-                return false;
-            }
-        }
+/// Client logging configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    /// JVM argument with a `${path}` placeholder for the config file
+    pub argument: String,
+    pub file: LoggingFile,
+    #[serde(rename = "type")]
+    pub logging_type: String,
+}
 
-        true
-    }
+/// Log4j configuration file download
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingFile {
+    pub id: String,
+    pub sha1: String,
+    pub size: u64,
+    pub url: String,
 }
 
 impl Library {
     /// Check if library should be included based on rules
     pub fn should_include(&self) -> bool {
-        match &self.rules {
+        self.should_include_on(Platform::current())
+    }
+
+    /// Check if library should be included on a platform
+    ///
+    /// Modern native libraries (`...:natives-linux-arm64`) are also filtered by
+    /// architecture, since their rules usually only check the OS name.
+    pub fn should_include_on(&self, platform: &Platform) -> bool {
+        let allowed = match &self.rules {
             None => true,
-            Some(rules) => {
-                // All rules must allow
-                rules.iter().all(|r| r.is_allowed())
+            Some(rules) => evaluate_rules(rules, platform, &FeatureSet::new()),
+        };
+
+        allowed
+            && self
+                .native_target()
+                .is_none_or(|(os, arch)| os == platform.os && arch == Some(platform.arch))
+    }
+
+    /// Get the classifier from Maven coordinates (group:artifact:version:classifier)
+    pub fn classifier(&self) -> Option<&str> {
+        let coords = self.name.split('@').next().unwrap_or(&self.name);
+        coords.split(':').nth(3)
+    }
+
+    /// Get the target of a modern native library from its classifier
+    ///
+    /// Returns `None` for regular libraries. The architecture is `None` when the
+    /// classifier names one we don't support (e.g. `natives-linux-ppc64le`).
+    pub fn native_target(&self) -> Option<(Os, Option<Arch>)> {
+        let rest = self.classifier()?.strip_prefix("natives-")?;
+        let (os_name, arch_suffix) = match rest.split_once('-') {
+            Some((os, arch)) => (os, Some(arch)),
+            None => (rest, None),
+        };
+
+        let os = Os::from_classifier(os_name)?;
+        let arch = match arch_suffix {
+            // No suffix means the default 64-bit x86 build
+            None => Some(Arch::X86_64),
+            Some(suffix) => Arch::from_classifier(suffix),
+        };
+        Some((os, arch))
+    }
+
+    /// Get the native classifier for a platform (legacy `natives` map)
+    ///
+    /// `${arch}` is replaced with the pointer width. If the library provides an
+    /// architecture-specific variant (`natives-linux-arm64`), it is preferred.
+    pub fn get_native_classifier_on(&self, platform: &Platform) -> Option<String> {
+        let natives = self.natives.as_ref()?;
+        let base = natives
+            .get(platform.os.name())?
+            .replace("${arch}", platform.arch.bits());
+
+        if let Some(suffix) = platform.arch.classifier_suffix() {
+            let specific = format!("{}-{}", base, suffix);
+            let has_specific = self
+                .downloads
+                .as_ref()
+                .and_then(|d| d.classifiers.as_ref())
+                .is_some_and(|c| c.contains_key(&specific));
+            if has_specific {
+                return Some(specific);
             }
         }
-    }
 
-    /// Get the native classifier for current OS
-    pub fn get_native_classifier(&self) -> Option<String> {
-        self.natives.as_ref().and_then(|natives| {
-            let os_key = if cfg!(target_os = "windows") {
-                "windows"
-            } else if cfg!(target_os = "macos") {
-                "osx"
-            } else {
-                "linux"
-            };
-
-            natives.get(os_key).map(|s| {
-                // Replace ${arch} placeholder
-                let arch = if cfg!(target_arch = "x86_64") {
-                    "64"
-                } else {
-                    "32"
-                };
-                s.replace("${arch}", arch)
-            })
-        })
+        Some(base)
     }
 
     /// Get artifact download info
@@ -242,7 +264,12 @@ impl Library {
 
     /// Get native artifact download info
     pub fn get_native_artifact(&self) -> Option<&Artifact> {
-        let classifier = self.get_native_classifier()?;
+        self.get_native_artifact_on(Platform::current())
+    }
+
+    /// Get native artifact download info for a platform
+    pub fn get_native_artifact_on(&self, platform: &Platform) -> Option<&Artifact> {
+        let classifier = self.get_native_classifier_on(platform)?;
         self.downloads
             .as_ref()
             .and_then(|d| d.classifiers.as_ref())
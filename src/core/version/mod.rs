@@ -3,8 +3,10 @@
 //! Download and manage Minecraft versions.
 
 mod details;
+mod rules;
 
 pub use details::*;
+pub use rules::*;
 
 use serde::{Deserialize, Serialize};
 
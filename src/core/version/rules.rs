@@ -0,0 +1,452 @@
+//! Rule evaluation
+//!
+//! Mojang rule engine for libraries and arguments (last matching rule wins).
+
+use super::{OsRule, Rule, RuleAction};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Operating system as named in version JSON rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Os {
+    Windows,
+    Osx,
+    Linux,
+}
+
+impl Os {
+    /// Get the current operating system
+    pub fn current() -> Self {
+        if cfg!(target_os = "windows") {
+            Os::Windows
+        } else if cfg!(target_os = "macos") {
+            Os::Osx
+        } else {
+            Os::Linux
+        }
+    }
+
+    /// Name used in rules and `natives` maps
+    pub fn name(&self) -> &'static str {
+        match self {
+            Os::Windows => "windows",
+            Os::Osx => "osx",
+            Os::Linux => "linux",
+        }
+    }
+
+    /// Check if a rule OS name refers to this OS
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            Os::Osx => name == "osx" || name == "macos",
+            _ => name == self.name(),
+        }
+    }
+
+    /// Parse the OS part of a native classifier (`natives-macos-arm64`)
+    pub fn from_classifier(name: &str) -> Option<Self> {
+        match name {
+            "windows" => Some(Os::Windows),
+            "macos" | "osx" => Some(Os::Osx),
+            "linux" => Some(Os::Linux),
+            _ => None,
+        }
+    }
+
+    /// OS name used by LWJGL 3 native classifiers
+    pub fn classifier_name(&self) -> &'static str {
+        match self {
+            Os::Windows => "windows",
+            Os::Osx => "macos",
+            Os::Linux => "linux",
+        }
+    }
+}
+
+/// CPU architecture
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arch {
+    X86,
+    X86_64,
+    Arm32,
+    Arm64,
+}
+
+impl Arch {
+    /// Get the current CPU architecture
+    pub fn current() -> Self {
+        if cfg!(target_arch = "x86") {
+            Arch::X86
+        } else if cfg!(target_arch = "aarch64") {
+            Arch::Arm64
+        } else if cfg!(target_arch = "arm") {
+            Arch::Arm32
+        } else {
+            Arch::X86_64
+        }
+    }
+
+    /// Check if a rule arch name refers to this architecture
+    pub fn matches(&self, name: &str) -> bool {
+        let aliases: &[&str] = match self {
+            Arch::X86 => &["x86", "i386", "i686"],
+            Arch::X86_64 => &["x86_64", "x64", "amd64"],
+            Arch::Arm32 => &["arm32", "arm"],
+            Arch::Arm64 => &["arm64", "aarch64"],
+        };
+        aliases.contains(&name)
+    }
+
+    /// Parse the architecture suffix of a native classifier
+    pub fn from_classifier(suffix: &str) -> Option<Self> {
+        match suffix {
+            "x86" => Some(Arch::X86),
+            "x64" | "x86_64" => Some(Arch::X86_64),
+            "arm32" => Some(Arch::Arm32),
+            "arm64" | "aarch64" => Some(Arch::Arm64),
+            _ => None,
+        }
+    }
+
+    /// Classifier suffix for this architecture (`None` for the default x86_64 build)
+    pub fn classifier_suffix(&self) -> Option<&'static str> {
+        match self {
+            Arch::X86 => Some("x86"),
+            Arch::X86_64 => None,
+            Arch::Arm32 => Some("arm32"),
+            Arch::Arm64 => Some("arm64"),
+        }
+    }
+
+    /// Value for the `${arch}` placeholder in legacy `natives` maps
+    pub fn bits(&self) -> &'static str {
+        match self {
+            Arch::X86 | Arch::Arm32 => "32",
+            Arch::X86_64 | Arch::Arm64 => "64",
+        }
+    }
+
+    /// Check if this is an ARM architecture
+    pub fn is_arm(&self) -> bool {
+        matches!(self, Arch::Arm32 | Arch::Arm64)
+    }
+}
+
+/// Platform that rules are evaluated against
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Platform {
+    pub os: Os,
+    /// OS version as reported by Java's `os.version`
+    pub os_version: String,
+    pub arch: Arch,
+}
+
+impl Platform {
+    /// Get the current platform (detected once and cached)
+    pub fn current() -> &'static Platform {
+        static CURRENT: OnceLock<Platform> = OnceLock::new();
+        CURRENT.get_or_init(|| Platform {
+            os: Os::current(),
+            os_version: detect_os_version(),
+            arch: Arch::current(),
+        })
+    }
+}
+
+/// Detect the OS version string the same way Java reports `os.version`
+fn detect_os_version() -> String {
+    #[cfg(target_os = "linux")]
+    {
+        std::fs::read_to_string("/proc/sys/kernel/osrelease")
+            .map(|s| s.trim().to_string())
+            .unwrap_or_default()
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("sw_vers")
+            .arg("-productVersion")
+            .output()
+            .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+            .unwrap_or_default()
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // "Microsoft Windows [Version 10.0.19045.3803]" -> "10.0"
+        std::process::Command::new("cmd")
+            .args(["/C", "ver"])
+            .output()
+            .ok()
+            .and_then(|o| {
+                let out = String::from_utf8_lossy(&o.stdout).to_string();
+                let start = out.find("Version ")? + "Version ".len();
+                let version = out[start..].trim_end_matches(|c: char| !c.is_ascii_digit());
+                let mut parts = version.split('.');
+                Some(format!("{}.{}", parts.next()?, parts.next()?))
+            })
+            .unwrap_or_default()
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+    {
+        String::new()
+    }
+}
+
+/// Launcher feature flags referenced by `features` rules
+#[derive(Debug, Clone, Default)]
+pub struct FeatureSet {
+    flags: HashMap<String, bool>,
+}
+
+impl FeatureSet {
+    pub const IS_DEMO_USER: &'static str = "is_demo_user";
+    pub const HAS_CUSTOM_RESOLUTION: &'static str = "has_custom_resolution";
+
+    /// Create an empty feature set (all features disabled)
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a feature flag
+    pub fn with(mut self, name: &str, enabled: bool) -> Self {
+        self.flags.insert(name.to_string(), enabled);
+        self
+    }
+
+    /// Check if a feature is enabled (unknown features are disabled)
+    pub fn is_enabled(&self, name: &str) -> bool {
+        self.flags.get(name).copied().unwrap_or(false)
+    }
+}
+
+/// Evaluate a rule list: no rules allows, otherwise the last matching rule decides
+pub fn evaluate_rules(rules: &[Rule], platform: &Platform, features: &FeatureSet) -> bool {
+    if rules.is_empty() {
+        return true;
+    }
+
+    let mut allowed = false;
+    for rule in rules {
+        if rule.matches(platform, features) {
+            allowed = rule.action == RuleAction::Allow;
+        }
+    }
+    allowed
+}
+
+impl Rule {
+    /// Check if all conditions of this rule match
+    pub fn matches(&self, platform: &Platform, features: &FeatureSet) -> bool {
+        if let Some(ref os) = self.os
+            && !os.matches(platform)
+        {
+            return false;
+        }
+
+        if let Some(ref required) = self.features
+            && required
+                .iter()
+                .any(|(name, value)| features.is_enabled(name) != *value)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+impl OsRule {
+    /// Check if the platform matches this OS condition
+    pub fn matches(&self, platform: &Platform) -> bool {
+        if let Some(ref name) = self.name
+            && !platform.os.matches(name)
+        {
+            return false;
+        }
+
+        if let Some(ref version) = self.version {
+            // An invalid pattern never matches
+            let matched = regex::Regex::new(version)
+                .map(|re| re.is_match(&platform.os_version))
+                .unwrap_or(false);
+            if !matched {
+                return false;
+            }
+        }
+
+        if let Some(ref arch) = self.arch
+            && !platform.arch.matches(arch)
+        {
+            return false;
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_OS: [Os; 3] = [Os::Windows, Os::Osx, Os::Linux];
+    const ALL_ARCH: [Arch; 4] = [Arch::X86, Arch::X86_64, Arch::Arm32, Arch::Arm64];
+
+    fn platform(os: Os, arch: Arch) -> Platform {
+        let os_version = match os {
+            Os::Windows => "10.0",
+            Os::Osx => "14.2.1",
+            Os::Linux => "6.1.0-18-amd64",
+        };
+        Platform {
+            os,
+            os_version: os_version.to_string(),
+            arch,
+        }
+    }
+
+    fn feature_sets() -> Vec<FeatureSet> {
+        vec![
+            FeatureSet::new(),
+            FeatureSet::new().with(FeatureSet::IS_DEMO_USER, true),
+            FeatureSet::new().with(FeatureSet::HAS_CUSTOM_RESOLUTION, true),
+            FeatureSet::new()
+                .with(FeatureSet::IS_DEMO_USER, true)
+                .with(FeatureSet::HAS_CUSTOM_RESOLUTION, true),
+        ]
+    }
+
+    fn rules(json: &str) -> Vec<Rule> {
+        serde_json::from_str(json).unwrap()
+    }
+
+    type Expected = fn(&Platform, &FeatureSet) -> bool;
+
+    #[test]
+    fn test_rule_table() {
+        let cases: Vec<(&str, &str, Expected)> = vec![
+            ("no rules", r#"[]"#, |_, _| true),
+            ("plain allow", r#"[{"action":"allow"}]"#, |_, _| true),
+            ("plain disallow", r#"[{"action":"disallow"}]"#, |_, _| false),
+            (
+                "allow osx only",
+                r#"[{"action":"allow","os":{"name":"osx"}}]"#,
+                |p, _| p.os == Os::Osx,
+            ),
+            (
+                "lone disallow never allows",
+                r#"[{"action":"disallow","os":{"name":"osx"}}]"#,
+                |_, _| false,
+            ),
+            (
+                "allow all but osx",
+                r#"[{"action":"allow"},{"action":"disallow","os":{"name":"osx"}}]"#,
+                |p, _| p.os != Os::Osx,
+            ),
+            (
+                "windows x86 only",
+                r#"[{"action":"allow","os":{"name":"windows","arch":"x86"}}]"#,
+                |p, _| p.os == Os::Windows && p.arch == Arch::X86,
+            ),
+            (
+                "any os on arm64",
+                r#"[{"action":"allow","os":{"arch":"aarch64"}}]"#,
+                |p, _| p.arch == Arch::Arm64,
+            ),
+            (
+                "arm32 alias",
+                r#"[{"action":"allow","os":{"arch":"arm32"}}]"#,
+                |p, _| p.arch == Arch::Arm32,
+            ),
+            (
+                "windows 10 version regex",
+                r#"[{"action":"allow","os":{"name":"windows","version":"^10\\."}}]"#,
+                |p, _| p.os == Os::Windows,
+            ),
+            (
+                "version regex without name",
+                r#"[{"action":"allow","os":{"version":"^6\\."}}]"#,
+                |p, _| p.os == Os::Linux,
+            ),
+            (
+                "last match wins",
+                r#"[{"action":"allow"},{"action":"disallow","os":{"name":"linux"}},{"action":"allow","os":{"name":"linux","arch":"arm64"}}]"#,
+                |p, _| p.os != Os::Linux || p.arch == Arch::Arm64,
+            ),
+            (
+                "demo feature",
+                r#"[{"action":"allow","features":{"is_demo_user":true}}]"#,
+                |_, f| f.is_enabled(FeatureSet::IS_DEMO_USER),
+            ),
+            (
+                "custom resolution feature",
+                r#"[{"action":"allow","features":{"has_custom_resolution":true}}]"#,
+                |_, f| f.is_enabled(FeatureSet::HAS_CUSTOM_RESOLUTION),
+            ),
+            (
+                "feature required false",
+                r#"[{"action":"allow","features":{"is_demo_user":false}}]"#,
+                |_, f| !f.is_enabled(FeatureSet::IS_DEMO_USER),
+            ),
+            (
+                "all features in a rule must match",
+                r#"[{"action":"allow","features":{"is_demo_user":true,"has_custom_resolution":true}}]"#,
+                |_, f| {
+                    f.is_enabled(FeatureSet::IS_DEMO_USER)
+                        && f.is_enabled(FeatureSet::HAS_CUSTOM_RESOLUTION)
+                },
+            ),
+            (
+                "unknown feature is disabled",
+                r#"[{"action":"allow","features":{"has_quick_plays_support":true}}]"#,
+                |_, _| false,
+            ),
+            (
+                "os and feature combined",
+                r#"[{"action":"allow","os":{"name":"linux"},"features":{"has_custom_resolution":true}}]"#,
+                |p, f| p.os == Os::Linux && f.is_enabled(FeatureSet::HAS_CUSTOM_RESOLUTION),
+            ),
+            (
+                "feature disallow overrides allow",
+                r#"[{"action":"allow"},{"action":"disallow","features":{"is_demo_user":true}}]"#,
+                |_, f| !f.is_enabled(FeatureSet::IS_DEMO_USER),
+            ),
+        ];
+
+        for (name, json, expected) in cases {
+            let rules = rules(json);
+            for os in ALL_OS {
+                for arch in ALL_ARCH {
+                    let platform = platform(os, arch);
+                    for features in feature_sets() {
+                        assert_eq!(
+                            evaluate_rules(&rules, &platform, &features),
+                            expected(&platform, &features),
+                            "case '{}' on {:?}/{:?} with {:?}",
+                            name,
+                            os,
+                            arch,
+                            features
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_os_version_mismatch() {
+        let rules = rules(r#"[{"action":"allow","os":{"name":"windows","version":"^10\\."}}]"#);
+        let mut windows_7 = platform(Os::Windows, Arch::X86_64);
+        windows_7.os_version = "6.1".to_string();
+        assert!(!evaluate_rules(&rules, &windows_7, &FeatureSet::new()));
+    }
+
+    #[test]
+    fn test_invalid_version_regex_never_matches() {
+        let rules = rules(r#"[{"action":"allow","os":{"version":"(["}}]"#);
+        let platform = platform(Os::Linux, Arch::X86_64);
+        assert!(!evaluate_rules(&rules, &platform, &FeatureSet::new()));
+    }
+}
@@ -2,15 +2,31 @@
 //!
 //! egui application state and rendering.
 
+use crate::config::{BackupConfig, InstanceSort};
 use crate::core::auth::{Account, AccountManager, DeviceCodeResponse};
+use crate::core::backup::{BackupManager, WorldBackup};
 use crate::core::fabric::FabricManager;
 use crate::core::forge::ForgeManager;
-use crate::core::instance::{Instance, InstanceManager, ModLoader};
+use crate::core::instance::{
+    BUILTIN_ICONS, CUSTOM_ICON, CloneOptions, GameDirMode, GameSession, Instance, InstanceManager,
+    ModLoader, format_playtime, groups, import_external, load_icon_rgba, parse_tags, read_external,
+    sort_instances, tags,
+};
+use crate::core::java::{JavaInstallation, JavaRegistry};
 use crate::core::launch::{LaunchResult, launch_instance_async};
-use crate::core::mods::{ModManager, format_size};
+use crate::core::modpack::{self, ExportFormat};
+use crate::core::mods::{
+    self, CurseForgeClient, ModEnvironment, ModIssue, ModManager, ModProvider, ModSource,
+    ModUpdate, ModrinthClient, ProjectHit, SearchQuery, format_size,
+};
+use crate::core::snapshot::{SnapshotDiff, SnapshotManager};
+use crate::core::template::{
+    InstanceTemplate, TemplateManager, install_template_mods, template_mods,
+};
 use crate::core::version::{self, VersionManifest, VersionType};
 use anyhow::Context;
 use eframe::egui;
+use std::collections::HashMap;
 use std::sync::mpsc;
 
 /// Main launcher application state
@@ -23,6 +39,12 @@ pub struct LauncherApp {
     instances: Vec<Instance>,
     /// Currently selected instance index
     selected_instance: Option<usize>,
+    /// Instance list order
+    instance_sort: InstanceSort,
+    /// Only show instances with this tag (empty = all)
+    tag_filter: String,
+    /// Custom icon textures by instance ID (`None` if the icon failed to load)
+    icon_textures: HashMap<String, Option<egui::TextureHandle>>,
     /// Current view
     current_view: View,
     /// Login state
@@ -42,12 +64,34 @@ pub struct LauncherApp {
     error_message: Option<String>,
     /// Success message to display
     success_message: Option<String>,
+    /// Mod problems found before the last launch
+    mod_warnings: Vec<ModIssue>,
     /// Show instance creation dialog
     show_create_dialog: bool,
+    /// Show modpack import dialog
+    show_import_dialog: bool,
+    /// Modpack import form
+    import_form: ImportForm,
+    /// Open duplicate-instance dialog
+    clone_form: Option<CloneForm>,
     /// Show instance settings dialog
     show_settings_dialog: bool,
     /// Instance being edited in settings
     settings_instance: Option<Instance>,
+    /// Comma-separated tags input in settings
+    settings_tags: String,
+    /// Custom icon path input in settings
+    settings_icon_path: String,
+    /// Template name input in settings
+    settings_template_name: String,
+    /// Java installations offered in instance settings
+    java_installations: Vec<JavaInstallation>,
+    /// Open Modrinth mod browser
+    mod_browser: Option<ModBrowser>,
+    /// Updates found for the instance in settings
+    mod_updates: Vec<ModUpdate>,
+    /// Mod update check in progress
+    checking_mod_updates: bool,
     /// New instance form
     new_instance: NewInstanceForm,
     /// Version manifest (cached)
@@ -58,6 +102,61 @@ pub struct LauncherApp {
     status_message: String,
     /// Offline account username input
     offline_username: String,
+    /// CurseForge API key input
+    curseforge_api_key: String,
+    /// World backup settings input
+    backup_config: BackupConfig,
+    /// Sender for launch threads to report when a game closes
+    game_exit_tx: mpsc::Sender<(String, std::time::Duration)>,
+    /// Instance name and session length of games that closed
+    game_exit_rx: mpsc::Receiver<(String, std::time::Duration)>,
+    /// Snapshot name input and last diff shown in instance settings
+    snapshot_form: SnapshotForm,
+}
+
+#[derive(Default)]
+struct ImportForm {
+    path: String,
+    name: String,
+    /// Official launcher profile, for launcher folders
+    profile: String,
+    /// Link the game folder instead of copying it
+    symlink: bool,
+}
+
+/// World backup action requested from instance settings
+enum BackupAction {
+    Backup(String),
+    Restore(WorldBackup),
+    Delete(WorldBackup),
+}
+
+/// Icon picked in instance settings
+enum IconChoice {
+    Builtin(&'static str),
+    File(String),
+}
+
+/// Snapshot action requested from instance settings
+enum SnapshotAction {
+    Create(String),
+    Diff(String),
+    Restore(String),
+    Delete(String),
+}
+
+#[derive(Default)]
+struct SnapshotForm {
+    name: String,
+    /// Snapshot name and its changes against the current files
+    diff: Option<(String, SnapshotDiff)>,
+}
+
+struct CloneForm {
+    source: String,
+    name: String,
+    include_saves: bool,
+    link_files: bool,
 }
 
 #[derive(Default)]
@@ -70,6 +169,21 @@ struct NewInstanceForm {
     available_loader_versions: Vec<String>,
     include_snapshots: bool,
     loading_loader_versions: bool,
+    /// Template to start from; empty for none
+    template: String,
+    /// Saved template names
+    templates: Vec<String>,
+}
+
+/// Mod search window for one instance
+struct ModBrowser {
+    instance: Instance,
+    source: ModSource,
+    query: String,
+    results: Vec<ProjectHit>,
+    searching: bool,
+    /// Project currently being installed
+    installing: Option<String>,
 }
 
 #[derive(Default, PartialEq)]
@@ -111,26 +225,41 @@ enum AsyncResult {
     VersionManifest(VersionManifest),
     LoaderVersions(Vec<String>),
     InstanceCreated(String),
+    InstanceImported(String),
+    InstanceExported(std::path::PathBuf),
+    InstanceCloned(String),
+    BackupsChanged(String),
+    SnapshotsChanged(String),
+    SnapshotDiff(String, SnapshotDiff),
+    TemplateSaved(String),
     LaunchProgress(String),
     LaunchSuccess,
     UpdateCheck(UpdateStatus),
     UpdateSuccess(String),
     UpdateError(String),
+    ModSearchResults(Vec<ProjectHit>),
+    ModUpdates(Vec<ModUpdate>),
+    ModsChanged(String),
     Error(String),
 }
 
 impl LauncherApp {
     pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
         let instance_manager = InstanceManager::new();
-        let instances = instance_manager.list().unwrap_or_default();
 
         let (tx, rx) = mpsc::channel();
+        let (game_exit_tx, game_exit_rx) = mpsc::channel();
 
         let mut app = Self {
             account_manager: AccountManager::default(),
             instance_manager,
-            instances,
+            instances: Vec::new(),
             selected_instance: None,
+            instance_sort: crate::config::load()
+                .map(|c| c.general.instance_sort)
+                .unwrap_or_default(),
+            tag_filter: String::new(),
+            icon_textures: HashMap::new(),
             current_view: View::Instances,
             login_state: LoginState::Idle,
             async_receiver: Some(rx),
@@ -139,14 +268,32 @@ impl LauncherApp {
             tx: tx.clone(),      // Add tx field
             error_message: None,
             success_message: None,
+            mod_warnings: Vec::new(),
             show_create_dialog: false,
+            show_import_dialog: false,
+            import_form: ImportForm::default(),
+            clone_form: None,
             show_settings_dialog: false,
             settings_instance: None,
+            settings_tags: String::new(),
+            settings_icon_path: String::new(),
+            settings_template_name: String::new(),
+            java_installations: Vec::new(),
+            mod_browser: None,
+            mod_updates: Vec::new(),
+            checking_mod_updates: false,
             new_instance: NewInstanceForm::default(),
             version_manifest: None,
             is_loading: false,
             status_message: "Ready".to_string(),
             offline_username: String::new(),
+            curseforge_api_key: crate::config::load()
+                .map(|c| c.mods.curseforge_api_key)
+                .unwrap_or_default(),
+            backup_config: crate::config::load().map(|c| c.backups).unwrap_or_default(),
+            snapshot_form: SnapshotForm::default(),
+            game_exit_tx,
+            game_exit_rx,
         };
 
         // Start update check
@@ -157,6 +304,8 @@ impl LauncherApp {
             let _ = tx_clone.send(AsyncResult::UpdateCheck(status));
         });
 
+        app.refresh_instances();
+
         // Auto-select first instance if available
         if !app.instances.is_empty() {
             app.selected_instance = Some(0);
@@ -166,19 +315,70 @@ impl LauncherApp {
     }
 
     fn refresh_instances(&mut self) {
-        self.instances = self.instance_manager.list().unwrap_or_default();
-        if self
+        let selected_id = self
             .selected_instance
-            .map_or(false, |i| i >= self.instances.len())
-        {
-            self.selected_instance = if self.instances.is_empty() {
-                None
-            } else {
-                Some(0)
-            };
+            .and_then(|i| self.instances.get(i))
+            .map(|i| i.info.id.clone());
+        self.instances = self.instance_manager.list().unwrap_or_default();
+        sort_instances(&mut self.instances, self.instance_sort);
+        self.icon_textures.clear();
+
+        // Keep the same instance selected after reordering
+        self.selected_instance = selected_id.and_then(|id| {
+            self.instances
+                .iter()
+                .position(|i| i.info.id == id)
+                .or(if self.instances.is_empty() {
+                    None
+                } else {
+                    Some(0)
+                })
+        });
+    }
+
+    /// Load custom icon textures not yet in the cache
+    fn load_icon_textures(&mut self, ctx: &egui::Context) {
+        for instance in &self.instances {
+            if self.icon_textures.contains_key(&instance.info.id) {
+                continue;
+            }
+            let texture = self.instance_manager.icon_path(instance).and_then(|path| {
+                let rgba = load_icon_rgba(&path)
+                    .map_err(|e| tracing::warn!("{}", e))
+                    .ok()?;
+                let size = [rgba.width() as usize, rgba.height() as usize];
+                let image = egui::ColorImage::from_rgba_unmultiplied(size, rgba.as_raw());
+                Some(ctx.load_texture(
+                    format!("instance-icon-{}", instance.info.id),
+                    image,
+                    Default::default(),
+                ))
+            });
+            self.icon_textures.insert(instance.info.id.clone(), texture);
         }
     }
 
+    fn set_instance_sort(&mut self, sort: InstanceSort) {
+        self.instance_sort = sort;
+        let mut config = crate::config::load().unwrap_or_default();
+        config.general.instance_sort = sort;
+        if let Err(e) = crate::config::save(&config) {
+            self.error_message = Some(format!("Failed to save settings: {}", e));
+        }
+        self.refresh_instances();
+    }
+
+    fn toggle_favorite(&mut self, id: &str) {
+        let result = self.instance_manager.load_id(id).and_then(|mut instance| {
+            instance.info.favorite = !instance.info.favorite;
+            self.instance_manager.save(&instance)
+        });
+        if let Err(e) = result {
+            self.error_message = Some(format!("Failed to save: {}", e));
+        }
+        self.refresh_instances();
+    }
+
     fn fetch_versions(&mut self, ctx: &egui::Context) {
         self.is_loading = true;
         self.status_message = "Fetching versions...".to_string();
@@ -219,6 +419,18 @@ impl LauncherApp {
             return;
         }
 
+        let template = if self.new_instance.template.is_empty() {
+            None
+        } else {
+            match TemplateManager::new().load(&self.new_instance.template) {
+                Ok(template) => Some(template),
+                Err(e) => {
+                    self.error_message = Some(e.to_string());
+                    return;
+                }
+            }
+        };
+
         self.is_loading = true;
         self.status_message = format!("Creating instance {}...", name);
 
@@ -229,14 +441,65 @@ impl LauncherApp {
         let instance_manager = InstanceManager::new();
 
         std::thread::spawn(move || {
-            match instance_manager.create(&name, &version, loader, loader_version) {
-                Ok(_) => {
-                    let _ = tx.send(AsyncResult::InstanceCreated(name));
-                }
-                Err(e) => {
-                    let _ = tx.send(AsyncResult::Error(e.to_string()));
-                }
-            }
+            let result = instance_manager
+                .create(&name, &version, loader, loader_version)
+                .and_then(|mut instance| {
+                    let Some(template) = template else {
+                        return Ok(format!("Created instance: {}", name));
+                    };
+                    instance_manager.apply_template(&mut instance, &template)?;
+                    if template.mods.is_empty() {
+                        return Ok(format!(
+                            "Created instance {} from template {}",
+                            name, template.name
+                        ));
+                    }
+
+                    let mods_dir = instance_manager
+                        .get_game_dir(&instance.info.id)
+                        .join("mods");
+                    let progress_tx = tx.clone();
+                    let on_progress = move |msg: &str| {
+                        let _ = progress_tx.send(AsyncResult::LaunchProgress(msg.to_string()));
+                    };
+                    let rt = tokio::runtime::Runtime::new().unwrap();
+                    let report = rt.block_on(install_template_mods(
+                        &ModrinthClient::new(),
+                        &template,
+                        &mods_dir,
+                        &instance,
+                        &on_progress,
+                    ));
+
+                    let mut message = format!(
+                        "Created instance {} from template {} ({} mod files)",
+                        name,
+                        template.name,
+                        report.installed.len()
+                    );
+                    if !report.failed.is_empty() {
+                        let skipped: Vec<_> =
+                            report.failed.iter().map(|(p, _)| p.as_str()).collect();
+                        message.push_str(&format!(". Skipped: {}", skipped.join(", ")));
+                    }
+                    if !report.manual.is_empty() {
+                        let files: Vec<_> = report
+                            .manual
+                            .iter()
+                            .map(|f| f.page_url.clone().unwrap_or_else(|| f.filename.clone()))
+                            .collect();
+                        message.push_str(&format!(
+                            ". Download these manually into {}: {}",
+                            mods_dir.display(),
+                            files.join(", ")
+                        ));
+                    }
+                    Ok(message)
+                });
+            let _ = tx.send(match result {
+                Ok(message) => AsyncResult::InstanceCreated(message),
+                Err(e) => AsyncResult::Error(e.to_string()),
+            });
             ctx.request_repaint();
         });
     }
@@ -307,26 +570,44 @@ impl LauncherApp {
         self.is_loading = true;
         self.status_message = format!("Launching {}...", instance.info.name);
         self.error_message = None;
+        self.mod_warnings = mods::check_instance(&instance);
 
         let (tx, rx) = mpsc::channel();
         self.async_receiver = Some(rx);
 
         let ctx = ctx.clone();
         let account = self.account_manager.active_account().cloned();
+        let exit_tx = self.game_exit_tx.clone();
 
         println!("Account: {:?}", account.as_ref().map(|a| &a.profile.name));
 
         std::thread::spawn(move || {
             println!("=== SPAWN THREAD STARTED ===");
+            let name = instance.info.name.clone();
             let rt = tokio::runtime::Runtime::new().unwrap();
-            rt.block_on(async {
+            let session = rt.block_on(async {
                 println!("=== ASYNC BLOCK STARTED ===");
-                if let Err(e) = launch_instance(instance, account, tx.clone()).await {
-                    println!("=== LAUNCH ERROR: {} ===", e);
-                    let _ = tx.send(AsyncResult::Error(e.to_string()));
+                match launch_instance(instance, account, tx.clone()).await {
+                    Ok(session) => session,
+                    Err(e) => {
+                        println!("=== LAUNCH ERROR: {} ===", e);
+                        let _ = tx.send(AsyncResult::Error(e.to_string()));
+                        None
+                    }
                 }
             });
             ctx.request_repaint();
+
+            // Wait for the game so its playtime is recorded
+            if let Some(session) = session {
+                match session.wait() {
+                    Ok(played) => {
+                        let _ = exit_tx.send((name, played));
+                        ctx.request_repaint();
+                    }
+                    Err(e) => tracing::warn!("{}", e),
+                }
+            }
         });
     }
 
@@ -393,6 +674,15 @@ impl LauncherApp {
     }
 
     fn check_async_results(&mut self) {
+        if let Ok((name, played)) = self.game_exit_rx.try_recv() {
+            self.success_message = Some(format!(
+                "{} closed after {}",
+                name,
+                format_playtime(played.as_secs())
+            ));
+            self.refresh_instances();
+        }
+
         if let Some(rx) = &self.async_receiver {
             if let Ok(result) = rx.try_recv() {
                 match result {
@@ -443,16 +733,94 @@ impl LauncherApp {
                         self.status_message = "Ready".to_string();
                         self.async_receiver = None;
                     }
-                    AsyncResult::InstanceCreated(name) => {
+                    AsyncResult::InstanceCreated(message) => {
                         self.refresh_instances();
                         self.show_create_dialog = false;
                         self.new_instance = NewInstanceForm::default();
-                        self.success_message = Some(format!("Created instance: {}", name));
+                        self.success_message = Some(message);
+                        self.is_loading = false;
+                        self.status_message = "Ready".to_string();
+                        self.async_receiver = None;
+                    }
+                    AsyncResult::InstanceImported(message) => {
+                        self.refresh_instances();
+                        self.show_import_dialog = false;
+                        self.import_form = ImportForm::default();
+                        self.success_message = Some(message);
+                        self.is_loading = false;
+                        self.status_message = "Ready".to_string();
+                        self.async_receiver = None;
+                    }
+                    AsyncResult::InstanceCloned(name) => {
+                        self.refresh_instances();
+                        self.clone_form = None;
+                        self.success_message = Some(format!("Created copy: {}", name));
+                        self.is_loading = false;
+                        self.status_message = "Ready".to_string();
+                        self.async_receiver = None;
+                    }
+                    AsyncResult::InstanceExported(path) => {
+                        self.success_message = Some(format!("Exported to {}", path.display()));
+                        self.is_loading = false;
+                        self.status_message = "Ready".to_string();
+                        self.async_receiver = None;
+                    }
+                    AsyncResult::ModSearchResults(results) => {
+                        if let Some(browser) = &mut self.mod_browser {
+                            browser.results = results;
+                            browser.searching = false;
+                        }
+                        self.async_receiver = None;
+                    }
+                    AsyncResult::ModUpdates(updates) => {
+                        if updates.is_empty() {
+                            self.success_message = Some("All mods are up to date".to_string());
+                        }
+                        self.mod_updates = updates;
+                        self.checking_mod_updates = false;
+                        self.async_receiver = None;
+                    }
+                    AsyncResult::BackupsChanged(message) => {
+                        self.success_message = Some(message);
+                        self.is_loading = false;
+                        self.status_message = "Ready".to_string();
+                        self.async_receiver = None;
+                    }
+                    AsyncResult::TemplateSaved(message) => {
+                        self.settings_template_name.clear();
+                        self.success_message = Some(message);
+                        self.is_loading = false;
+                        self.status_message = "Ready".to_string();
+                        self.async_receiver = None;
+                    }
+                    AsyncResult::SnapshotsChanged(message) => {
+                        self.refresh_instances();
+                        self.success_message = Some(message);
+                        self.is_loading = false;
+                        self.status_message = "Ready".to_string();
+                        self.async_receiver = None;
+                    }
+                    AsyncResult::SnapshotDiff(snapshot, diff) => {
+                        self.snapshot_form.diff = Some((snapshot, diff));
+                        self.is_loading = false;
+                        self.status_message = "Ready".to_string();
+                        self.async_receiver = None;
+                    }
+                    AsyncResult::ModsChanged(message) => {
+                        if let Some(browser) = &mut self.mod_browser {
+                            browser.installing = None;
+                        }
+                        self.success_message = Some(message);
                         self.is_loading = false;
                         self.status_message = "Ready".to_string();
                         self.async_receiver = None;
                     }
                     AsyncResult::Error(e) => {
+                        self.checking_mod_updates = false;
+                        if let Some(browser) = &mut self.mod_browser {
+                            browser.searching = false;
+                            browser.installing = None;
+                        }
                         self.error_message = Some(e);
                         self.is_loading = false;
                         self.status_message = "Ready".to_string();
@@ -570,6 +938,25 @@ impl eframe::App for LauncherApp {
                     ui.label(&self.status_message);
                 }
 
+                if !self.mod_warnings.is_empty() {
+                    ui.separator();
+                    let color = if self.mod_warnings.iter().any(ModIssue::is_fatal) {
+                        egui::Color32::RED
+                    } else {
+                        egui::Color32::YELLOW
+                    };
+                    let details: Vec<String> =
+                        self.mod_warnings.iter().map(|i| i.to_string()).collect();
+                    ui.colored_label(
+                        color,
+                        format!("⚠️ {} mod issue(s)", self.mod_warnings.len()),
+                    )
+                    .on_hover_text(details.join("\n"));
+                    if ui.small_button("✕").clicked() {
+                        self.mod_warnings.clear();
+                    }
+                }
+
                 if let Some(UpdateStatus::UpdateAvailable { latest, .. }) = &self.update_status {
                     ui.separator();
                     ui.colored_label(
@@ -614,11 +1001,26 @@ impl eframe::App for LauncherApp {
             self.show_create_instance_dialog(ctx);
         }
 
+        // Modpack import dialog
+        if self.show_import_dialog {
+            self.show_import_pack_dialog(ctx);
+        }
+
+        // Duplicate instance dialog
+        if self.clone_form.is_some() {
+            self.show_clone_dialog(ctx);
+        }
+
         // Instance settings dialog
         if self.show_settings_dialog {
             self.show_instance_settings_dialog(ctx);
         }
 
+        // Modrinth mod browser
+        if self.mod_browser.is_some() {
+            self.show_mod_browser(ctx);
+        }
+
         // Request repaint while waiting
         if self.is_loading
             || matches!(
@@ -638,10 +1040,18 @@ impl LauncherApp {
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui.button("➕ New").clicked() {
                     self.show_create_dialog = true;
+                    self.new_instance.templates = TemplateManager::new()
+                        .list()
+                        .into_iter()
+                        .map(|t| t.name)
+                        .collect();
                     if self.version_manifest.is_none() {
                         self.fetch_versions(ctx);
                     }
                 }
+                if ui.button("📥 Import").clicked() {
+                    self.show_import_dialog = true;
+                }
                 if ui.button("🔄").clicked() {
                     self.refresh_instances();
                 }
@@ -671,25 +1081,141 @@ impl LauncherApp {
             return;
         }
 
-        // Instance list
+        // Sort and tag filter
+        let mut sort = self.instance_sort;
+        let all_tags = tags(&self.instances);
+        ui.horizontal(|ui| {
+            ui.label("Sort:");
+            egui::ComboBox::from_id_salt("instance_sort")
+                .selected_text(sort.to_string())
+                .show_ui(ui, |ui| {
+                    for option in InstanceSort::ALL {
+                        ui.selectable_value(&mut sort, option, option.to_string());
+                    }
+                });
+            if !all_tags.is_empty() {
+                ui.label("Tag:");
+                let selected_text = if self.tag_filter.is_empty() {
+                    "All".to_string()
+                } else {
+                    format!("#{}", self.tag_filter)
+                };
+                egui::ComboBox::from_id_salt("instance_tag_filter")
+                    .selected_text(selected_text)
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.tag_filter, String::new(), "All");
+                        for tag in &all_tags {
+                            ui.selectable_value(
+                                &mut self.tag_filter,
+                                tag.clone(),
+                                format!("#{}", tag),
+                            );
+                        }
+                    });
+            }
+        });
+        if sort != self.instance_sort {
+            self.set_instance_sort(sort);
+        }
+
+        self.load_icon_textures(ctx);
+
+        // Instance list: ungrouped first, then one collapsible section per group
         let mut selected = self.selected_instance;
+        let mut duplicate = None;
+        let mut favorite = None;
+        let mut sections = vec![String::new()];
+        sections.extend(groups(&self.instances));
         egui::ScrollArea::vertical().show(ui, |ui| {
-            for (i, instance) in self.instances.iter().enumerate() {
-                let is_selected = selected == Some(i);
-                let response = ui.selectable_label(
-                    is_selected,
-                    format!(
-                        "📦 {} - {} {}",
-                        instance.info.name, instance.info.version, instance.info.loader
-                    ),
-                );
-                if response.clicked() {
-                    selected = Some(i);
+            for section in &sections {
+                let members: Vec<usize> = (0..self.instances.len())
+                    .filter(|&i| {
+                        let info = &self.instances[i].info;
+                        info.in_group(section)
+                            && (self.tag_filter.is_empty() || info.has_tag(&self.tag_filter))
+                    })
+                    .collect();
+                if members.is_empty() {
+                    continue;
+                }
+
+                let mut show_rows = |ui: &mut egui::Ui| {
+                    for &i in &members {
+                        let info = &self.instances[i].info;
+                        let star = if info.favorite { "⭐ " } else { "" };
+                        let tags: String = info.tags.iter().map(|t| format!("  #{}", t)).collect();
+                        let response = ui
+                            .horizontal(|ui| {
+                                match self.icon_textures.get(&info.id) {
+                                    Some(Some(texture)) => {
+                                        ui.add(
+                                            egui::Image::new(texture)
+                                                .fit_to_exact_size(egui::vec2(18.0, 18.0)),
+                                        );
+                                    }
+                                    _ => {
+                                        ui.label(info.icon_emoji());
+                                    }
+                                }
+                                let response = ui.selectable_label(
+                                    selected == Some(i),
+                                    format!(
+                                        "{}{} - {} {}{}",
+                                        star, info.name, info.version, info.loader, tags
+                                    ),
+                                );
+                                ui.label(egui::RichText::new(info.play_stats()).weak().small());
+                                response
+                            })
+                            .inner;
+                        if response.clicked() {
+                            selected = Some(i);
+                        }
+                        response.context_menu(|ui| {
+                            let label = if info.favorite {
+                                "☆ Remove from favourites"
+                            } else {
+                                "⭐ Add to favourites"
+                            };
+                            if ui.button(label).clicked() {
+                                favorite = Some(info.id.clone());
+                                ui.close_menu();
+                            }
+                            if ui.button("📋 Duplicate").clicked() {
+                                duplicate = Some(info.name.clone());
+                                ui.close_menu();
+                            }
+                        });
+                    }
+                };
+
+                if section.is_empty() {
+                    show_rows(ui);
+                } else {
+                    egui::CollapsingHeader::new(format!("📁 {} ({})", section, members.len()))
+                        .id_salt(("instance_group", section.to_lowercase()))
+                        .default_open(true)
+                        .show(ui, |ui| show_rows(ui));
                 }
             }
         });
         self.selected_instance = selected;
 
+        if let Some(id) = favorite {
+            self.toggle_favorite(&id);
+        }
+
+        if let Some(source) = duplicate {
+            self.clone_form = Some(CloneForm {
+                name: self
+                    .instance_manager
+                    .unique_name(&format!("{} (copy)", source)),
+                source,
+                include_saves: true,
+                link_files: true,
+            });
+        }
+
         // Bottom actions
         ui.with_layout(egui::Layout::bottom_up(egui::Align::Center), |ui| {
             ui.add_space(10.0);
@@ -718,7 +1244,8 @@ impl LauncherApp {
                 {
                     if let Some(i) = self.selected_instance {
                         let name = self.instances[i].info.name.clone();
-                        if let Err(e) = self.instance_manager.delete(&name) {
+                        let id = self.instances[i].info.id.clone();
+                        if let Err(e) = self.instance_manager.delete(&id) {
                             self.error_message = Some(e.to_string());
                         } else {
                             self.success_message = Some(format!("Deleted: {}", name));
@@ -736,15 +1263,27 @@ impl LauncherApp {
                 {
                     if let Some(i) = self.selected_instance {
                         self.settings_instance = Some(self.instances[i].clone());
+                        self.settings_tags = self.instances[i].info.tags.join(", ");
+                        self.settings_icon_path.clear();
+                        self.settings_template_name.clear();
                         self.show_settings_dialog = true;
+                        self.snapshot_form = SnapshotForm::default();
+                        self.mod_updates.clear();
+                        self.load_java_installations(false);
                     }
                 }
             });
         });
     }
 
-    fn show_create_instance_dialog(&mut self, ctx: &egui::Context) {
-        egui::Window::new("New Instance")
+    fn show_clone_dialog(&mut self, ctx: &egui::Context) {
+        let Some(form) = &mut self.clone_form else {
+            return;
+        };
+        let mut cancel = false;
+        let mut start = false;
+
+        egui::Window::new(format!("Duplicate {}", form.source))
             .collapsible(false)
             .resizable(false)
             .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
@@ -753,53 +1292,332 @@ impl LauncherApp {
 
                 ui.horizontal(|ui| {
                     ui.label("Name:");
-                    ui.text_edit_singleline(&mut self.new_instance.name);
+                    ui.text_edit_singleline(&mut form.name);
                 });
+                ui.checkbox(&mut form.include_saves, "Copy worlds");
+                ui.checkbox(&mut form.link_files, "Share mod files with the original")
+                    .on_hover_text(
+                        "Uses copy-on-write clones or hardlinks for jars and packs to save space",
+                    );
 
-                ui.add_space(5.0);
+                ui.add_space(10.0);
 
                 ui.horizontal(|ui| {
-                    ui.label("Version:");
-                    if self.is_loading && self.new_instance.available_versions.is_empty() {
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                    let can_clone = !self.is_loading && !form.name.trim().is_empty();
+                    if ui
+                        .add_enabled(can_clone, egui::Button::new("Duplicate"))
+                        .clicked()
+                    {
+                        start = true;
+                    }
+                    if self.is_loading {
                         ui.spinner();
-                        ui.label("Loading...");
-                    } else {
-                        let old_version = self.new_instance.version.clone();
-                        egui::ComboBox::from_id_salt("version_select")
-                            .selected_text(&self.new_instance.version)
-                            .show_ui(ui, |ui| {
-                                for v in &self.new_instance.available_versions {
-                                    ui.selectable_value(
-                                        &mut self.new_instance.version,
-                                        v.clone(),
-                                        v,
-                                    );
-                                }
-                            });
-                        // If version changed, reset loader versions
-                        if old_version != self.new_instance.version {
-                            self.new_instance.available_loader_versions.clear();
-                            self.new_instance.loader_version.clear();
-                        }
                     }
                 });
+            });
+
+        if cancel {
+            self.clone_form = None;
+        } else if start {
+            self.clone_instance(ctx);
+        }
+    }
+
+    fn clone_instance(&mut self, ctx: &egui::Context) {
+        let Some(form) = &self.clone_form else {
+            return;
+        };
+        let source = form.source.clone();
+        let name = form.name.trim().to_string();
+        let options = CloneOptions {
+            include_saves: form.include_saves,
+            link_files: form.link_files,
+        };
+
+        self.is_loading = true;
+        self.status_message = format!("Copying {}...", source);
+
+        let (tx, rx) = mpsc::channel();
+        self.async_receiver = Some(rx);
+
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let result = InstanceManager::new().clone_instance(&source, &name, &options);
+            let _ = tx.send(match result {
+                Ok(instance) => AsyncResult::InstanceCloned(instance.info.name),
+                Err(e) => AsyncResult::Error(format!("Failed to duplicate {}: {:#}", source, e)),
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn show_import_pack_dialog(&mut self, ctx: &egui::Context) {
+        let mut import = false;
+
+        egui::Window::new("Import")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.set_min_width(350.0);
 
                 ui.horizontal(|ui| {
-                    ui.checkbox(
-                        &mut self.new_instance.include_snapshots,
-                        "Include snapshots",
+                    ui.label("Path:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.import_form.path)
+                            .hint_text("Modpack file or launcher instance folder"),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.import_form.name)
+                            .hint_text("(from pack)"),
                     );
                 });
 
-                ui.add_space(5.0);
+                // MultiMC/Prism instances and official launcher folders
+                if std::path::Path::new(self.import_form.path.trim()).is_dir() {
+                    ui.horizontal(|ui| {
+                        ui.label("Profile:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.import_form.profile)
+                                .hint_text("(official launcher only)"),
+                        );
+                    });
+                    ui.checkbox(
+                        &mut self.import_form.symlink,
+                        "Link the game folder instead of copying it",
+                    );
+                }
+
+                ui.add_space(10.0);
 
                 ui.horizontal(|ui| {
-                    ui.label("Loader:");
-                    let old_loader = self.new_instance.loader.clone();
-                    egui::ComboBox::from_id_salt("loader_select")
-                        .selected_text(format!("{}", self.new_instance.loader))
-                        .show_ui(ui, |ui| {
-                            ui.selectable_value(
+                    if ui.button("Cancel").clicked() {
+                        self.show_import_dialog = false;
+                    }
+                    let can_import = !self.is_loading && !self.import_form.path.trim().is_empty();
+                    if ui
+                        .add_enabled(can_import, egui::Button::new("Import"))
+                        .clicked()
+                    {
+                        import = true;
+                    }
+                    if self.is_loading {
+                        ui.spinner();
+                    }
+                });
+            });
+
+        if import {
+            self.import_pack(ctx);
+        }
+    }
+
+    fn import_pack(&mut self, ctx: &egui::Context) {
+        let path = std::path::PathBuf::from(self.import_form.path.trim());
+        let name = Some(self.import_form.name.trim().to_string()).filter(|n| !n.is_empty());
+
+        if path.is_dir() {
+            self.import_instance(path, name, ctx);
+            return;
+        }
+
+        self.is_loading = true;
+        self.status_message = format!("Importing {}...", path.display());
+
+        let (tx, rx) = mpsc::channel();
+        self.async_receiver = Some(rx);
+
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                let progress_tx = tx.clone();
+                let on_progress = move |msg: &str| {
+                    let _ = progress_tx.send(AsyncResult::LaunchProgress(msg.to_string()));
+                };
+                let result = modpack::import_pack(
+                    &InstanceManager::new(),
+                    &path,
+                    name.as_deref(),
+                    &on_progress,
+                )
+                .await;
+                let _ = tx.send(match result {
+                    Ok(report) if !report.manual.is_empty() => {
+                        let files: Vec<_> = report
+                            .manual
+                            .iter()
+                            .map(|f| format!("{} → {}", f.page_url, f.dest_dir.display()))
+                            .collect();
+                        AsyncResult::InstanceImported(format!(
+                            "Imported instance: {}. Download these manually: {}",
+                            report.instance_name,
+                            files.join(", ")
+                        ))
+                    }
+                    Ok(report) => AsyncResult::InstanceImported(format!(
+                        "Imported instance: {}",
+                        report.instance_name
+                    )),
+                    Err(e) => AsyncResult::Error(format!("Import failed: {:#}", e)),
+                });
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn import_instance(
+        &mut self,
+        path: std::path::PathBuf,
+        name: Option<String>,
+        ctx: &egui::Context,
+    ) {
+        let profile = Some(self.import_form.profile.trim().to_string()).filter(|p| !p.is_empty());
+        let mode = if self.import_form.symlink {
+            GameDirMode::Symlink
+        } else {
+            GameDirMode::Copy
+        };
+
+        self.is_loading = true;
+        self.status_message = format!("Importing {}...", path.display());
+
+        let (tx, rx) = mpsc::channel();
+        self.async_receiver = Some(rx);
+
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let result = read_external(&path, profile.as_deref()).and_then(|external| {
+                import_external(&InstanceManager::new(), &external, name.as_deref(), mode)
+            });
+            let _ = tx.send(match result {
+                Ok(instance) => AsyncResult::InstanceImported(format!(
+                    "Imported instance: {}",
+                    instance.info.name
+                )),
+                Err(e) => AsyncResult::Error(format!("Import failed: {:#}", e)),
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn export_pack(&mut self, name: &str, format: ExportFormat, ctx: &egui::Context) {
+        let output = dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .unwrap_or_default()
+            .join(format!("{}.{}", name, format.extension()));
+        let name = name.to_string();
+
+        self.is_loading = true;
+        self.status_message = format!("Exporting {}...", name);
+
+        let (tx, rx) = mpsc::channel();
+        self.async_receiver = Some(rx);
+
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                let progress_tx = tx.clone();
+                let on_progress = move |msg: &str| {
+                    let _ = progress_tx.send(AsyncResult::LaunchProgress(msg.to_string()));
+                };
+                let manager = InstanceManager::new();
+                let result = match format {
+                    ExportFormat::Mrpack => {
+                        modpack::export_mrpack(
+                            &manager,
+                            &name,
+                            &output,
+                            &ModrinthClient::new(),
+                            &on_progress,
+                        )
+                        .await
+                    }
+                    ExportFormat::Zip => modpack::export_archive(
+                        &manager,
+                        &name,
+                        &output,
+                        modpack::DEFAULT_ARCHIVE_CONTENT,
+                        &on_progress,
+                    ),
+                };
+                let _ = tx.send(match result {
+                    Ok(_) => AsyncResult::InstanceExported(output),
+                    Err(e) => AsyncResult::Error(format!("Export failed: {:#}", e)),
+                });
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn show_create_instance_dialog(&mut self, ctx: &egui::Context) {
+        egui::Window::new("New Instance")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.set_min_width(300.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Name:");
+                    ui.text_edit_singleline(&mut self.new_instance.name);
+                });
+
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Version:");
+                    if self.is_loading && self.new_instance.available_versions.is_empty() {
+                        ui.spinner();
+                        ui.label("Loading...");
+                    } else {
+                        let old_version = self.new_instance.version.clone();
+                        egui::ComboBox::from_id_salt("version_select")
+                            .selected_text(&self.new_instance.version)
+                            .show_ui(ui, |ui| {
+                                for v in &self.new_instance.available_versions {
+                                    ui.selectable_value(
+                                        &mut self.new_instance.version,
+                                        v.clone(),
+                                        v,
+                                    );
+                                }
+                            });
+                        // If version changed, reset loader versions
+                        if old_version != self.new_instance.version {
+                            self.new_instance.available_loader_versions.clear();
+                            self.new_instance.loader_version.clear();
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    ui.checkbox(
+                        &mut self.new_instance.include_snapshots,
+                        "Include snapshots",
+                    );
+                });
+
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Loader:");
+                    let old_loader = self.new_instance.loader.clone();
+                    egui::ComboBox::from_id_salt("loader_select")
+                        .selected_text(format!("{}", self.new_instance.loader))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
                                 &mut self.new_instance.loader,
                                 ModLoader::Vanilla,
                                 "Vanilla",
@@ -856,6 +1674,34 @@ impl LauncherApp {
                     });
                 }
 
+                if !self.new_instance.templates.is_empty() {
+                    ui.add_space(5.0);
+                    ui.horizontal(|ui| {
+                        ui.label("Template:");
+                        let selected = if self.new_instance.template.is_empty() {
+                            "None"
+                        } else {
+                            self.new_instance.template.as_str()
+                        };
+                        egui::ComboBox::from_id_salt("template_select")
+                            .selected_text(selected.to_string())
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut self.new_instance.template,
+                                    String::new(),
+                                    "None",
+                                );
+                                for name in &self.new_instance.templates {
+                                    ui.selectable_value(
+                                        &mut self.new_instance.template,
+                                        name.clone(),
+                                        name,
+                                    );
+                                }
+                            });
+                    });
+                }
+
                 ui.add_space(10.0);
 
                 ui.horizontal(|ui| {
@@ -878,6 +1724,332 @@ impl LauncherApp {
             });
     }
 
+    fn search_mods(&mut self, ctx: &egui::Context) {
+        let Some(browser) = &mut self.mod_browser else {
+            return;
+        };
+        browser.searching = true;
+
+        let source = browser.source;
+        let query = SearchQuery {
+            query: browser.query.clone(),
+            mc_version: browser.instance.info.version.clone(),
+            loader: browser.instance.info.loader.clone(),
+            limit: 20,
+        };
+
+        let (tx, rx) = mpsc::channel();
+        self.async_receiver = Some(rx);
+
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                let result = match source {
+                    ModSource::Modrinth => ModrinthClient::new().search(&query).await,
+                    ModSource::CurseForge => match CurseForgeClient::from_config() {
+                        Ok(client) => client.search(&query).await,
+                        Err(e) => Err(e),
+                    },
+                };
+                match result {
+                    Ok(results) => {
+                        let _ = tx.send(AsyncResult::ModSearchResults(results));
+                    }
+                    Err(e) => {
+                        let _ = tx.send(AsyncResult::Error(e.to_string()));
+                    }
+                }
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn install_mod(&mut self, hit: &ProjectHit, ctx: &egui::Context) {
+        let Some(browser) = &mut self.mod_browser else {
+            return;
+        };
+        browser.installing = Some(hit.id.clone());
+
+        let source = browser.source;
+        let instance = browser.instance.clone();
+        let mods_dir = self
+            .instance_manager
+            .get_game_dir(&instance.info.id)
+            .join("mods");
+        let project = hit.id.clone();
+        let title = hit.title.clone();
+
+        self.is_loading = true;
+        self.status_message = format!("Installing {}...", title);
+
+        let (tx, rx) = mpsc::channel();
+        self.async_receiver = Some(rx);
+
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                let progress_tx = tx.clone();
+                let on_progress = move |msg: &str| {
+                    let _ = progress_tx.send(AsyncResult::LaunchProgress(msg.to_string()));
+                };
+                let (version, loader) = (&instance.info.version, &instance.info.loader);
+                let result = match source {
+                    ModSource::Modrinth => {
+                        let client = ModrinthClient::new();
+                        mods::install_project(
+                            &client,
+                            &project,
+                            &mods_dir,
+                            version,
+                            loader,
+                            &on_progress,
+                        )
+                        .await
+                    }
+                    ModSource::CurseForge => match CurseForgeClient::from_config() {
+                        Ok(client) => {
+                            mods::install_project(
+                                &client,
+                                &project,
+                                &mods_dir,
+                                version,
+                                loader,
+                                &on_progress,
+                            )
+                            .await
+                        }
+                        Err(e) => Err(e),
+                    },
+                };
+
+                let message = match result {
+                    Ok(report) if !report.manual.is_empty() => {
+                        let files: Vec<_> = report
+                            .manual
+                            .iter()
+                            .map(|f| f.page_url.clone().unwrap_or_else(|| f.filename.clone()))
+                            .collect();
+                        Ok(format!(
+                            "Installed {} file(s). Download these manually into {}: {}",
+                            report.installed.len(),
+                            mods_dir.display(),
+                            files.join(", ")
+                        ))
+                    }
+                    Ok(report) if report.installed.is_empty() => {
+                        Ok(format!("{} is already installed", title))
+                    }
+                    Ok(report) => Ok(format!(
+                        "Installed {} ({} file(s))",
+                        title,
+                        report.installed.len()
+                    )),
+                    Err(e) => Err(e),
+                };
+                let _ = tx.send(match message {
+                    Ok(message) => AsyncResult::ModsChanged(message),
+                    Err(e) => AsyncResult::Error(format!("Failed to install {}: {}", title, e)),
+                });
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn check_mod_updates(&mut self, instance: &Instance, ctx: &egui::Context) {
+        self.checking_mod_updates = true;
+        self.mod_updates.clear();
+
+        let mods_dir = self
+            .instance_manager
+            .get_game_dir(&instance.info.id)
+            .join("mods");
+        let mc_version = instance.info.version.clone();
+        let loader = instance.info.loader.clone();
+
+        let (tx, rx) = mpsc::channel();
+        self.async_receiver = Some(rx);
+
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                let result = match ModManager::new(&mods_dir).list_mods() {
+                    Ok(mods) => {
+                        mods::check_updates(&ModrinthClient::new(), &mods, &mc_version, &loader)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                let _ = tx.send(match result {
+                    Ok(updates) => AsyncResult::ModUpdates(updates),
+                    Err(e) => AsyncResult::Error(format!("Update check failed: {}", e)),
+                });
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn apply_mod_updates(&mut self, instance: &Instance, ctx: &egui::Context) {
+        let updates = std::mem::take(&mut self.mod_updates);
+        let mods_dir = self
+            .instance_manager
+            .get_game_dir(&instance.info.id)
+            .join("mods");
+
+        self.is_loading = true;
+        self.status_message = format!("Updating {} mod(s)...", updates.len());
+
+        let (tx, rx) = mpsc::channel();
+        self.async_receiver = Some(rx);
+
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            rt.block_on(async {
+                let client = reqwest::Client::new();
+                for update in &updates {
+                    let _ = tx.send(AsyncResult::LaunchProgress(format!(
+                        "Downloading {}...",
+                        update.file.filename
+                    )));
+                    if let Err(e) = mods::apply_update(&client, update, &mods_dir).await {
+                        let _ = tx.send(AsyncResult::Error(format!(
+                            "Failed to update {}: {}",
+                            update.name, e
+                        )));
+                        return;
+                    }
+                }
+                let _ = tx.send(AsyncResult::ModsChanged(format!(
+                    "Updated {} mod(s)",
+                    updates.len()
+                )));
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn show_mod_browser(&mut self, ctx: &egui::Context) {
+        let Some(browser) = &mut self.mod_browser else {
+            return;
+        };
+
+        let mut open = true;
+        let mut search = false;
+        let mut install: Option<ProjectHit> = None;
+        let busy = browser.searching || browser.installing.is_some();
+
+        egui::Window::new(format!("🔍 Mods — {}", browser.instance.info.name))
+            .open(&mut open)
+            .collapsible(false)
+            .default_width(450.0)
+            .show(ctx, |ui| {
+                ui.label(
+                    egui::RichText::new(format!(
+                        "Minecraft {} · {}",
+                        browser.instance.info.version, browser.instance.info.loader
+                    ))
+                    .weak(),
+                );
+
+                ui.horizontal(|ui| {
+                    ui.label("Source:");
+                    let before = browser.source;
+                    ui.add_enabled_ui(!busy, |ui| {
+                        egui::ComboBox::from_id_salt("mod_browser_source")
+                            .selected_text(browser.source.to_string())
+                            .show_ui(ui, |ui| {
+                                for source in [ModSource::Modrinth, ModSource::CurseForge] {
+                                    ui.selectable_value(
+                                        &mut browser.source,
+                                        source,
+                                        source.to_string(),
+                                    );
+                                }
+                            });
+                    });
+                    if browser.source != before {
+                        browser.results.clear();
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    let response = ui.text_edit_singleline(&mut browser.query);
+                    let entered =
+                        response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if (ui.add_enabled(!busy, egui::Button::new("Search")).clicked() || entered)
+                        && !busy
+                    {
+                        search = true;
+                    }
+                    if browser.searching {
+                        ui.spinner();
+                    }
+                });
+
+                ui.add_space(5.0);
+
+                egui::ScrollArea::vertical()
+                    .max_height(350.0)
+                    .show(ui, |ui| {
+                        if browser.results.is_empty() && !browser.searching {
+                            ui.label("No results");
+                        }
+                        for hit in &browser.results {
+                            ui.horizontal(|ui| {
+                                ui.vertical(|ui| {
+                                    ui.strong(&hit.title).on_hover_text(format!(
+                                        "{}\nby {} · {} downloads",
+                                        hit.slug, hit.author, hit.downloads
+                                    ));
+                                    ui.label(egui::RichText::new(&hit.description).small());
+                                });
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        if browser.installing.as_deref() == Some(hit.id.as_str()) {
+                                            ui.spinner();
+                                        } else if ui
+                                            .add_enabled(!busy, egui::Button::new("Install"))
+                                            .clicked()
+                                        {
+                                            install = Some(hit.clone());
+                                        }
+                                    },
+                                );
+                            });
+                            ui.separator();
+                        }
+                    });
+            });
+
+        if !open {
+            self.mod_browser = None;
+        } else if search {
+            self.search_mods(ctx);
+        } else if let Some(hit) = install {
+            self.install_mod(&hit, ctx);
+        }
+    }
+
+    /// Load Java installations from the registry cache, or rescan the system
+    fn load_java_installations(&mut self, rescan: bool) {
+        let java_dir = crate::config::config_dir().join("java");
+        let mut registry = JavaRegistry::load(&java_dir);
+        if (rescan || registry.installations().is_empty())
+            && let Err(e) = registry.refresh()
+        {
+            tracing::warn!("Failed to scan for Java: {}", e);
+        }
+        self.java_installations = registry.installations().to_vec();
+    }
+
     fn show_instance_settings_dialog(&mut self, _ctx: &egui::Context) {
         // Take instance to avoid borrow conflicts
         let mut instance = match self.settings_instance.take() {
@@ -887,8 +2059,19 @@ impl LauncherApp {
 
         // Track original name for rename
         let original_name = instance.info.name.clone();
+        let original_favorite = instance.info.favorite;
+        let instance_id = instance.info.id.clone();
         let mut should_close = false;
         let mut save_result: Option<Result<(), String>> = None;
+        let mut rescan_java = false;
+        let mut open_browser = false;
+        let mut check_updates = false;
+        let mut apply_updates = false;
+        let mut export_format = None;
+        let mut backup_action = None;
+        let mut snapshot_action = None;
+        let mut icon_choice = None;
+        let mut save_template = None;
 
         egui::Window::new(format!("⚙️ {} Settings", instance.info.name))
             .collapsible(false)
@@ -906,6 +2089,46 @@ impl LauncherApp {
                     ui.text_edit_singleline(&mut instance.info.name);
                 });
 
+                ui.horizontal(|ui| {
+                    ui.label("Group:");
+                    ui.add(egui::TextEdit::singleline(&mut instance.info.group).hint_text("None"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Tags:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.settings_tags)
+                            .hint_text("Comma separated"),
+                    );
+                });
+                ui.checkbox(&mut instance.info.favorite, "⭐ Favourite");
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.label("Icon:");
+                    for (name, emoji) in BUILTIN_ICONS {
+                        let current = instance.info.icon == *name
+                            || (instance.info.icon.is_empty() && *name == BUILTIN_ICONS[0].0);
+                        if ui.selectable_label(current, *emoji).on_hover_text(*name).clicked() {
+                            icon_choice = Some(IconChoice::Builtin(name));
+                        }
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Custom:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.settings_icon_path)
+                            .hint_text("/path/to/icon.png"),
+                    );
+                    let has_path = !self.settings_icon_path.trim().is_empty();
+                    if ui.add_enabled(has_path, egui::Button::new("Use image")).clicked() {
+                        icon_choice = Some(IconChoice::File(
+                            self.settings_icon_path.trim().to_string(),
+                        ));
+                    }
+                    if instance.info.icon == CUSTOM_ICON {
+                        ui.label(egui::RichText::new("(custom icon set)").weak().small());
+                    }
+                });
+
                 // MC Version (display only)
                 ui.horizontal(|ui| {
                     ui.label("Minecraft:");
@@ -999,6 +2222,64 @@ impl LauncherApp {
                         });
                 });
 
+                ui.add_space(10.0);
+                ui.heading("Java");
+                ui.add_space(5.0);
+
+                ui.horizontal(|ui| {
+                    ui.label("Java:");
+                    let selected = if instance.java.override_global {
+                        self.java_installations
+                            .iter()
+                            .find(|j| j.path.to_string_lossy() == instance.java.path)
+                            .map(|j| format!("Java {} ({})", j.version, j.vendor))
+                            .unwrap_or_else(|| "Custom".to_string())
+                    } else {
+                        "Auto".to_string()
+                    };
+                    egui::ComboBox::from_id_salt("java_select")
+                        .selected_text(selected)
+                        .show_ui(ui, |ui| {
+                            if ui
+                                .selectable_label(!instance.java.override_global, "Auto")
+                                .clicked()
+                            {
+                                instance.java.override_global = false;
+                                instance.java.path.clear();
+                            }
+                            for java in &self.java_installations {
+                                let path = java.path.to_string_lossy().to_string();
+                                let label = format!(
+                                    "Java {} ({}, {}){}",
+                                    java.version,
+                                    java.vendor,
+                                    java.arch,
+                                    if java.managed { " [managed]" } else { "" }
+                                );
+                                let is_selected =
+                                    instance.java.override_global && instance.java.path == path;
+                                if ui
+                                    .selectable_label(is_selected, label)
+                                    .on_hover_text(&path)
+                                    .clicked()
+                                {
+                                    instance.java.override_global = true;
+                                    instance.java.path = path;
+                                }
+                            }
+                        });
+                    if ui.button("🔄").on_hover_text("Rescan").clicked() {
+                        rescan_java = true;
+                    }
+                });
+
+                if instance.java.override_global {
+                    ui.horizontal(|ui| {
+                        ui.label("Path:");
+                        ui.text_edit_singleline(&mut instance.java.path);
+                    });
+                }
+
                 ui.add_space(10.0);
                 ui.heading("Display");
                 ui.add_space(5.0);
@@ -1025,15 +2306,60 @@ impl LauncherApp {
                 ui.add_space(5.0);
 
                 // Get mods directory
-                let game_dir = self.instance_manager.get_game_dir(&instance.info.name);
+                let game_dir = self.instance_manager.get_game_dir(&instance.info.id);
                 let mods_dir = game_dir.join("mods");
                 let mod_manager = ModManager::new(&mods_dir);
 
-                // Open folder button
-                if ui.button("📁 Open Mods Folder").clicked() {
-                    if let Err(e) = mod_manager.open_folder() {
+                ui.horizontal(|ui| {
+                    // Open folder button
+                    if ui.button("📁 Open Mods Folder").clicked()
+                        && let Err(e) = mod_manager.open_folder()
+                    {
                         tracing::error!("Failed to open mods folder: {}", e);
                     }
+
+                    if ui
+                        .add_enabled(
+                            instance.info.loader != ModLoader::Vanilla,
+                            egui::Button::new("🔍 Browse Mods"),
+                        )
+                        .clicked()
+                    {
+                        open_browser = true;
+                    }
+
+                    if self.checking_mod_updates {
+                        ui.spinner();
+                    } else if ui
+                        .add_enabled(
+                            instance.info.loader != ModLoader::Vanilla && !self.is_loading,
+                            egui::Button::new("⬆ Check Updates"),
+                        )
+                        .clicked()
+                    {
+                        check_updates = true;
+                    }
+                });
+
+                if !self.mod_updates.is_empty() {
+                    ui.add_space(3.0);
+                    ui.label(format!("{} update(s) available:", self.mod_updates.len()));
+                    for update in &self.mod_updates {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "{} {} → {}",
+                                update.name, update.current_version, update.file.version_number
+                            ))
+                            .small(),
+                        );
+                    }
+                    if ui
+                        .add_enabled(!self.is_loading, egui::Button::new("Apply Updates"))
+                        .on_hover_text("Old versions are kept as .jar.disabled")
+                        .clicked()
+                    {
+                        apply_updates = true;
+                    }
                 }
 
                 ui.add_space(5.0);
@@ -1047,6 +2373,16 @@ impl LauncherApp {
                             ui.label(format!("{} mod(s) installed", mods.len()));
                             ui.add_space(3.0);
 
+                            let env = ModEnvironment::for_instance(&instance);
+                            for issue in mods::check_mods(&mods, &env) {
+                                let color = if issue.is_fatal() {
+                                    egui::Color32::RED
+                                } else {
+                                    egui::Color32::YELLOW
+                                };
+                                ui.colored_label(color, format!("⚠️ {}", issue));
+                            }
+
                             egui::ScrollArea::vertical()
                                 .max_height(150.0)
                                 .show(ui, |ui| {
@@ -1061,7 +2397,22 @@ impl LauncherApp {
                                             }
 
                                             // Mod info
-                                            ui.label(&mod_info.name);
+                                            let mut details = Vec::new();
+                                            if !mod_info.mod_id.is_empty() {
+                                                details.push(format!("ID: {}", mod_info.mod_id));
+                                            }
+                                            if let Some(ref loader) = mod_info.loader {
+                                                details.push(format!("Loader: {}", loader));
+                                            }
+                                            if !mod_info.authors.is_empty() {
+                                                details.push(format!(
+                                                    "Authors: {}",
+                                                    mod_info.authors.join(", ")
+                                                ));
+                                            }
+                                            details.push(mod_info.filename.clone());
+                                            ui.label(&mod_info.name)
+                                                .on_hover_text(details.join("\n"));
                                             ui.label(
                                                 egui::RichText::new(&mod_info.version)
                                                     .weak()
@@ -1082,6 +2433,216 @@ impl LauncherApp {
                     }
                 }
 
+                ui.add_space(10.0);
+                ui.collapsing("🌍 Worlds & Backups", |ui| {
+                    let backups = BackupManager::new(
+                        &self.instance_manager.get_instance_dir(&instance_id),
+                    );
+
+                    match backups.worlds() {
+                        Ok(worlds) if worlds.is_empty() => {
+                            ui.label("No worlds yet.");
+                        }
+                        Ok(worlds) => {
+                            for world in worlds {
+                                ui.horizontal(|ui| {
+                                    ui.label(&world);
+                                    if ui
+                                        .add_enabled(!self.is_loading, egui::Button::new("💾 Back up"))
+                                        .clicked()
+                                    {
+                                        backup_action = Some(BackupAction::Backup(world.clone()));
+                                    }
+                                });
+                            }
+                        }
+                        Err(e) => {
+                            ui.label(format!("Error listing worlds: {}", e));
+                        }
+                    }
+
+                    ui.add_space(5.0);
+                    match backups.list(None) {
+                        Ok(list) if list.is_empty() => {
+                            ui.label(egui::RichText::new("No backups").weak());
+                        }
+                        Ok(list) => {
+                            egui::ScrollArea::vertical()
+                                .id_salt("world_backups")
+                                .max_height(120.0)
+                                .show(ui, |ui| {
+                                    for backup in list {
+                                        ui.horizontal(|ui| {
+                                            ui.label(format!(
+                                                "{} — {}",
+                                                backup.world,
+                                                backup.created_at.format("%Y-%m-%d %H:%M")
+                                            ));
+                                            ui.label(
+                                                egui::RichText::new(format_size(backup.size))
+                                                    .weak()
+                                                    .small(),
+                                            );
+                                            if ui
+                                                .add_enabled(
+                                                    !self.is_loading,
+                                                    egui::Button::new("♻ Restore"),
+                                                )
+                                                .on_hover_text("Replace the world with this backup")
+                                                .clicked()
+                                            {
+                                                backup_action =
+                                                    Some(BackupAction::Restore(backup.clone()));
+                                            }
+                                            if ui.small_button("🗑").clicked() {
+                                                backup_action = Some(BackupAction::Delete(backup));
+                                            }
+                                        });
+                                    }
+                                });
+                        }
+                        Err(e) => {
+                            ui.label(format!("Error listing backups: {}", e));
+                        }
+                    }
+                });
+
+                ui.add_space(10.0);
+                ui.collapsing("📸 Snapshots", |ui| {
+                    ui.label(
+                        egui::RichText::new("Saves mods, config and instance settings")
+                            .weak()
+                            .small(),
+                    );
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut self.snapshot_form.name);
+                        let name = self.snapshot_form.name.trim();
+                        if ui
+                            .add_enabled(
+                                !self.is_loading && !name.is_empty(),
+                                egui::Button::new("📸 Take snapshot"),
+                            )
+                            .clicked()
+                        {
+                            snapshot_action = Some(SnapshotAction::Create(name.to_string()));
+                        }
+                    });
+
+                    let snapshots =
+                        SnapshotManager::new(&self.instance_manager.get_instance_dir(&instance_id));
+                    match snapshots.list() {
+                        Ok(list) if list.is_empty() => {
+                            ui.label(egui::RichText::new("No snapshots").weak());
+                        }
+                        Ok(list) => {
+                            egui::ScrollArea::vertical()
+                                .id_salt("instance_snapshots")
+                                .max_height(120.0)
+                                .show(ui, |ui| {
+                                    for snapshot in list {
+                                        ui.horizontal(|ui| {
+                                            ui.label(&snapshot.name);
+                                            ui.label(
+                                                egui::RichText::new(format!(
+                                                    "{} · {} files",
+                                                    snapshot
+                                                        .created_at
+                                                        .with_timezone(&chrono::Local)
+                                                        .format("%Y-%m-%d %H:%M"),
+                                                    snapshot.files.len()
+                                                ))
+                                                .weak()
+                                                .small(),
+                                            );
+                                            if ui
+                                                .small_button("🔍")
+                                                .on_hover_text("Show changes since this snapshot")
+                                                .clicked()
+                                            {
+                                                snapshot_action =
+                                                    Some(SnapshotAction::Diff(snapshot.name.clone()));
+                                            }
+                                            if ui
+                                                .add_enabled(
+                                                    !self.is_loading,
+                                                    egui::Button::new("♻ Restore"),
+                                                )
+                                                .on_hover_text(
+                                                    "Roll mods, config and settings back to this snapshot",
+                                                )
+                                                .clicked()
+                                            {
+                                                snapshot_action =
+                                                    Some(SnapshotAction::Restore(snapshot.name.clone()));
+                                            }
+                                            if ui.small_button("🗑").clicked() {
+                                                snapshot_action =
+                                                    Some(SnapshotAction::Delete(snapshot.name));
+                                            }
+                                        });
+                                    }
+                                });
+                        }
+                        Err(e) => {
+                            ui.label(format!("Error listing snapshots: {}", e));
+                        }
+                    }
+
+                    if let Some((name, diff)) = &self.snapshot_form.diff {
+                        ui.add_space(5.0);
+                        if diff.is_empty() {
+                            ui.label(format!("No changes since '{}'", name));
+                        } else {
+                            ui.label(format!("Changes since '{}':", name));
+                            egui::ScrollArea::vertical()
+                                .id_salt("snapshot_diff")
+                                .max_height(100.0)
+                                .show(ui, |ui| {
+                                    let lines = [
+                                        ("+", &diff.added, egui::Color32::GREEN),
+                                        ("-", &diff.removed, egui::Color32::RED),
+                                        ("~", &diff.changed, egui::Color32::YELLOW),
+                                    ];
+                                    for (sign, paths, color) in lines {
+                                        for path in paths {
+                                            ui.label(
+                                                egui::RichText::new(format!("{} {}", sign, path))
+                                                    .color(color)
+                                                    .small(),
+                                            );
+                                        }
+                                    }
+                                });
+                        }
+                    }
+                });
+
+                ui.add_space(10.0);
+                ui.collapsing("📋 Template", |ui| {
+                    ui.label(
+                        egui::RichText::new(
+                            "Saves memory, JVM arguments, resolution, mods and options.txt \
+                             as defaults for new instances",
+                        )
+                        .weak()
+                        .small(),
+                    );
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut self.settings_template_name);
+                        let name = self.settings_template_name.trim();
+                        if ui
+                            .add_enabled(
+                                !self.is_loading && !name.is_empty(),
+                                egui::Button::new("💾 Save as template"),
+                            )
+                            .on_hover_text("Uses the saved settings of this instance")
+                            .clicked()
+                        {
+                            save_template = Some(name.to_string());
+                        }
+                    });
+                });
+
                 ui.add_space(15.0);
                 ui.separator();
                 ui.add_space(5.0);
@@ -1094,13 +2655,96 @@ impl LauncherApp {
                     if ui.button("Save").clicked() {
                         save_result = Some(Ok(()));
                     }
+
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui
+                            .add_enabled(!self.is_loading, egui::Button::new("📤 Export .mrpack"))
+                            .on_hover_text("Saved to your Downloads folder")
+                            .clicked()
+                        {
+                            export_format = Some(ExportFormat::Mrpack);
+                        }
+                        if ui
+                            .add_enabled(!self.is_loading, egui::Button::new("📦 Export .zip"))
+                            .on_hover_text(
+                                "Portable copy with mods, config, resource packs, saves and options",
+                            )
+                            .clicked()
+                        {
+                            export_format = Some(ExportFormat::Zip);
+                        }
+                    });
                 });
             });
 
+        if rescan_java {
+            self.load_java_installations(true);
+        }
+
+        if check_updates {
+            self.check_mod_updates(&instance, _ctx);
+        }
+
+        if let Some(format) = export_format {
+            self.export_pack(&original_name, format, _ctx);
+        }
+
+        if let Some(action) = backup_action {
+            self.run_backup_action(&instance_id, action, _ctx);
+        }
+
+        if let Some(choice) = icon_choice {
+            let result = match choice {
+                IconChoice::Builtin(name) => {
+                    self.instance_manager.set_builtin_icon(&instance_id, name)
+                }
+                IconChoice::File(path) => self
+                    .instance_manager
+                    .set_icon_file(&instance_id, std::path::Path::new(&path)),
+            };
+            match result {
+                Ok(updated) => {
+                    instance.info.icon = updated.info.icon;
+                    self.settings_icon_path.clear();
+                    self.icon_textures.remove(&instance_id);
+                    self.refresh_instances();
+                }
+                Err(e) => self.error_message = Some(format!("{:#}", e)),
+            }
+        }
+
+        if let Some(name) = save_template {
+            self.save_template(&instance_id, name, _ctx);
+        }
+
+        if let Some(action) = snapshot_action {
+            if matches!(action, SnapshotAction::Restore(_)) {
+                // The restored instance.toml replaces the settings being edited
+                self.show_settings_dialog = false;
+            }
+            self.run_snapshot_action(&instance_id, action, _ctx);
+        }
+
+        if apply_updates {
+            self.apply_mod_updates(&instance, _ctx);
+        }
+
+        if open_browser {
+            self.mod_browser = Some(ModBrowser {
+                instance: instance.clone(),
+                source: ModSource::default(),
+                query: String::new(),
+                results: Vec::new(),
+                searching: false,
+                installing: None,
+            });
+        }
+
         if should_close {
             self.show_settings_dialog = false;
             // Don't put instance back - it's discarded
         } else if save_result.is_some() {
+            self.show_settings_dialog = false;
             // Handle rename if name changed
             if original_name != instance.info.name {
                 if let Err(e) = self
@@ -1108,24 +2752,190 @@ impl LauncherApp {
                     .rename(&original_name, &instance.info.name)
                 {
                     self.error_message = Some(format!("Failed to rename: {}", e));
-                    self.show_settings_dialog = false;
                     return;
                 }
             }
-            // Save the instance
-            if let Err(e) = self.instance_manager.save(&instance) {
+
+            // Start from the file on disk so play stats and favourites changed
+            // while the dialog was open are kept
+            let mut saved = match self.instance_manager.load_id(&instance_id) {
+                Ok(saved) => saved,
+                Err(e) => {
+                    self.error_message = Some(format!("Failed to save: {}", e));
+                    return;
+                }
+            };
+            saved.info.group = instance.info.group.trim().to_string();
+            saved.info.tags = parse_tags(&self.settings_tags);
+            if instance.info.favorite != original_favorite {
+                saved.info.favorite = instance.info.favorite;
+            }
+            saved.info.loader = instance.info.loader;
+            saved.info.loader_version = instance.info.loader_version;
+            saved.java = instance.java;
+            saved.game = instance.game;
+
+            if let Err(e) = self.instance_manager.save(&saved) {
                 self.error_message = Some(format!("Failed to save: {}", e));
             } else {
-                self.success_message = Some(format!("Settings saved for {}", instance.info.name));
+                self.success_message = Some(format!("Settings saved for {}", saved.info.name));
                 self.refresh_instances();
             }
-            self.show_settings_dialog = false;
         } else {
             // Put instance back
             self.settings_instance = Some(instance);
         }
     }
 
+    fn run_backup_action(&mut self, id: &str, action: BackupAction, ctx: &egui::Context) {
+        let keep = crate::config::load().unwrap_or_default().backups.keep;
+        let manager = BackupManager::new(&self.instance_manager.get_instance_dir(id)).keep(keep);
+
+        if let BackupAction::Delete(backup) = &action {
+            match manager.delete(backup) {
+                Ok(()) => self.success_message = Some(format!("Deleted {}", backup.file_name())),
+                Err(e) => self.error_message = Some(e.to_string()),
+            }
+            return;
+        }
+
+        self.is_loading = true;
+        self.status_message = match &action {
+            BackupAction::Restore(backup) => format!("Restoring {}...", backup.world),
+            _ => "Backing up...".to_string(),
+        };
+
+        let (tx, rx) = mpsc::channel();
+        self.async_receiver = Some(rx);
+
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let result = match action {
+                BackupAction::Backup(world) => manager
+                    .backup_world(&world)
+                    .map(|b| format!("Backed up {} ({})", b.world, format_size(b.size))),
+                BackupAction::Restore(backup) => manager
+                    .restore(&backup)
+                    .map(|_| format!("Restored {} from {}", backup.world, backup.file_name())),
+                BackupAction::Delete(_) => unreachable!("handled synchronously"),
+            };
+            let _ = tx.send(match result {
+                Ok(message) => AsyncResult::BackupsChanged(message),
+                Err(e) => AsyncResult::Error(format!("{:#}", e)),
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn save_template(&mut self, id: &str, name: String, ctx: &egui::Context) {
+        let instance = match self.instance_manager.load_id(id) {
+            Ok(instance) => instance,
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+                return;
+            }
+        };
+        let game_dir = self.instance_manager.get_game_dir(&instance.info.id);
+
+        self.is_loading = true;
+        self.status_message = format!("Saving template {}...", name);
+
+        let (tx, rx) = mpsc::channel();
+        self.async_receiver = Some(rx);
+
+        let ctx = ctx.clone();
+
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            let result = rt.block_on(async {
+                let mut template = InstanceTemplate::from_instance(&name, &instance, &game_dir)?;
+                let (projects, unknown) =
+                    template_mods(&ModrinthClient::new(), &game_dir.join("mods")).await?;
+                template.mods = projects;
+                TemplateManager::new().save(&template)?;
+
+                let mut message = format!(
+                    "Template {} saved ({} mods)",
+                    template.name,
+                    template.mods.len()
+                );
+                if !unknown.is_empty() {
+                    message.push_str(&format!(". Not found on Modrinth: {}", unknown.join(", ")));
+                }
+                anyhow::Ok(message)
+            });
+            let _ = tx.send(match result {
+                Ok(message) => AsyncResult::TemplateSaved(message),
+                Err(e) => AsyncResult::Error(format!("{:#}", e)),
+            });
+            ctx.request_repaint();
+        });
+    }
+
+    fn run_snapshot_action(&mut self, id: &str, action: SnapshotAction, ctx: &egui::Context) {
+        let manager = SnapshotManager::new(&self.instance_manager.get_instance_dir(id));
+        let restore = matches!(action, SnapshotAction::Restore(_));
+
+        match action {
+            SnapshotAction::Diff(snapshot) => {
+                self.is_loading = true;
+                self.status_message = format!("Comparing with snapshot {}...", snapshot);
+
+                let (tx, rx) = mpsc::channel();
+                self.async_receiver = Some(rx);
+
+                let ctx = ctx.clone();
+
+                std::thread::spawn(move || {
+                    let _ = tx.send(match manager.diff(&snapshot, None) {
+                        Ok(diff) => AsyncResult::SnapshotDiff(snapshot, diff),
+                        Err(e) => AsyncResult::Error(format!("{:#}", e)),
+                    });
+                    ctx.request_repaint();
+                });
+            }
+            SnapshotAction::Delete(snapshot) => match manager.delete(&snapshot) {
+                Ok(_) => {
+                    self.snapshot_form.diff = None;
+                    self.success_message = Some(format!("Deleted snapshot {}", snapshot));
+                }
+                Err(e) => self.error_message = Some(e.to_string()),
+            },
+            SnapshotAction::Create(snapshot) | SnapshotAction::Restore(snapshot) => {
+                self.snapshot_form = SnapshotForm::default();
+                self.is_loading = true;
+                self.status_message = if restore {
+                    format!("Restoring snapshot {}...", snapshot)
+                } else {
+                    "Taking snapshot...".to_string()
+                };
+
+                let (tx, rx) = mpsc::channel();
+                self.async_receiver = Some(rx);
+
+                let ctx = ctx.clone();
+
+                std::thread::spawn(move || {
+                    let result = if restore {
+                        manager
+                            .restore(&snapshot)
+                            .map(|_| format!("Restored snapshot {}", snapshot))
+                    } else {
+                        manager
+                            .create(&snapshot)
+                            .map(|s| format!("Snapshot {} saved ({} files)", s.name, s.files.len()))
+                    };
+                    let _ = tx.send(match result {
+                        Ok(message) => AsyncResult::SnapshotsChanged(message),
+                        Err(e) => AsyncResult::Error(format!("{:#}", e)),
+                    });
+                    ctx.request_repaint();
+                });
+            }
+        }
+    }
+
     fn show_accounts(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
         ui.heading("Accounts");
         ui.separator();
@@ -1282,15 +3092,56 @@ impl LauncherApp {
             ui.label("Theme: Dark");
             ui.label("Language: Japanese");
         });
+
+        ui.collapsing("Mod Sources", |ui| {
+            ui.horizontal(|ui| {
+                ui.label("CurseForge API key:");
+                ui.add(egui::TextEdit::singleline(&mut self.curseforge_api_key).password(true));
+                if ui.button("Save").clicked() {
+                    let result = crate::config::load().and_then(|mut config| {
+                        config.mods.curseforge_api_key = self.curseforge_api_key.trim().to_string();
+                        crate::config::save(&config)
+                    });
+                    match result {
+                        Ok(()) => self.success_message = Some("API key saved".to_string()),
+                        Err(e) => self.error_message = Some(format!("Failed to save: {}", e)),
+                    }
+                }
+            });
+        });
+
+        ui.collapsing("World Backups", |ui| {
+            ui.checkbox(
+                &mut self.backup_config.before_launch,
+                "Back up worlds before every launch",
+            );
+            ui.horizontal(|ui| {
+                ui.label("Backups kept per world:");
+                ui.add(egui::DragValue::new(&mut self.backup_config.keep).range(0..=100));
+                ui.label(egui::RichText::new("(0 = keep all)").weak());
+            });
+            if ui.button("Save").clicked() {
+                let result = crate::config::load().and_then(|mut config| {
+                    config.backups = self.backup_config.clone();
+                    crate::config::save(&config)
+                });
+                match result {
+                    Ok(()) => self.success_message = Some("Backup settings saved".to_string()),
+                    Err(e) => self.error_message = Some(format!("Failed to save: {}", e)),
+                }
+            }
+        });
     }
 }
 
 /// Launch an instance (runs in background thread)
+///
+/// Returns the game session when Minecraft is running.
 async fn launch_instance(
     instance: Instance,
     account: Option<Account>,
     tx: mpsc::Sender<AsyncResult>,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<Option<GameSession>> {
     println!("=== launch_instance START ===");
     let account = account.context("No account. Please login first.")?;
     println!("Account OK: {}", account.profile.name);
@@ -1302,9 +3153,10 @@ async fn launch_instance(
     })
     .await
     {
-        Ok(LaunchResult::Success(_child)) => {
+        Ok(LaunchResult::Success(session)) => {
             tracing::info!("Minecraft process is running");
             let _ = tx.send(AsyncResult::LaunchSuccess);
+            return Ok(Some(session));
         }
         Ok(LaunchResult::EarlyExit(code)) => {
             let error_msg = format!(
@@ -1321,5 +3173,5 @@ async fn launch_instance(
         }
     }
 
-    Ok(())
+    Ok(None)
 }
@@ -22,6 +22,10 @@ async fn main() -> anyhow::Result<()> {
 
     let args = Args::parse();
 
+    if let Err(e) = core::instance::InstanceManager::new().migrate_legacy() {
+        tracing::warn!("Failed to migrate instances: {}", e);
+    }
+
     // Handle subcommands first
     if let Some(command) = args.command {
         return handle_command(command).await;
@@ -34,7 +38,7 @@ async fn main() -> anyhow::Result<()> {
         cli::run_instance(instance_name, args.offline).await?;
     } else if args.list {
         // List instances
-        cli::list_instances()?;
+        cli::list_instances(args.group.as_deref(), args.tag.as_deref())?;
     } else {
         // GUI mode: Start the launcher UI
         tracing::info!("Starting gLauncher GUI");
@@ -50,8 +54,54 @@ async fn handle_command(command: Commands) -> anyhow::Result<()> {
             name,
             version,
             loader,
-        } => cli::create_instance(&name, &version, &loader).await,
+            template,
+        } => cli::create_instance(&name, &version, &loader, template.as_deref()).await,
+        Commands::Clone {
+            source,
+            name,
+            no_saves,
+            no_links,
+        } => cli::clone_instance(&source, &name, !no_saves, !no_links),
+        Commands::Snapshot { action } => cli::handle_snapshot(action),
+        Commands::Template { action } => cli::handle_template(action).await,
         Commands::Auth { action } => cli::handle_auth(action).await,
+        Commands::Java { action } => cli::handle_java(action).await,
+        Commands::Backup {
+            instance,
+            world,
+            list,
+            force,
+        } => {
+            if list {
+                cli::list_backups(&instance, world.as_deref())
+            } else {
+                cli::backup_worlds(&instance, world.as_deref(), force)
+            }
+        }
+        Commands::Restore {
+            instance,
+            backup,
+            force,
+        } => cli::restore_backup(&instance, &backup, force),
+        Commands::Import {
+            path,
+            name,
+            profile,
+            symlink,
+        } => {
+            if std::path::Path::new(&path).is_dir() {
+                cli::import_instance(&path, name.as_deref(), profile.as_deref(), symlink)
+            } else {
+                cli::import_pack(&path, name.as_deref()).await
+            }
+        }
+        Commands::Export {
+            instance,
+            output,
+            format,
+            include,
+        } => cli::export_instance(&instance, output.as_deref(), format.as_deref(), &include).await,
+        Commands::Mods { action } => cli::handle_mods(action).await,
         Commands::Update => {
             use crate::core::update::UpdateManager;
             UpdateManager::update()?;
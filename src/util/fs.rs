@@ -0,0 +1,122 @@
+//! Filesystem helpers
+//!
+//! Recursive directory copies (with copy-on-write clones and hardlinks
+//! where possible) and directory links.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Recursively copy `src` into `dst`, returning the number of files copied
+///
+/// Top-level entries named in `exclude` are skipped. Symlinks are recreated
+/// where the platform allows it and copied as files otherwise.
+pub fn copy_dir(src: &Path, dst: &Path, exclude: &[&str]) -> Result<usize> {
+    copy_dir_linked(src, dst, exclude, &|_| false)
+}
+
+/// Like [`copy_dir`], but shares file contents with the source when possible
+///
+/// Every file is first cloned copy-on-write (reflink) where the filesystem
+/// supports it. Otherwise files for which `can_hardlink` returns true (given
+/// the path relative to `src`) are hardlinked, and the rest are copied.
+/// Only hardlink files that are replaced rather than edited in place.
+pub fn copy_dir_linked(
+    src: &Path,
+    dst: &Path,
+    exclude: &[&str],
+    can_hardlink: &dyn Fn(&Path) -> bool,
+) -> Result<usize> {
+    copy_tree(src, src, dst, exclude, can_hardlink)
+}
+
+fn copy_tree(
+    root: &Path,
+    src: &Path,
+    dst: &Path,
+    exclude: &[&str],
+    can_hardlink: &dyn Fn(&Path) -> bool,
+) -> Result<usize> {
+    std::fs::create_dir_all(dst).with_context(|| format!("Failed to create {}", dst.display()))?;
+    let mut count = 0;
+
+    for entry in
+        std::fs::read_dir(src).with_context(|| format!("Failed to read {}", src.display()))?
+    {
+        let entry = entry?;
+        if exclude.iter().any(|e| entry.file_name() == *e) {
+            continue;
+        }
+        count += copy_entry(
+            root,
+            &entry.path(),
+            &dst.join(entry.file_name()),
+            can_hardlink,
+        )?;
+    }
+    Ok(count)
+}
+
+fn copy_entry(
+    root: &Path,
+    src: &Path,
+    dst: &Path,
+    can_hardlink: &dyn Fn(&Path) -> bool,
+) -> Result<usize> {
+    let file_type = std::fs::symlink_metadata(src)?.file_type();
+
+    #[cfg(unix)]
+    if file_type.is_symlink() {
+        std::os::unix::fs::symlink(std::fs::read_link(src)?, dst)
+            .with_context(|| format!("Failed to link {}", dst.display()))?;
+        return Ok(1);
+    }
+
+    if file_type.is_dir() || (file_type.is_symlink() && src.is_dir()) {
+        return copy_tree(root, src, dst, &[], can_hardlink);
+    }
+
+    if reflink_copy::reflink(src, dst).is_ok() {
+        return Ok(1);
+    }
+    let relative = src.strip_prefix(root).unwrap_or(src);
+    if can_hardlink(relative) && std::fs::hard_link(src, dst).is_ok() {
+        return Ok(1);
+    }
+    std::fs::copy(src, dst).with_context(|| format!("Failed to copy {}", src.display()))?;
+    Ok(1)
+}
+
+/// Create a directory symlink (a junction-free symlink on Windows)
+pub fn link_dir(target: &Path, link: &Path) -> Result<()> {
+    #[cfg(unix)]
+    let result = std::os::unix::fs::symlink(target, link);
+    #[cfg(windows)]
+    let result = std::os::windows::fs::symlink_dir(target, link);
+
+    result.with_context(|| format!("Failed to link {} to {}", link.display(), target.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::temp::TempDir;
+
+    #[test]
+    fn test_copy_dir_excludes_top_level() {
+        let dir = TempDir::new("copydir");
+        let src = dir.join("src");
+        std::fs::create_dir_all(src.join("mods")).unwrap();
+        std::fs::create_dir_all(src.join("logs")).unwrap();
+        std::fs::create_dir_all(src.join("config/logs")).unwrap();
+        std::fs::write(src.join("mods/a.jar"), b"a").unwrap();
+        std::fs::write(src.join("logs/latest.log"), b"l").unwrap();
+        std::fs::write(src.join("config/logs/keep.txt"), b"k").unwrap();
+        std::fs::write(src.join("options.txt"), b"o").unwrap();
+
+        let dst = dir.join("dst");
+        assert_eq!(copy_dir(&src, &dst, &["logs"]).unwrap(), 3);
+        assert!(dst.join("mods/a.jar").exists());
+        assert!(dst.join("config/logs/keep.txt").exists());
+        assert!(!dst.join("logs").exists());
+    }
+}
@@ -1,8 +1,9 @@
 //! Hash verification utilities
 //!
-//! SHA1 hash verification for downloaded files.
+//! SHA1, SHA256 and SHA512 hash verification for downloaded files.
 
 use sha1::{Digest, Sha1};
+use sha2::{Sha256, Sha512};
 use std::path::Path;
 
 /// Calculate SHA1 hash of a file
@@ -12,6 +13,27 @@ pub fn sha1_file(path: &Path) -> anyhow::Result<String> {
     Ok(format!("{:x}", hash))
 }
 
+/// Calculate SHA1 hash of in-memory data
+pub fn sha1_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha1::digest(bytes))
+}
+
+/// Calculate SHA256 hash of in-memory data
+pub fn sha256_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+/// Calculate SHA512 hash of in-memory data
+pub fn sha512_bytes(bytes: &[u8]) -> String {
+    format!("{:x}", Sha512::digest(bytes))
+}
+
+/// Calculate SHA512 hash of a file
+pub fn sha512_file(path: &Path) -> anyhow::Result<String> {
+    let bytes = std::fs::read(path)?;
+    Ok(sha512_bytes(&bytes))
+}
+
 /// Verify file hash matches expected
 pub fn verify_sha1(path: &Path, expected: &str) -> anyhow::Result<bool> {
     let actual = sha1_file(path)?;
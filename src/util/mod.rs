@@ -3,7 +3,10 @@
 //! Common utilities used across the application.
 
 pub mod download;
+pub mod fs;
 pub mod hash;
+#[cfg(test)]
+pub(crate) mod temp;
 
 use std::path::PathBuf;
 
@@ -0,0 +1,37 @@
+//! Scratch directories for tests
+
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+/// An empty directory under the system temp dir, removed on drop
+pub(crate) struct TempDir(PathBuf);
+
+impl TempDir {
+    /// Create `glauncher-<tag>-<pid>`, clearing leftovers from earlier runs
+    pub(crate) fn new(tag: &str) -> Self {
+        let path = std::env::temp_dir().join(format!("glauncher-{}-{}", tag, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).expect("failed to create temp dir");
+        Self(path)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}